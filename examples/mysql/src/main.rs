@@ -150,7 +150,7 @@ async fn main() {
     println!("\n2. Find users with multiple conditions (active=true AND org=1):");
     let filtered_users = User::builder_select()
         .active(&true).unwrap()
-        .org(&Some(1)).unwrap()
+        .org(&1).unwrap()
         .find_all(&db).await.unwrap();
     println!("Found {} users with org=1 and active=true", filtered_users.len());
 
@@ -209,7 +209,7 @@ async fn main() {
 
     // Example 8: Stream with conditions
     println!("\n8. Stream users with conditions:");
-    let org_ref = Some(1);
+    let org_ref = 1;
     let mut builder = User::builder_select()
         .active(&true).unwrap()
         .org(&org_ref).unwrap()
@@ -240,7 +240,7 @@ async fn main() {
     println!("\n10. Count users with conditions:");
     let user_count = User::builder_select()
         .active(&true).unwrap()
-        .org(&Some(1)).unwrap()
+        .org(&1).unwrap()
         .count(&db).await.unwrap();
     println!("Total count of active users in org 1: {}", user_count);
 
@@ -255,7 +255,7 @@ async fn main() {
 
     // Example 12: Build SQL without executing
     println!("\n12. Build SQL query without executing:");
-    let org_ref2 = Some(1);
+    let org_ref2 = 1;
     let query_builder = User::builder_select()
         .active(&true).unwrap()
         .org(&org_ref2).unwrap()
@@ -269,7 +269,7 @@ async fn main() {
     let update_result = User::builder_update()
         .on_version(&1).unwrap()  // SET version = 1
         .on_updated_by("admin").unwrap()  // SET updated_by = 'admin'
-        .by_org(&Some(1)).unwrap()  // WHERE org = 1
+        .by_org(&1).unwrap()  // WHERE org = 1
         .by_active(&true).unwrap()  // WHERE active = true
         .execute(&db).await.unwrap();
     println!("Updated {} users", update_result);
@@ -296,7 +296,7 @@ async fn main() {
     println!("\n15. Complex query - active users in org 1, ordered by email, limit 5:");
     let complex_users = User::builder_select()
         .active(&true).unwrap()
-        .org(&Some(1)).unwrap()
+        .org(&1).unwrap()
         .id_gte(&1).unwrap()  // ID >= 1
         .email_end_with(".com").unwrap()  // email ends with .com
         .order_by_email_asc().unwrap()
@@ -148,7 +148,7 @@ async fn main() {
     println!("\n2. Find users with multiple conditions (active=true AND org=1):");
     let filtered_users = User::builder_select()
         .active(&true).unwrap()
-        .org(&Some(1)).unwrap()
+        .org(&1).unwrap()
         .find_all(&db).await.unwrap();
     println!("Found {} users with org=1 and active=true", filtered_users.len());
 
@@ -209,7 +209,7 @@ async fn main() {
     println!("\n8. Stream users with conditions:");
     let mut builder = User::builder_select()
         .active(&true).unwrap()
-        .org(&Some(1)).unwrap()
+        .org(&1).unwrap()
         .order_by_email_asc().unwrap();
     let mut user_stream = builder.stream(&db).await;
 
@@ -237,7 +237,7 @@ async fn main() {
     println!("\n10. Count users with conditions:");
     let user_count = User::builder_select()
         .active(&true).unwrap()
-        .org(&Some(1)).unwrap()
+        .org(&1).unwrap()
         .count(&db).await.unwrap();
     println!("Total count of active users in org 1: {}", user_count);
 
@@ -254,7 +254,7 @@ async fn main() {
     println!("\n12. Build SQL query without executing:");
     let query_builder = User::builder_select()
         .active(&true).unwrap()
-        .org(&Some(1)).unwrap()
+        .org(&1).unwrap()
         .email_like("%abc%").unwrap()
         .order_by_id_desc().unwrap();
     let sql = query_builder.build_sql();
@@ -265,7 +265,7 @@ async fn main() {
     let update_result = User::builder_update()
         .on_version(&1).unwrap()  // SET version = 1
         .on_updated_by("admin").unwrap()  // SET updated_by = 'admin'
-        .by_org(&Some(1)).unwrap()  // WHERE org = 1
+        .by_org(&1).unwrap()  // WHERE org = 1
         .by_active(&true).unwrap()  // WHERE active = true
         .execute(&db).await.unwrap();
     println!("Updated {} users", update_result);
@@ -292,7 +292,7 @@ async fn main() {
     println!("\n15. Complex query - active users in org 1, ordered by email, limit 5:");
     let complex_users = User::builder_select()
         .active(&true).unwrap()
-        .org(&Some(1)).unwrap()
+        .org(&1).unwrap()
         .id_gte(&1).unwrap()  // ID >= 1
         .email_end_with(".com").unwrap()  // email ends with .com
         .order_by_email_asc().unwrap()
@@ -203,7 +203,7 @@ async fn main() {
     println!("\n2. Find users with multiple conditions (active=true AND org=1):");
     let filtered_users = User::builder_select()
         .active(&true).unwrap()
-        .org(&Some(org_1.id)).unwrap()
+        .org(&org_1.id).unwrap()
         .find_all(&db).await.unwrap();
     println!("Found {} users with org={} and active=true", filtered_users.len(), org_1.id);
 
@@ -262,7 +262,7 @@ async fn main() {
 
     // Example 8: Stream with conditions
     println!("\n8. Stream users with conditions:");
-    let org_ref = Some(org_1.id);
+    let org_ref = org_1.id;
     let mut builder = User::builder_select()
         .active(&true).unwrap()
         .org(&org_ref).unwrap()
@@ -293,7 +293,7 @@ async fn main() {
     println!("\n10. Count users with conditions:");
     let user_count = User::builder_select()
         .active(&true).unwrap()
-        .org(&Some(org_1.id)).unwrap()
+        .org(&org_1.id).unwrap()
         .count(&db).await.unwrap();
     println!("Total count of active users in org {}: {}", org_1.id, user_count);
 
@@ -308,7 +308,7 @@ async fn main() {
 
     // Example 12: Build SQL without executing
     println!("\n12. Build SQL query without executing:");
-    let org_ref2 = Some(org_1.id);
+    let org_ref2 = org_1.id;
     let query_builder = User::builder_select()
         .active(&true).unwrap()
         .org(&org_ref2).unwrap()
@@ -361,7 +361,7 @@ async fn main() {
     println!("\n18. Complex query - active users in org {}, ordered by email, limit 5:", org_1.id);
     let complex_users = User::builder_select()
         .active(&true).unwrap()
-        .org(&Some(org_1.id)).unwrap()
+        .org(&org_1.id).unwrap()
         .id_gte(&1).unwrap()  // ID >= 1
         .email_end_with(".com").unwrap()  // email ends with .com
         .order_by_email_asc().unwrap()
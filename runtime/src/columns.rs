@@ -0,0 +1,6 @@
+/// Columns selected by projection queries into a struct, implemented by the `Columns` derive
+/// of `sqlx-template` with `#[columns_trait]`
+pub trait Columns {
+    /// Field names of the struct
+    const COLUMNS: &'static [&'static str];
+}
@@ -0,0 +1,336 @@
+use std::error::Error;
+
+/// Error decoding the binary `COPY` data, converted into `sqlx::Error::Decode` by the generated code
+pub type CopyError = Box<dyn Error + Send + Sync + 'static>;
+
+/// Signature starting the binary `COPY` data
+const SIGNATURE: &[u8] = b"PGCOPY\n\xff\r\n\0";
+
+/// Value of a field in the binary `COPY` format of PostgreSQL, decoded by the `copy_out` function
+/// generated by `sqlx-template` with `#[tp_copy_out]`
+pub trait CopyDecode: Sized {
+    /// Decode the value of a field, `None` for NULL
+    fn decode_copy(value: Option<&[u8]>) -> Result<Self, CopyError>;
+}
+
+fn not_null(value: Option<&[u8]>) -> Result<&[u8], CopyError> {
+    value.ok_or_else(|| "unexpected NULL, use an Option field".into())
+}
+
+fn fixed<const N: usize>(value: Option<&[u8]>) -> Result<[u8; N], CopyError> {
+    let value = not_null(value)?;
+    value
+        .try_into()
+        .map_err(|_| format!("expected {N} bytes, found {}", value.len()).into())
+}
+
+impl<T: CopyDecode> CopyDecode for Option<T> {
+    fn decode_copy(value: Option<&[u8]>) -> Result<Self, CopyError> {
+        value.map(|value| T::decode_copy(Some(value))).transpose()
+    }
+}
+
+impl CopyDecode for bool {
+    fn decode_copy(value: Option<&[u8]>) -> Result<Self, CopyError> {
+        Ok(fixed::<1>(value)?[0] != 0)
+    }
+}
+
+macro_rules! impl_copy_decode_be {
+    ($($ty:ty),*) => {
+        $(
+            impl CopyDecode for $ty {
+                fn decode_copy(value: Option<&[u8]>) -> Result<Self, CopyError> {
+                    Ok(<$ty>::from_be_bytes(fixed(value)?))
+                }
+            }
+        )*
+    };
+}
+
+impl_copy_decode_be!(i8, i16, i32, i64, f32, f64);
+
+impl CopyDecode for String {
+    fn decode_copy(value: Option<&[u8]>) -> Result<Self, CopyError> {
+        Ok(std::str::from_utf8(not_null(value)?)?.to_string())
+    }
+}
+
+impl CopyDecode for Vec<u8> {
+    fn decode_copy(value: Option<&[u8]>) -> Result<Self, CopyError> {
+        Ok(not_null(value)?.to_vec())
+    }
+}
+
+#[cfg(feature = "uuid")]
+impl CopyDecode for uuid::Uuid {
+    fn decode_copy(value: Option<&[u8]>) -> Result<Self, CopyError> {
+        Ok(uuid::Uuid::from_bytes(fixed(value)?))
+    }
+}
+
+#[cfg(feature = "chrono")]
+mod chrono_impls {
+    use super::{fixed, CopyDecode, CopyError};
+    use chrono::{DateTime, Duration, NaiveDate, NaiveDateTime, NaiveTime, Utc};
+
+    /// PostgreSQL dates and timestamps count from 2000-01-01
+    fn epoch() -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2000, 1, 1).unwrap().and_hms_opt(0, 0, 0).unwrap()
+    }
+
+    impl CopyDecode for NaiveDate {
+        fn decode_copy(value: Option<&[u8]>) -> Result<Self, CopyError> {
+            let days = i32::from_be_bytes(fixed(value)?);
+            epoch()
+                .date()
+                .checked_add_signed(Duration::days(days.into()))
+                .ok_or_else(|| "date out of range".into())
+        }
+    }
+
+    impl CopyDecode for NaiveTime {
+        fn decode_copy(value: Option<&[u8]>) -> Result<Self, CopyError> {
+            let micros = i64::from_be_bytes(fixed(value)?);
+            Ok(NaiveTime::MIN + Duration::microseconds(micros))
+        }
+    }
+
+    impl CopyDecode for NaiveDateTime {
+        fn decode_copy(value: Option<&[u8]>) -> Result<Self, CopyError> {
+            let micros = i64::from_be_bytes(fixed(value)?);
+            epoch()
+                .checked_add_signed(Duration::microseconds(micros))
+                .ok_or_else(|| "timestamp out of range".into())
+        }
+    }
+
+    impl CopyDecode for DateTime<Utc> {
+        fn decode_copy(value: Option<&[u8]>) -> Result<Self, CopyError> {
+            Ok(NaiveDateTime::decode_copy(value)?.and_utc())
+        }
+    }
+}
+
+/// Fields of a row of the binary `COPY` data
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CopyRow(Vec<Option<Vec<u8>>>);
+
+impl CopyRow {
+    /// Decode the field at `index`
+    pub fn get<T: CopyDecode>(&self, index: usize) -> Result<T, CopyError> {
+        let value = self
+            .0
+            .get(index)
+            .ok_or_else(|| format!("row has no field {index}"))?;
+        T::decode_copy(value.as_deref())
+    }
+}
+
+/// Reader of the rows of binary `COPY ... TO STDOUT` data, which arrives in chunks not aligned to the rows
+#[derive(Debug, Default)]
+pub struct CopyReader {
+    buf: Vec<u8>,
+    // Start of the unread data in `buf`
+    pos: usize,
+    header_read: bool,
+    done: bool,
+}
+
+impl CopyReader {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append a chunk of data
+    pub fn push(&mut self, data: &[u8]) {
+        self.buf.drain(..self.pos);
+        self.pos = 0;
+        self.buf.extend_from_slice(data);
+    }
+
+    /// Read the next complete row, `None` until more data is pushed or after the trailer
+    pub fn next_row(&mut self) -> Result<Option<CopyRow>, CopyError> {
+        if self.done {
+            return Ok(None);
+        }
+        let buf = &self.buf[self.pos..];
+        if !self.header_read {
+            // Signature, flags and length of the header extension area
+            let Some(extension_len) = read_i32(buf, SIGNATURE.len() + 4) else {
+                return Ok(None);
+            };
+            if !buf.starts_with(SIGNATURE) {
+                return Err("invalid binary COPY signature".into());
+            }
+            let header_len = SIGNATURE.len() + 8 + usize::try_from(extension_len)?;
+            if buf.len() < header_len {
+                return Ok(None);
+            }
+            self.pos += header_len;
+            self.header_read = true;
+            return self.next_row();
+        }
+        let Some(field_count) = read_i16(buf, 0) else {
+            return Ok(None);
+        };
+        // Trailer
+        if field_count == -1 {
+            self.done = true;
+            return Ok(None);
+        }
+        let mut pos = 2;
+        let mut fields = Vec::with_capacity(field_count.max(0) as usize);
+        for _ in 0..field_count {
+            let Some(len) = read_i32(buf, pos) else {
+                return Ok(None);
+            };
+            pos += 4;
+            if len < 0 {
+                fields.push(None);
+                continue;
+            }
+            let end = pos + len as usize;
+            if buf.len() < end {
+                return Ok(None);
+            }
+            fields.push(Some(buf[pos..end].to_vec()));
+            pos = end;
+        }
+        self.pos += pos;
+        Ok(Some(CopyRow(fields)))
+    }
+}
+
+fn read_i16(buf: &[u8], pos: usize) -> Option<i16> {
+    Some(i16::from_be_bytes(buf.get(pos..pos + 2)?.try_into().ok()?))
+}
+
+fn read_i32(buf: &[u8], pos: usize) -> Option<i32> {
+    Some(i32::from_be_bytes(buf.get(pos..pos + 4)?.try_into().ok()?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The crate does not depend on sqlx, the binary COPY data is written by hand in big-endian like PostgreSQL does
+
+    /// Binary COPY data of the rows followed by the trailer
+    fn copy_data(rows: &[Vec<Option<Vec<u8>>>]) -> Vec<u8> {
+        let mut data = SIGNATURE.to_vec();
+        // Flags and an empty header extension area
+        data.extend_from_slice(&0i32.to_be_bytes());
+        data.extend_from_slice(&0i32.to_be_bytes());
+        for row in rows {
+            data.extend_from_slice(&(row.len() as i16).to_be_bytes());
+            for field in row {
+                match field {
+                    Some(value) => {
+                        data.extend_from_slice(&(value.len() as i32).to_be_bytes());
+                        data.extend_from_slice(value);
+                    }
+                    None => data.extend_from_slice(&(-1i32).to_be_bytes()),
+                }
+            }
+        }
+        data.extend_from_slice(&(-1i16).to_be_bytes());
+        data
+    }
+
+    fn read_all(reader: &mut CopyReader) -> Vec<CopyRow> {
+        let mut rows = vec![];
+        while let Some(row) = reader.next_row().unwrap() {
+            rows.push(row);
+        }
+        rows
+    }
+
+    #[test]
+    fn test_copy_reader() {
+        let data = copy_data(&[
+            vec![Some(1i64.to_be_bytes().to_vec()), Some(b"first".to_vec()), None],
+            vec![Some(2i64.to_be_bytes().to_vec()), None, Some(vec![1])],
+        ]);
+
+        let mut reader = CopyReader::new();
+        reader.push(&data);
+        let rows = read_all(&mut reader);
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].get::<i64>(0).unwrap(), 1);
+        assert_eq!(rows[0].get::<String>(1).unwrap(), "first");
+        assert_eq!(rows[0].get::<Option<bool>>(2).unwrap(), None);
+        assert_eq!(rows[1].get::<Option<String>>(1).unwrap(), None);
+        assert_eq!(rows[1].get::<Option<bool>>(2).unwrap(), Some(true));
+        assert!(rows[1].get::<String>(1).is_err());
+        assert!(rows[1].get::<i64>(3).is_err());
+        assert!(reader.next_row().unwrap().is_none());
+
+        // Chunks split anywhere, even inside the header or a field, give the same rows
+        let mut reader = CopyReader::new();
+        let mut chunked = vec![];
+        for chunk in data.chunks(3) {
+            reader.push(chunk);
+            chunked.extend(read_all(&mut reader));
+        }
+        assert_eq!(chunked, rows);
+
+        let mut reader = CopyReader::new();
+        reader.push(b"NOTCOPY\n\xff\r\n\0\0\0\0\0\0\0\0\0");
+        assert!(reader.next_row().is_err());
+    }
+
+    #[test]
+    fn test_copy_decode() {
+        fn decode<T: CopyDecode>(value: &[u8]) -> T {
+            T::decode_copy(Some(value)).unwrap()
+        }
+
+        assert!(decode::<bool>(&[1]));
+        assert!(!decode::<bool>(&[0]));
+        assert_eq!(decode::<i8>(&(-3i8).to_be_bytes()), -3);
+        assert_eq!(decode::<i16>(&(-300i16).to_be_bytes()), -300);
+        assert_eq!(decode::<i32>(&0x0102_0304i32.to_be_bytes()), 0x0102_0304);
+        assert_eq!(decode::<i64>(&i64::MIN.to_be_bytes()), i64::MIN);
+        assert_eq!(decode::<f32>(&1.5f32.to_be_bytes()), 1.5);
+        assert_eq!(decode::<f64>(&(-0.25f64).to_be_bytes()), -0.25);
+        assert_eq!(decode::<String>("héllo".as_bytes()), "héllo");
+        assert_eq!(decode::<Vec<u8>>(&[0, 255]), vec![0, 255]);
+        assert_eq!(decode::<Option<i32>>(&7i32.to_be_bytes()), Some(7));
+        assert_eq!(Option::<i32>::decode_copy(None).unwrap(), None);
+
+        assert!(i32::decode_copy(None).is_err());
+        assert!(i32::decode_copy(Some(&[0, 1])).is_err());
+        assert!(String::decode_copy(Some(&[0xff])).is_err());
+    }
+
+    #[cfg(feature = "uuid")]
+    #[test]
+    fn test_copy_decode_uuid() {
+        let uuid = uuid::Uuid::from_u128(0x0123_4567_89ab_cdef_0123_4567_89ab_cdef);
+        assert_eq!(uuid::Uuid::decode_copy(Some(uuid.as_bytes())).unwrap(), uuid);
+        assert!(uuid::Uuid::decode_copy(Some(&[0; 15])).is_err());
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn test_copy_decode_chrono() {
+        use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, Utc};
+
+        // Days and microseconds since 2000-01-01
+        let date = NaiveDate::decode_copy(Some(&(-1i32).to_be_bytes())).unwrap();
+        assert_eq!(date, NaiveDate::from_ymd_opt(1999, 12, 31).unwrap());
+        let date = NaiveDate::decode_copy(Some(&8825i32.to_be_bytes())).unwrap();
+        assert_eq!(date, NaiveDate::from_ymd_opt(2024, 2, 29).unwrap());
+
+        let time = NaiveTime::decode_copy(Some(&45_001_000_250i64.to_be_bytes())).unwrap();
+        assert_eq!(time, NaiveTime::from_hms_micro_opt(12, 30, 1, 250).unwrap());
+
+        let micros = 8825 * 86_400_000_000i64 + 45_001_000_250;
+        let expected = NaiveDate::from_ymd_opt(2024, 2, 29).unwrap().and_hms_micro_opt(12, 30, 1, 250).unwrap();
+        assert_eq!(NaiveDateTime::decode_copy(Some(&micros.to_be_bytes())).unwrap(), expected);
+        assert_eq!(DateTime::<Utc>::decode_copy(Some(&micros.to_be_bytes())).unwrap(), expected.and_utc());
+
+        assert!(NaiveDateTime::decode_copy(Some(&i64::MAX.to_be_bytes())).is_err());
+    }
+}
@@ -0,0 +1,19 @@
+//! Runtime types referenced by the code generated by `sqlx-template`.
+//!
+//! Add this crate to your `Cargo.toml` and set `page_type = true` on a `tp_select_page` or `tp_select_builder`
+//! attribute to have the generated paging function take a [`PageRequest`] and return a [`Page`]. The `Columns`
+//! derive implements [`Columns`] with `#[columns_trait]`, used by the `into` projections and `find_all_as`, and `#[tp_copy_out]` generates
+//! the PostgreSQL `copy_out` functions decoding the rows with [`CopyReader`] and [`CopyDecode`]. The `chrono` and
+//! `uuid` features of this crate decode the date/time and UUID types of those crates. The `serde` feature
+//! re-exports `serde`, used by the keyset pagination cursors generated with `serde = true`.
+
+mod columns;
+mod copy;
+mod page;
+
+pub use columns::Columns;
+pub use copy::{CopyDecode, CopyError, CopyReader, CopyRow};
+pub use page::{Page, PageRequest, PageStart};
+
+#[cfg(feature = "serde")]
+pub use serde;
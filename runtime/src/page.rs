@@ -0,0 +1,170 @@
+/// Where a page starts: a zero-based page number or a row offset
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PageStart {
+    Page(i64),
+    Offset(i64),
+}
+
+/// Page request accepted by the generated paging functions.
+///
+/// The tuple `(offset, size, with_total)` converts into a `PageRequest` for compatibility
+/// with the previous paging API.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PageRequest {
+    pub start: PageStart,
+    pub size: i32,
+    pub with_total: bool,
+}
+
+impl PageRequest {
+    /// Request the zero-based page `page` of `size` rows, with the total count
+    pub fn page(page: i64, size: i32) -> Self {
+        Self {
+            start: PageStart::Page(page),
+            size,
+            with_total: true,
+        }
+    }
+
+    /// Request `size` rows starting at row `offset`, with the total count
+    pub fn offset(offset: i64, size: i32) -> Self {
+        Self {
+            start: PageStart::Offset(offset),
+            size,
+            with_total: true,
+        }
+    }
+
+    /// Whether the total count is queried
+    pub fn with_total(mut self, with_total: bool) -> Self {
+        self.with_total = with_total;
+        self
+    }
+
+    /// Limit the page size to `max_size`
+    pub fn clamp_size(mut self, max_size: i32) -> Self {
+        self.size = self.size.min(max_size);
+        self
+    }
+
+    /// Number of rows of the page, never negative
+    pub fn limit(&self) -> i32 {
+        self.size.max(0)
+    }
+
+    /// Row offset of the first row of the page, never negative
+    pub fn row_offset(&self) -> i64 {
+        let offset = match self.start {
+            PageStart::Page(page) => page.saturating_mul(self.limit() as i64),
+            PageStart::Offset(offset) => offset,
+        };
+        offset.max(0)
+    }
+
+    /// Zero-based page number, an offset in the middle of a page rounds down
+    pub fn page_number(&self) -> i64 {
+        match self.start {
+            PageStart::Page(page) => page.max(0),
+            PageStart::Offset(_) if self.limit() == 0 => 0,
+            PageStart::Offset(_) => self.row_offset() / self.limit() as i64,
+        }
+    }
+}
+
+impl Default for PageRequest {
+    fn default() -> Self {
+        Self::page(0, 20)
+    }
+}
+
+impl From<(i64, i32, bool)> for PageRequest {
+    fn from((offset, size, with_total): (i64, i32, bool)) -> Self {
+        Self::offset(offset, size).with_total(with_total)
+    }
+}
+
+/// Page of rows returned by the generated paging functions
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    /// Total number of rows, only when requested with `with_total`
+    pub total: Option<i64>,
+    /// Zero-based page number
+    pub page: i64,
+    pub size: i32,
+    pub has_next: bool,
+}
+
+impl<T> Page<T> {
+    /// Build the page of `request` from the fetched rows. `items` may hold one extra row,
+    /// fetched to tell whether there is a next page, which is removed
+    pub fn new(mut items: Vec<T>, total: Option<i64>, request: &PageRequest) -> Self {
+        let size = request.limit();
+        let has_next = items.len() > size as usize;
+        items.truncate(size as usize);
+        Self {
+            items,
+            total,
+            page: request.page_number(),
+            size,
+            has_next,
+        }
+    }
+
+    /// Convert the rows of the page
+    pub fn map<U>(self, f: impl FnMut(T) -> U) -> Page<U> {
+        Page {
+            items: self.items.into_iter().map(f).collect(),
+            total: self.total,
+            page: self.page,
+            size: self.size,
+            has_next: self.has_next,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_page_request() {
+        let request = PageRequest::page(2, 10);
+        assert_eq!(request.row_offset(), 20);
+        assert_eq!(request.page_number(), 2);
+        assert!(request.with_total);
+
+        let request = PageRequest::offset(25, 10).with_total(false);
+        assert_eq!(request.row_offset(), 25);
+        assert_eq!(request.page_number(), 2);
+        assert!(!request.with_total);
+
+        let request: PageRequest = (30, 15, true).into();
+        assert_eq!(request.start, PageStart::Offset(30));
+        assert_eq!(request.size, 15);
+        assert_eq!(request.page_number(), 2);
+
+        let request = PageRequest::page(-1, -5);
+        assert_eq!(request.limit(), 0);
+        assert_eq!(request.row_offset(), 0);
+        assert_eq!(request.page_number(), 0);
+
+        assert_eq!(PageRequest::page(1, 50).clamp_size(20).size, 20);
+        assert_eq!(PageRequest::default(), PageRequest::page(0, 20));
+    }
+
+    #[test]
+    fn test_page() {
+        let request = PageRequest::page(1, 3);
+        let page = Page::new(vec![1, 2, 3, 4], Some(10), &request);
+        assert_eq!(page.items, vec![1, 2, 3]);
+        assert_eq!(page.total, Some(10));
+        assert_eq!(page.page, 1);
+        assert_eq!(page.size, 3);
+        assert!(page.has_next);
+
+        let page = Page::new(vec![1, 2], None, &request).map(|x| x * 10);
+        assert_eq!(page.items, vec![10, 20]);
+        assert!(!page.has_next);
+    }
+}
@@ -41,12 +41,22 @@ pub fn derive(ast: DeriveInput) -> syn::Result<TokenStream> {
     }
     let all_str = all_fields_str.join(", ");
     let return_ = "&' static ";
+    let runtime_impl = if ast.attrs.iter().any(|attr| attr.path.is_ident("columns_trait")) {
+        Some(quote! {
+            impl sqlx_template_runtime::Columns for #struct_name {
+                const COLUMNS: &'static [&'static str] = &[#(#all_fields_str),*];
+            }
+        })
+    } else {
+        None
+    };
     let expanded = quote!{
         impl #struct_name {
             pub const fn as_select_all_fields() -> &'static str {
                 #all_str
             }
         }
+        #runtime_impl
     };
 
     Ok(expanded.into())
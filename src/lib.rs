@@ -27,8 +27,39 @@ mod parser;
 ///   - If not configured, no debug logs will be generated.
 /// - `auto`: Applied to fields that should be excluded from the insert statement, typically for auto-incrementing primary keys.
 /// - `db`: Specifies the target database type (e.g., `#[db("postgres")]`, `#[db("mysql")]`, `#[db("sqlite")]`).
-///
-/// Additionally, when using PostgreSQL (`#[db("postgres")]`), the library will generate an `insert_return` function that returns the newly inserted record.
+/// - `tp_insert`: Generates an additional insert function (can be repeated), with the following sub-attributes:
+///   - `fn_name`: The name of the generated function. If empty, the library will automatically generate a function name
+///     (e.g., `insert_or_ignore`, `insert_return_id`).
+///   - `returning`: Can be set to `true` for returning the full record, or specify specific columns (e.g., `returning = "id, created_at"`)
+///     to return a scalar or a tuple. Supported on PostgreSQL and SQLite (3.35+).
+///   - `on_conflict`: Set to `"ignore"` to skip rows that violate a constraint. Generates `ON CONFLICT DO NOTHING` on PostgreSQL,
+///     `INSERT OR IGNORE` on SQLite and `INSERT IGNORE` on MySQL. Combined with `returning`, the function returns an `Option`.
+///   - `debug`: Configures debug logs for the executed query, with priority over `debug_slow`.
+/// - `tp_insert_builder`: Generates an INSERT builder (`builder_insert()`) with `.on_field_name(value)` setters. Only the set
+///   columns are inserted, the others fall back to their database defaults. The builder is executed with `.execute()`, or
+///   `.execute_returning()` on PostgreSQL and SQLite to get the inserted record.
+/// - `tp_tenant`: Scopes the table to a tenant (see `SelectTemplate`). `builder_insert(tenant)` returns a `Result` with a builder
+///   inserting into the tenant, without an `.on_` setter for the tenant column, and `unscoped_builder_insert()` leaves it to its
+///   database default.
+/// - `tp_copy_out`: Generates `copy_out` on PostgreSQL (see below), which needs the `sqlx-template-runtime` crate.
+///
+/// Additionally, when using PostgreSQL (`#[db("postgres")]`) or SQLite (`#[db("sqlite")]`, requires SQLite 3.35+),
+/// the library will generate an `insert_return` function that returns the newly inserted record.
+///
+/// An `insert_many` function is also generated for batch inserts. It builds a multi-row
+/// `INSERT ... VALUES (...), (...)` statement and transparently splits the rows into chunks
+/// that respect the bind parameter limit of the database (65535 for PostgreSQL and MySQL,
+/// 32766 for SQLite), returning the total number of rows affected. On PostgreSQL and SQLite, `insert_many_return`
+/// returns the inserted records. Both take anything implementing `sqlx::Acquire` (a pool, a connection or a transaction)
+/// and run all chunks in one transaction, or a savepoint inside a transaction: when a chunk fails, no row is inserted.
+///
+/// On PostgreSQL, `copy_in` bulk loads rows with `COPY ... FROM STDIN` in the binary format, using the same
+/// columns as `insert`, which is much faster than multi-row inserts for large data sets. Field values are
+/// encoded with their `sqlx::Encode` implementation, so field types must match the column types exactly
+/// (e.g. `i64` for `BIGINT`). With the struct level `#[tp_copy_out]`, `copy_out` exports all columns of the table with
+/// `COPY ... TO STDOUT` in the binary format as a stream of decoded rows. The fields are decoded by the `CopyDecode`
+/// trait of `sqlx-template-runtime`, implemented for the integer, float, `bool`, `String`, `Vec<u8>` and `Option` types,
+/// and the chrono and uuid types with the `chrono` and `uuid` features of the runtime crate, which must be added to `Cargo.toml`.
 ///
 /// # Example
 ///
@@ -58,6 +89,8 @@ mod parser;
 /// #[derive(InsertTemplate, sqlx::FromRow, Debug)]
 /// #[table("users")]
 /// #[db("postgres")]
+/// #[tp_insert(returning = "id")]
+/// #[tp_insert(on_conflict = "ignore", fn_name = "insert_if_absent")]
 /// pub struct UserPg {
 ///     #[auto]
 ///     pub id: i32,
@@ -68,6 +101,18 @@ mod parser;
 /// let user_pg = UserPg { id: 0, email: "john.doe@example.com".to_string(), password: "password123".to_string() };
 /// let new_user = UserPg::insert_return(&user_pg, &pool).await?;
 /// println!("New user: {:?}", new_user);
+///
+/// let new_id: i32 = UserPg::insert_return_id(&user_pg, &pool).await?;
+/// let rows_affected = UserPg::insert_if_absent(&user_pg, &pool).await?;
+///
+/// // Batch insert
+/// let users = vec![user_pg];
+/// let rows_affected = UserPg::insert_many(&users, &pool).await?;
+/// let new_users = UserPg::insert_many_return(&users, &pool).await?;
+///
+/// // Bulk load with COPY
+/// let mut conn = pool.acquire().await?;
+/// let rows_copied = UserPg::copy_in(&users, &mut conn).await?;
 /// # Ok(())
 /// # }
 /// ```
@@ -85,7 +130,7 @@ mod parser;
 ///
 
 
-#[proc_macro_derive(InsertTemplate, attributes(table, auto, debug_slow, db))]
+#[proc_macro_derive(InsertTemplate, attributes(table, tp_insert, tp_insert_builder, tp_copy_out, auto, debug_slow, tp_tenant, db))]
 pub fn insert_derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let input = syn::parse_macro_input!(input as syn::DeriveInput);
     match sqlx_template::insert::derive_insert(&input, None, sqlx_template::Scope::Struct, None) {
@@ -107,16 +152,24 @@ pub fn insert_derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream
 /// - `table`: Specifies the name of the table in the database (mandatory).
 /// - `tp_update`: The main configuration for generating the update function, with the following sub-attributes:
 ///   - `by`: List of columns that will be the update condition, will be the function's input (mandatory and non-empty).
+///     Columns accept the operators of `tp_select_all` except `in` (e.g., `by = "created_at <"` generates `update_by_created_at_lt`).
 ///   - `on`: List of columns that will be updated. If empty, all columns will be updated.
 ///   - `where`: Additional WHERE clause with placeholder support (see Placeholder Mapping in SelectTemplate).
 ///   - `fn_name`: The name of the generated function. If empty, the library will automatically generate a function name.
 ///   - `op_lock`: The name of the column to apply optimistic locking (optional).
-///   - `returning`: Can be set to `true` for returning the full record, or specify specific columns (e.g., `returning = "id, email"`).
+///   - `returning`: Can be set to `true` for returning the full record, or specify specific columns (e.g., `returning = "id, email"`). Supported on PostgreSQL and SQLite (3.35+).
 ///   - `debug_slow`: Configures debug logs for the executed query:
 ///     - If `0`: Only logs the executed query.
 ///     - If `> 0`: Only logs the query if the execution time exceeds the configured value (in milliseconds).
 ///     - If not configured, no debug logs will be generated.
 /// - `debug_slow`: Configures debug logs for the executed query, with priority given to the value in `tp_update`.
+/// - `tp_soft_delete`: Marks the table as soft deleted (see `SelectTemplate`). Updates skip the deleted rows, and the soft
+///   delete column is not updated by the functions updating all columns. The builder includes them with `.with_deleted()`.
+/// - `tp_tenant`: Scopes the table to a tenant (see `SelectTemplate`). Updates take the tenant as their first argument and only
+///   update its rows. The tenant column is never updated, except by `unscoped = true` functions (`unscoped_` name prefix).
+///   `builder_update(tenant)` returns a `Result` with the scoped builder, and `unscoped_builder_update()` updates all tenants.
+/// - `primary_key`: Field attribute marking the primary key, on one or more fields for a composite key (e.g., `#[primary_key]`).
+///   Generates `update_by_pk` like `#[tp_update(by = "<key fields>", fn_name = "update_by_pk")]`.
 /// - `db`: Specifies the target database type (e.g., `#[db("postgres")]`).
 /// - `tp_update_builder`: Builder pattern configuration for UPDATE operations with custom WHERE conditions.
 ///
@@ -176,7 +229,7 @@ pub fn insert_derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream
 /// This macro relies on `sqlx`, so you need to add `sqlx` to your `[dependencies]` in `Cargo.toml`
 /// and properly configure the database connection before using the generated update methods.
 
-#[proc_macro_derive(UpdateTemplate, attributes(table, tp_update, tp_update_builder, debug_slow, db, tp_update_builder))]
+#[proc_macro_derive(UpdateTemplate, attributes(table, tp_update, tp_update_builder, debug_slow, tp_soft_delete, tp_tenant, primary_key, db, tp_update_builder))]
 pub fn update_derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let input = syn::parse_macro_input!(input as syn::DeriveInput);
     match sqlx_template::update::derive_update(&input, None, sqlx_template::Scope::Struct, None) {
@@ -202,13 +255,23 @@ pub fn update_derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream
 ///   - If not configured, no debug logs will be generated.
 /// - `tp_delete`: The main configuration for generating the delete function, with the following sub-attributes:
 ///   - `by`: List of columns that will be the delete condition, will be the function's input (can be empty if `where` is provided).
+///     Columns accept the operators of `tp_select_all` except `in` (e.g., `by = "org, score <="` generates `delete_by_org_and_score_lte`).
 ///   - `where`: Additional WHERE clause with placeholder support (see Placeholder Mapping in SelectTemplate).
 ///   - `fn_name`: The name of the generated function. If empty, the library will automatically generate a function name.
-///   - `returning`: Can be set to `true` for returning the full record, or specify specific columns (e.g., `returning = "id, email"`).
+///   - `returning`: Can be set to `true` for returning the full record, or specify specific columns (e.g., `returning = "id, email"`). Supported on PostgreSQL and SQLite (3.35+).
 ///   - `debug_slow`: Configures debug logs for the executed query:
 ///     - If set to `0`: Only logs the executed query.
 ///     - If set to a value greater than `0`: Only logs the query if the execution time exceeds the configured value (in milliseconds).
 ///     - If not configured, no debug logs will be generated.
+/// - `tp_soft_delete`: Marks the table as soft deleted (see `SelectTemplate`). `tp_delete` then sets the soft delete column to
+///   `CURRENT_TIMESTAMP` on the rows not deleted yet, and also generates `hard_delete_by_{by}` to remove the rows and
+///   `restore_by_{by}` to clear the column of deleted rows (`hard_{fn_name}` and `restore_{fn_name}` with `fn_name`),
+///   both returning the number of affected rows. The builder removes the rows with `.hard_delete()`.
+/// - `tp_tenant`: Scopes the table to a tenant (see `SelectTemplate`). Deletes take the tenant as their first argument and only
+///   delete its rows, except `unscoped = true` functions (`unscoped_` name prefix). `builder_delete(tenant)` returns a `Result`
+///   with the scoped builder, and `unscoped_builder_delete()` deletes the rows of all tenants.
+/// - `primary_key`: Field attribute marking the primary key, on one or more fields for a composite key (e.g., `#[primary_key]`).
+///   Generates `delete_by_pk` like `#[tp_delete(by = "<key fields>", fn_name = "delete_by_pk")]`.
 /// - `db`: Specifies the target database type (e.g., `#[db("postgres")]`).
 /// - `tp_delete_builder`: Builder pattern configuration for DELETE operations with custom WHERE conditions.
 ///
@@ -262,7 +325,7 @@ pub fn update_derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream
 /// and properly configure the database connection before using the generated delete methods.
 ///
 
-#[proc_macro_derive(DeleteTemplate, attributes(table, tp_delete, tp_delete_builder, debug_slow, db, tp_delete_builder))]
+#[proc_macro_derive(DeleteTemplate, attributes(table, tp_delete, tp_delete_builder, debug_slow, tp_soft_delete, tp_tenant, primary_key, db, tp_delete_builder))]
 pub fn delete_derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let input = syn::parse_macro_input!(input as syn::DeriveInput);
     match sqlx_template::delete::derive_delete(&input, None, sqlx_template::Scope::Struct, None) {
@@ -287,16 +350,101 @@ pub fn delete_derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream
 ///   - If not configured, no debug logs will be generated.
 /// - `tp_select_all`: Generates a function that returns all records as a `Vec<T>`. It has the following sub-attributes:
 ///   - `by`: List of columns for the `WHERE` condition, used as function input (can be empty).
+///     A column may be followed by an operator: `!=`, `>`, `>=`, `<`, `<=`, `like` or `ilike` (e.g., `by = "org, created_at >=, score <"`).
+///     Comparisons take the inner type of `Option` columns, `like` and `ilike` take a `&str` pattern, and `ilike` becomes
+///     `LOWER(column) LIKE LOWER(?)` outside of PostgreSQL. The default name uses `{column}_{ne|gt|gte|lt|lte|like|ilike}`
+///     (e.g., `find_by_created_at_gte_and_org_and_score_lt`).
+///     A trailing `?` makes a column optional (e.g., `by = "org, status?, price >=?"`): its argument is an `Option`, and `None`
+///     drops the condition, compiled as `($2 IS NULL OR status = $2)`. The generated doc comment lists the optional columns.
+///     Optional columns are not supported by `tp_update` and `tp_delete`.
+///     A column followed by `in` (e.g., `by = "org, status in"`) takes a slice and matches any of its values, with `= ANY($n)`
+///     on PostgreSQL and an `IN (?, ?, ...)` list expanded at runtime on the other databases. An empty slice returns no rows
+///     without querying the database. The default name uses `{column}_in` (e.g., `find_by_org_and_status_in`).
+///   - `keep_order`: With a single `in` column, returns the rows in the order of the input values (e.g., `keep_order = true`).
+///   - `lock`: Locks the selected rows with `FOR UPDATE`, `FOR SHARE` or `FOR NO KEY UPDATE` (`lock = "update" | "share" | "no_key_update"`),
+///     optionally with `skip_locked = true` or `nowait = true`. Supported on PostgreSQL and MySQL (except `no_key_update`), rejected
+///     at compile time on SQLite and by `tp_select_page`, `tp_select_count` and `tp_select_exists`. The default name ends with the
+///     clause (e.g., `find_by_status_for_update_skip_locked`).
 ///   - `fn_name`: The name of the generated function. If empty, the library will automatically generate a function name.
 ///   - `order`: Adds an `ORDER BY` clause based on the specified columns and order (supports `asc|desc`, default is `asc`).
 ///   - `where`: Additional WHERE clause with placeholder support (see Placeholder Mapping section below).
 ///   - `debug_slow`: Configures debug logs for the executed query.
+///   - `into`: Selects only the columns named by the fields of another struct deriving `Columns` with `#[columns_trait]`
+///     and `FromRow`, and decodes the rows into it (e.g., `into = "UserSummary"`), which needs the `sqlx-template-runtime`
+///     crate. Each of its fields must be a field of this struct, which is checked at compile time. The default name ends with `_as_{struct}` (e.g., `find_by_org_as_user_summary`).
+///   - `with_deleted` / `only_deleted`: With `tp_soft_delete`, also returns the deleted rows or only returns them
+///     (e.g., `with_deleted = true`). The default name ends with `_with_deleted` or `_only_deleted`.
 /// - `tp_select_one`: Similar to `tp_select_all`, but returns a single record as `Option<T>`.
 /// - `tp_select_stream`: Similar to `tp_select_all`, but returns an `impl Stream<Item = T>`.
 /// - `tp_select_count`: Similar to `tp_select_all`, but returns the count of records as `i64`.
+///   With `group_by` (e.g., `group_by = "status"`), it returns the count for each value of the grouped fields as
+///   `Vec<(T, i64)>` ordered by the grouped values, where `T` is the field type (one tuple element per grouped field).
+///   The default name is `count_by_{by}_group_by_{group_by}` (e.g., `count_by_org_group_by_status`).
+/// - `tp_select_exists`: Similar to `tp_select_count`, but returns whether a record exists as `bool` without counting all of them,
+///   using `SELECT EXISTS(...)` (`LIMIT 1` on MySQL). The default name is `exists_by_{by}` (e.g., `exists_by_email`), or `exists_all`
+///   without `by`, which returns whether the table has any record.
+/// - `tp_select_agg`: Generates a function returning an aggregate of a column as `Option<T>` (`None` when no record matches).
+///   It has the following sub-attributes:
+///   - `func`: The aggregate function, one of `sum`, `avg`, `min` or `max` (mandatory).
+///   - `column`: The aggregated field (mandatory). `min` and `max` return the field type. `sum` returns `i64` for integer fields
+///     and `f64` for float fields, `avg` returns `f64`, and both return the field type for decimal fields. An integer sum beyond
+///     the range of `i64` fails the query (PostgreSQL, SQLite) or is truncated (MySQL), use a decimal field for larger sums.
+///   - `by`, `fn_name` and `debug_slow` like `tp_select_all`, without `in` columns. The default name is
+///     `{func}_{column}_by_{by}` (e.g., `sum_amount_by_org` or `max_created_at_by_created_at_gte_and_org`).
 /// - `tp_select_page`: Similar to `tp_select_all`, but accepts pagination parameters and returns a tuple of all records and the total count.
+///   The `max_page_size` sub-attribute limits the number of rows of a page, with priority over the struct-level `max_page_size`.
+///   With `page_type = true`, the function takes an `impl Into<sqlx_template_runtime::PageRequest>` (page number or offset,
+///   size and `with_total`) and returns a `sqlx_template_runtime::Page<T>` with the items, the optional total and `has_next`.
+///   The `(offset, size, with_total)` tuple still converts into a `PageRequest`. The default `find_page_all` keeps the tuple.
+/// - `tp_select_cursor`: Generates a keyset (cursor) pagination function, which stays fast on large tables unlike `OFFSET`.
+///   It accepts `by` (without `in` columns), `fn_name` and `debug_slow` like `tp_select_all`, and a mandatory `order` (e.g., `order = "created_at desc, id desc"`)
+///   whose last column should be unique. `Option` fields are rejected in `order`, since the seek predicate never matches a `NULL`
+///   value. The function takes an optional `{Struct}Cursor` and a `limit`, continues after the cursor
+///   with a `WHERE (created_at, id) < (?, ?)` seek predicate, and returns the rows with the cursor of the next page if there is one.
+///   The `{Struct}Cursor` holds the `order` fields and has `encode` and `decode` methods converting it from and to an opaque
+///   string for clients, which requires the field types to implement `Display` and `FromStr` (`NaiveDateTime` is written in
+///   ISO 8601, which its `FromStr` reads back). With `serde = true` it also derives `Serialize` and `Deserialize`, which needs the `sqlx-template-runtime` crate with its `serde` feature. The default name is `find_after_by_{by}_order_by_{order}`.
 /// - `db`: Specifies the target database type (e.g., `#[db("postgres")]`).
 /// - `tp_select_builder`: Builder pattern configuration for SELECT operations with custom WHERE conditions.
+///   With `cursor = "field1, field2"`, the builder also supports keyset pagination with `.find_after(cursor, limit)` following
+///   its `order_by_*` clauses on those fields, which can not be `Option` fields, and `serde = true` derives serde for the cursor like
+///   `tp_select_cursor`. With `find_all_as = true`, its `.find_all_as::<T>()` selects only the columns of a struct deriving
+///   `Columns` with `#[columns_trait]`, like `into`, which needs the `sqlx-template-runtime` crate. With `page_type = true`, its `.find_page()` takes a `PageRequest` and returns
+///   a `Page<T>` like `tp_select_page`. It also supports row locking with `.for_update()`, `.for_share()` and
+///   `.for_no_key_update()` followed by `.skip_locked()` or `.nowait()` on PostgreSQL and MySQL, except for `.find_page()`.
+///
+/// - `max_page_size`: Limits the number of rows of a page for all paging functions (e.g., `#[max_page_size = 100]`).
+/// - `tp_soft_delete`: Marks the table as soft deleted with a nullable timestamp column (e.g., `#[tp_soft_delete(column = "deleted_at")]`),
+///   which must be a field of the struct.
+///   All generated selects, including `find_all`, `count_all`, aggregates and cursors, only return the rows where the column is `NULL`.
+///   The select builder includes the deleted rows with `.with_deleted()` or only returns them with `.only_deleted()`.
+/// - `tp_tenant`: Scopes the table to a tenant column (e.g., `#[tp_tenant(column = "tenant_id")]`). All generated functions take
+///   the tenant as their first argument (`&str` for a `String` column) and only access its rows, including `find_all(tenant)`,
+///   `count_all(tenant)` and `find_page_all(tenant)`. The names are unchanged, the tenant is not part of `by`.
+///   `unscoped = true` on a `tp_select_*` opts out of the scope and prefixes the default name with `unscoped_`
+///   (e.g., `unscoped_find_by_org`). The builder constructors take the tenant, `builder_select(tenant)` returns a `Result`
+///   with the scoped builder, and `unscoped_builder_select()` accesses the rows of all tenants.
+/// - `primary_key`: Field attribute marking the primary key, on one or more fields for a composite key (e.g., `#[primary_key]`).
+///   It generates `find_by_pk` returning an `Option<T>`, `get_by_pk` failing with `sqlx::Error::RowNotFound` instead,
+///   `exists_by_pk`, and the instance method `reload(&mut self, conn)` reading the record again by the key of its fields.
+///   The key fields are the arguments sorted by name, like `by` fields, after the tenant of a `tp_tenant` table.
+/// - `tp_belongs_to`: Declares that a field references the key of another struct deriving `TableName`
+///   (e.g., `#[tp_belongs_to(Organization, fk = "org_id")]`). It generates `load_{name}(&self, conn)` returning an
+///   `Option` of the target, and `load_{name}s_for(&[Self], conn)` loading the targets of many records with a single
+///   `IN` query (`= ANY($1)` on PostgreSQL, one query per chunk of keys within the bind parameter limit on the other databases)
+///   into a `HashMap` by key, where `name` is `fk` without its `_id` suffix.
+///   It accepts `references` for the key field of the target (default `id`) and `name`. `None` keys are not loaded.
+///   The `_for` loaders take anything implementing `sqlx::Acquire` (a pool, a connection or a transaction).
+/// - `tp_has_many`: Declares that the rows of another struct deriving `TableName` reference this struct
+///   (e.g., `#[tp_has_many(Order, fk = "user_id")]`). It generates `load_{name}(&self, conn)` returning a `Vec`
+///   of the target, and `load_{name}_for(&[Self], conn)` loading the children of many records with a single query
+///   (chunked like the `tp_belongs_to` loaders) into a `HashMap` of `Vec` by key, where `name` is the plural snake case target (e.g., `load_orders_for`).
+///   It accepts `key` for the referenced field of this struct (default the single `primary_key` field, or `id`) and `name`.
+///
+/// The loaders skip the deleted rows of a `tp_soft_delete` target. The loaders of a `tp_tenant` struct take the tenant
+/// after the receiver and only load the rows of a target in the same tenant, unless the relation is declared with
+/// `unscoped = true`, which generates `unscoped_load_*` without the tenant instead. Whether the target is scoped by
+/// `tp_tenant` is checked at compile time.
 ///
 /// The `debug_slow` attribute at the struct level has priority over the value in `tp_select_*`.
 ///
@@ -446,7 +594,7 @@ pub fn delete_derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream
 /// and properly configure the database connection before using the generated query methods.
 ///
 
-#[proc_macro_derive(SelectTemplate, attributes(table, debug_slow, tp_select_all, tp_select_one, tp_select_page, tp_select_stream, tp_select_count, tp_select_builder, db, tp_select_builder, auto))]
+#[proc_macro_derive(SelectTemplate, attributes(table, debug_slow, max_page_size, tp_soft_delete, tp_tenant, tp_select_all, tp_select_one, tp_select_page, tp_select_stream, tp_select_count, tp_select_exists, tp_select_agg, tp_select_cursor, tp_belongs_to, tp_has_many, tp_select_builder, db, tp_select_builder, auto, primary_key))]
 pub fn select_derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let input = syn::parse_macro_input!(input as syn::DeriveInput);
     match sqlx_template::select::derive_select(&input, None, sqlx_template::Scope::Struct, None) {
@@ -466,6 +614,8 @@ pub fn select_derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream
 ///
 /// `Columns` accepts the following attributes:
 /// - `group`: Groups fields together for specific operations (optional).
+/// - `columns_trait`: Also implements `sqlx_template_runtime::Columns`, needed by `into` projections and the builder's
+///   `.find_all_as::<T>()` (optional).
 ///
 /// # Generated Functions
 ///
@@ -500,7 +650,7 @@ pub fn select_derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream
 /// This macro is useful for maintaining consistency between struct field names
 /// and database column names, and provides compile-time safety when referencing columns.
 ///
-#[proc_macro_derive(Columns, attributes(group))]
+#[proc_macro_derive(Columns, attributes(group, columns_trait))]
 pub fn columns_derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let input = syn::parse_macro_input!(input as syn::DeriveInput);
     match columns::derive(input) {
@@ -590,8 +740,11 @@ pub fn ddl_derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
 ///   - `update`: List of columns that will be updated on conflict. If empty, all non-conflict columns will be updated.
 ///   - `where`: Additional WHERE clause for the ON CONFLICT DO UPDATE with placeholder support (see Placeholder Mapping in SelectTemplate).
 ///   - `fn_name`: The name of the generated function. If empty, the library will automatically generate a function name.
-///   - `returning`: If set to true, the generated function will return the upserted record (PostgreSQL only).
+///   - `returning`: If set to true, the generated function will return the upserted record (PostgreSQL and SQLite).
 ///   - `debug_slow`: Configures debug logs for the executed query (overrides struct-level setting).
+/// - `tp_tenant`: Scopes the table to a tenant (see `SelectTemplate`). Upserts take the tenant as their first argument and insert
+///   the row for it, ignoring the tenant field of the record. A conflicting row of another tenant is left unchanged, and the tenant
+///   column is never updated. `unscoped = true` upserts (`unscoped_` name prefix) use the tenant field of the record.
 ///
 /// # Database Support
 ///
@@ -659,7 +812,7 @@ pub fn ddl_derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
 /// This macro relies on `sqlx` and database-specific upsert syntax. Make sure your target
 /// database supports the generated upsert statements.
 ///
-#[proc_macro_derive(UpsertTemplate, attributes(table, tp_upsert, debug_slow, db))]
+#[proc_macro_derive(UpsertTemplate, attributes(table, tp_upsert, debug_slow, tp_tenant, db))]
 pub fn upsert_derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let input = syn::parse_macro_input!(input as syn::DeriveInput);
     match sqlx_template::upsert::derive_upsert(&input, None, sqlx_template::Scope::Struct, None) {
@@ -680,11 +833,16 @@ pub fn upsert_derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream
 /// - `table`: Specifies the name of the table in the database (mandatory).
 /// - `debug_slow`: Global debug configuration for all generated functions.
 /// - `auto`: Applied to fields that should be excluded from insert statements.
+/// - `primary_key`: Applied to the primary key fields, generating the `*_by_pk` functions and `reload`.
+/// - `tp_insert`: Insert operation configurations.
 /// - `tp_select_all`, `tp_select_one`, `tp_select_page`, `tp_select_stream`, `tp_select_count`: Select operation configurations.
 /// - `tp_update`: Update operation configurations.
 /// - `tp_delete`: Delete operation configurations.
 /// - `tp_upsert`: Upsert operation configurations.
-/// - `tp_select_builder`, `tp_update_builder`, `tp_delete_builder`: Builder pattern configurations.
+/// - `tp_select_builder`, `tp_insert_builder`, `tp_update_builder`, `tp_delete_builder`: Builder pattern configurations.
+/// - `tp_soft_delete`: Soft delete configuration (see `SelectTemplate` and `DeleteTemplate`).
+/// - `tp_tenant`: Tenant scope configuration (see `SelectTemplate`).
+/// - `tp_belongs_to`, `tp_has_many`: Relation loaders (see `SelectTemplate`).
 /// - `db`: Specifies the target database type.
 ///
 #[doc = include_str!("../docs/builder_pattern.md")]
@@ -692,7 +850,8 @@ pub fn upsert_derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream
 /// # Generated Functions
 ///
 /// This macro generates all functions from the individual templates:
-/// - Insert operations: `insert()`, `insert_return()` (PostgreSQL only)
+/// - Insert operations: `insert()`, `insert_many()`, `insert_return()` and `insert_many_return()` (PostgreSQL and SQLite),
+///   `copy_in()` and `copy_out()` with `#[tp_copy_out]` (PostgreSQL), plus `tp_insert` configurations
 /// - Update operations: Based on `tp_update` configurations
 /// - Select operations: Based on `tp_select_*` configurations, plus default `find_all()`, `count_all()`, `find_page_all()`
 /// - Delete operations: Based on `tp_delete` configurations
@@ -750,7 +909,7 @@ pub fn upsert_derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream
 /// This is the most convenient macro to use when you need comprehensive database operations
 /// for a struct. It combines all individual template macros into one.
 ///
-#[proc_macro_derive(SqlxTemplate, attributes(table, tp_upsert, tp_select_all, tp_select_one, tp_select_page, tp_select_stream, tp_select_count, tp_update, tp_delete, tp_update_builder, tp_select_builder, tp_delete_builder, auto, debug_slow, db))]
+#[proc_macro_derive(SqlxTemplate, attributes(table, tp_insert, tp_insert_builder, tp_copy_out, tp_upsert, tp_select_all, tp_select_one, tp_select_page, tp_select_stream, tp_select_count, tp_select_exists, tp_select_agg, tp_select_cursor, tp_belongs_to, tp_has_many, tp_update, tp_delete, tp_update_builder, tp_select_builder, tp_delete_builder, auto, primary_key, debug_slow, max_page_size, tp_soft_delete, tp_tenant, db))]
 pub fn sqlx_derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let input = syn::parse_macro_input!(input as syn::DeriveInput);
     match sqlx_template::derive_all(&input, None, sqlx_template::Scope::Struct, None) {
@@ -771,11 +930,16 @@ pub fn sqlx_derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
 /// - `table`: Specifies the name of the table in the database (mandatory).
 /// - `debug_slow`: Global debug configuration for all generated functions.
 /// - `auto`: Applied to fields that should be excluded from insert statements.
+/// - `primary_key`: Applied to the primary key fields, generating the `*_by_pk` functions and `reload`.
+/// - `tp_insert`: Insert operation configurations.
 /// - `tp_select_all`, `tp_select_one`, `tp_select_page`, `tp_select_stream`, `tp_select_count`: Select operation configurations.
 /// - `tp_update`: Update operation configurations.
 /// - `tp_delete`: Delete operation configurations.
 /// - `tp_upsert`: Upsert operation configurations.
-/// - `tp_select_builder`, `tp_update_builder`, `tp_delete_builder`: Builder pattern configurations.
+/// - `tp_select_builder`, `tp_insert_builder`, `tp_update_builder`, `tp_delete_builder`: Builder pattern configurations.
+/// - `tp_soft_delete`: Soft delete configuration (see `SelectTemplate` and `DeleteTemplate`).
+/// - `tp_tenant`: Tenant scope configuration (see `SelectTemplate`).
+/// - `tp_belongs_to`, `tp_has_many`: Relation loaders (see `SelectTemplate`).
 ///
 #[doc = include_str!("../docs/builder_pattern.md")]
 ///
@@ -831,7 +995,7 @@ pub fn sqlx_derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
 /// This macro is specifically designed for PostgreSQL and may not work with other databases.
 /// Use `SqlxTemplate` for database-agnostic code or other database-specific templates for other databases.
 ///
-#[proc_macro_derive(PostgresTemplate, attributes(table, tp_upsert, tp_select_all, tp_select_one, tp_select_page, tp_select_stream, tp_select_count, tp_update, tp_delete, auto, debug_slow, tp_select_builder, tp_update_builder, tp_delete_builder))]
+#[proc_macro_derive(PostgresTemplate, attributes(table, tp_insert, tp_insert_builder, tp_copy_out, tp_upsert, tp_select_all, tp_select_one, tp_select_page, tp_select_stream, tp_select_count, tp_select_exists, tp_select_agg, tp_select_cursor, tp_belongs_to, tp_has_many, tp_update, tp_delete, auto, primary_key, debug_slow, max_page_size, tp_soft_delete, tp_tenant, tp_select_builder, tp_update_builder, tp_delete_builder))]
 pub fn postgres_derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let input = syn::parse_macro_input!(input as syn::DeriveInput);
     match sqlx_template::derive_all(&input, None, sqlx_template::Scope::Struct, Some(Database::Postgres)) {
@@ -852,11 +1016,16 @@ pub fn postgres_derive(input: proc_macro::TokenStream) -> proc_macro::TokenStrea
 /// - `table`: Specifies the name of the table in the database (mandatory).
 /// - `debug_slow`: Global debug configuration for all generated functions.
 /// - `auto`: Applied to fields that should be excluded from insert statements.
+/// - `primary_key`: Applied to the primary key fields, generating the `*_by_pk` functions and `reload`.
+/// - `tp_insert`: Insert operation configurations.
 /// - `tp_select_all`, `tp_select_one`, `tp_select_page`, `tp_select_stream`, `tp_select_count`: Select operation configurations.
 /// - `tp_update`: Update operation configurations.
 /// - `tp_delete`: Delete operation configurations.
 /// - `tp_upsert`: Upsert operation configurations.
-/// - `tp_select_builder`, `tp_update_builder`, `tp_delete_builder`: Builder pattern configurations.
+/// - `tp_select_builder`, `tp_insert_builder`, `tp_update_builder`, `tp_delete_builder`: Builder pattern configurations.
+/// - `tp_soft_delete`: Soft delete configuration (see `SelectTemplate` and `DeleteTemplate`).
+/// - `tp_tenant`: Tenant scope configuration (see `SelectTemplate`).
+/// - `tp_belongs_to`, `tp_has_many`: Relation loaders (see `SelectTemplate`).
 ///
 #[doc = include_str!("../docs/builder_pattern.md")]
 ///
@@ -913,7 +1082,7 @@ pub fn postgres_derive(input: proc_macro::TokenStream) -> proc_macro::TokenStrea
 /// This macro is specifically designed for MySQL and generates MySQL-compatible SQL syntax.
 /// Use `SqlxTemplate` for database-agnostic code or other database-specific templates for other databases.
 ///
-#[proc_macro_derive(MysqlTemplate, attributes(table, tp_upsert, tp_select_all, tp_select_one, tp_select_page, tp_select_stream, tp_select_count, tp_update, tp_delete, auto, debug_slow, tp_select_builder, tp_update_builder, tp_delete_builder))]
+#[proc_macro_derive(MysqlTemplate, attributes(table, tp_insert, tp_insert_builder, tp_upsert, tp_select_all, tp_select_one, tp_select_page, tp_select_stream, tp_select_count, tp_select_exists, tp_select_agg, tp_select_cursor, tp_belongs_to, tp_has_many, tp_update, tp_delete, auto, primary_key, debug_slow, max_page_size, tp_soft_delete, tp_tenant, tp_select_builder, tp_update_builder, tp_delete_builder))]
 pub fn mysql_derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let input = syn::parse_macro_input!(input as syn::DeriveInput);
     match sqlx_template::derive_all(&input, None, sqlx_template::Scope::Struct, Some(Database::Mysql)) {
@@ -934,11 +1103,14 @@ pub fn mysql_derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
 /// - `table`: Specifies the name of the table in the database (mandatory).
 /// - `debug_slow`: Global debug configuration for all generated functions.
 /// - `auto`: Applied to fields that should be excluded from insert statements.
+/// - `primary_key`: Applied to the primary key fields, generating the `*_by_pk` functions and `reload`.
+/// - `tp_insert`: Insert operation configurations.
 /// - `tp_select_all`, `tp_select_one`, `tp_select_page`, `tp_select_stream`, `tp_select_count`: Select operation configurations.
 /// - `tp_update`: Update operation configurations.
 /// - `tp_delete`: Delete operation configurations.
 /// - `tp_upsert`: Upsert operation configurations.
 /// - `tp_select_builder`: Builder pattern configuration for SELECT operations.
+/// - `tp_insert_builder`: Builder pattern configuration for INSERT operations.
 /// - `tp_update_builder`: Builder pattern configuration for UPDATE operations.
 /// - `tp_delete_builder`: Builder pattern configuration for DELETE operations.
 ///
@@ -1010,7 +1182,7 @@ pub fn mysql_derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
 /// This macro is specifically designed for SQLite and generates SQLite-compatible SQL syntax.
 /// Use `SqlxTemplate` for database-agnostic code or other database-specific templates for other databases.
 ///
-#[proc_macro_derive(SqliteTemplate, attributes(table, tp_upsert, tp_select_all, tp_select_one, tp_select_page, tp_select_stream, tp_select_count, tp_update, tp_delete, auto, debug_slow, tp_select_builder, tp_update_builder, tp_delete_builder))]
+#[proc_macro_derive(SqliteTemplate, attributes(table, tp_insert, tp_insert_builder, tp_upsert, tp_select_all, tp_select_one, tp_select_page, tp_select_stream, tp_select_count, tp_select_exists, tp_select_agg, tp_select_cursor, tp_belongs_to, tp_has_many, tp_update, tp_delete, auto, primary_key, debug_slow, max_page_size, tp_soft_delete, tp_tenant, tp_select_builder, tp_update_builder, tp_delete_builder))]
 pub fn sqlite_derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let input = syn::parse_macro_input!(input as syn::DeriveInput);
     match sqlx_template::derive_all(&input, None, sqlx_template::Scope::Struct, Some(Database::Sqlite)) {
@@ -1030,11 +1202,16 @@ pub fn sqlite_derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream
 /// - `table`: Specifies the name of the table in the database (mandatory).
 /// - `debug_slow`: Global debug configuration for all generated functions.
 /// - `auto`: Applied to fields that should be excluded from insert statements.
+/// - `primary_key`: Applied to the primary key fields, generating the `*_by_pk` functions and `reload`.
+/// - `tp_insert`: Insert operation configurations.
 /// - `tp_select_all`, `tp_select_one`, `tp_select_page`, `tp_select_stream`, `tp_select_count`: Select operation configurations.
 /// - `tp_update`: Update operation configurations.
 /// - `tp_delete`: Delete operation configurations.
 /// - `tp_upsert`: Upsert operation configurations.
-/// - `tp_select_builder`, `tp_update_builder`, `tp_delete_builder`: Builder pattern configurations.
+/// - `tp_select_builder`, `tp_insert_builder`, `tp_update_builder`, `tp_delete_builder`: Builder pattern configurations.
+/// - `tp_soft_delete`: Soft delete configuration (see `SelectTemplate` and `DeleteTemplate`).
+/// - `tp_tenant`: Tenant scope configuration (see `SelectTemplate`).
+/// - `tp_belongs_to`, `tp_has_many`: Relation loaders (see `SelectTemplate`).
 ///
 #[doc = include_str!("../docs/builder_pattern.md")]
 ///
@@ -1091,7 +1268,7 @@ pub fn sqlite_derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream
 /// of database-specific optimizations. Use database-specific templates for better performance
 /// when targeting a single database type.
 ///
-#[proc_macro_derive(AnyTemplate, attributes(table, tp_upsert, tp_select_all, tp_select_one, tp_select_page, tp_select_stream, tp_select_count, tp_update, tp_delete, auto, debug_slow))]
+#[proc_macro_derive(AnyTemplate, attributes(table, tp_insert, tp_insert_builder, tp_upsert, tp_select_all, tp_select_one, tp_select_page, tp_select_stream, tp_select_count, tp_select_exists, tp_select_agg, tp_select_cursor, tp_belongs_to, tp_has_many, tp_update, tp_delete, auto, primary_key, debug_slow, max_page_size, tp_soft_delete, tp_tenant))]
 pub fn any_derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let input = syn::parse_macro_input!(input as syn::DeriveInput);
     match sqlx_template::derive_all(&input, None, sqlx_template::Scope::Struct, Some(Database::Any)) {
@@ -1173,10 +1350,13 @@ pub fn tp_gen(args: TokenStream, item: TokenStream) -> proc_macro::TokenStream {
 /// # Attributes
 ///
 /// - `table`: Specifies the name of the table as a string (e.g., `#[table("users")]`).
+/// - `tp_soft_delete` and `tp_tenant`: The struct-level filters of the rows, read by the relation loaders of other structs.
 ///
 /// # Function Signature
 ///
-/// The macro generates a const function named `table_name()` which returns a `&'static str` containing the table name.
+/// The macro generates a const function named `table_name()` which returns a `&'static str` containing the table name,
+/// and the const functions `soft_delete_condition()` and `tenant_column()` returning the condition skipping the deleted
+/// rows (e.g., `Some("deleted_at IS NULL")`) and the tenant column, `None` without `tp_soft_delete` and `tp_tenant`.
 ///
 /// # Example Usage
 ///
@@ -1200,10 +1380,10 @@ pub fn tp_gen(args: TokenStream, item: TokenStream) -> proc_macro::TokenStream {
 ///
 /// This macro is often used in combination with other sqlx-template macros to provide
 /// a consistent way to reference table names throughout your application.
-#[proc_macro_derive(TableName, attributes(table))]
+#[proc_macro_derive(TableName, attributes(table, tp_soft_delete, tp_tenant))]
 pub fn table_name_derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let input = syn::parse_macro_input!(input as syn::DeriveInput);
-    match sqlx_template::table_name_derive(&input) {
+    match sqlx_template::table_name_derive(&input, None) {
         Ok(ok) => ok,
         Err(err) => err.to_compile_error().into(),
     }
@@ -1388,6 +1568,7 @@ pub fn sqlite_multi_query(args: TokenStream, item: TokenStream) -> proc_macro::T
 ///
 /// - **Paged Records:**
 ///   - `Page<T>`: Returns paginated results. Requires an additional parameter for pagination (e.g., `impl Into<(i64, i32, bool)>`). The function returns a tuple `(Vec<T>, Option<i64>)`, where the vector contains the paginated records, and the optional value represents the total number of records if requested.
+///   - `sqlx_template_runtime::Page<T>`: Like `Page<T>`, but takes an `impl Into<sqlx_template_runtime::PageRequest>` and returns a `sqlx_template_runtime::Page<T>`.
 ///
 /// - **Scalar Value:**
 ///   - `Scalar<T>`: Returns a single scalar value from the query.
@@ -1539,6 +1720,7 @@ pub fn sqlite_query(args: TokenStream, item: TokenStream) -> proc_macro::TokenSt
 ///
 /// - **Paged Records:**
 ///   - `Page<T>`: Returns paginated results. Requires an additional parameter for pagination (e.g., `impl Into<(i64, i32, bool)>`). The function returns a tuple `(Vec<T>, Option<i64>)`, where the vector contains the paginated records, and the optional value represents the total number of records if requested.
+///   - `sqlx_template_runtime::Page<T>`: Like `Page<T>`, but takes an `impl Into<sqlx_template_runtime::PageRequest>` and returns a `sqlx_template_runtime::Page<T>`.
 ///
 /// - **Scalar Value:**
 ///   - `Scalar<T>`: Returns a single scalar value from the query.
@@ -1672,6 +1854,7 @@ pub fn sqlite_select(args: TokenStream, item: TokenStream) -> proc_macro::TokenS
 ///
 /// - **Paged Records:**
 ///   - `Page<T>`: Returns paginated results. Requires an additional parameter for pagination (e.g., `impl Into<(i64, i32, bool)>`). The function returns a tuple `(Vec<T>, Option<i64>)`, where the vector contains the paginated records, and the optional value represents the total number of records if requested.
+///   - `sqlx_template_runtime::Page<T>`: Like `Page<T>`, but takes an `impl Into<sqlx_template_runtime::PageRequest>` and returns a `sqlx_template_runtime::Page<T>`.
 ///
 /// - **Scalar Value:**
 ///   - `Scalar<T>`: Returns a single scalar value from the query.
@@ -1804,6 +1987,7 @@ pub fn sqlite_update(args: TokenStream, item: TokenStream) -> proc_macro::TokenS
 ///
 /// - **Paged Records:**
 ///   - `Page<T>`: Returns paginated results. Requires an additional parameter for pagination (e.g., `impl Into<(i64, i32, bool)>`). The function returns a tuple `(Vec<T>, Option<i64>)`, where the vector contains the paginated records, and the optional value represents the total number of records if requested.
+///   - `sqlx_template_runtime::Page<T>`: Like `Page<T>`, but takes an `impl Into<sqlx_template_runtime::PageRequest>` and returns a `sqlx_template_runtime::Page<T>`.
 ///
 /// - **Scalar Value:**
 ///   - `Scalar<T>`: Returns a single scalar value from the query.
@@ -1938,6 +2122,7 @@ pub fn sqlite_insert(args: TokenStream, item: TokenStream) -> proc_macro::TokenS
 ///
 /// - **Paged Records:**
 ///   - `Page<T>`: Returns paginated results. Requires an additional parameter for pagination (e.g., `impl Into<(i64, i32, bool)>`). The function returns a tuple `(Vec<T>, Option<i64>)`, where the vector contains the paginated records, and the optional value represents the total number of records if requested.
+///   - `sqlx_template_runtime::Page<T>`: Like `Page<T>`, but takes an `impl Into<sqlx_template_runtime::PageRequest>` and returns a `sqlx_template_runtime::Page<T>`.
 ///
 /// - **Scalar Value:**
 ///   - `Scalar<T>`: Returns a single scalar value from the query.
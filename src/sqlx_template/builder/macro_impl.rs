@@ -1,3 +1,5 @@
+use std::collections::HashSet;
+
 use proc_macro2::{TokenStream, Literal};
 use quote::{quote, ToTokens};
 use syn::{DeriveInput, Data, Fields, Field, Type as SynType, Ident};
@@ -39,6 +41,12 @@ pub fn impl_select_builder(input: &DeriveInput, config: &super::BuilderConfig) -
     let args_struct_name = quote::format_ident!("{}QueryBuilderArgs", struct_name);
     let table_name = &config.table_name;
     let database_type = get_database_type(config.database);
+    let super::super::PageCode {
+        request: page_request,
+        response: page_response,
+        prelude: page_prelude,
+        result: page_result,
+    } = super::super::gen_page_code(&quote! { #struct_name }, config.max_page_size, config.page_type);
 
     // Parse fields to generate methods
     let fields = match &input.data {
@@ -59,11 +67,47 @@ pub fn impl_select_builder(input: &DeriveInput, config: &super::BuilderConfig) -
         generate_order_methods(field, config.database)
     }).collect::<Vec<_>>();
 
+    // Generate aggregate terminals
+    let aggregate_methods = fields.iter().map(|field| {
+        generate_aggregate_methods(field, config.database)
+    }).collect::<Vec<_>>();
+
+    // Generate grouped count terminals
+    let group_count_methods = fields.iter().map(|field| {
+        generate_group_count_method(field, config.database)
+    }).collect::<Vec<_>>();
+
+    // Generate single column terminals
+    let select_column_methods = fields.iter().map(|field| {
+        generate_select_column_method(field, &quote::format_ident!("select_{}", field.ident.as_ref().unwrap()), config.database)
+    }).collect::<Vec<_>>();
+
+    // `pluck_id` selects the single primary key, then the `id` field, and is not generated without both
+    let id_field = match super::super::get_primary_key_fields(input).as_slice() {
+        [pk_field] => Some(pk_field.clone()),
+        _ => fields.iter().find(|field| field.ident.as_ref().is_some_and(|x| x == "id")).cloned(),
+    };
+    let pluck_id_method = id_field.map(|field| {
+        generate_select_column_method(&field, &quote::format_ident!("pluck_id"), config.database)
+    });
+
+    let group_methods = generate_group_methods();
+
     // Generate custom condition methods
     let custom_methods = config.custom_conditions.iter().map(|condition| {
         generate_custom_condition_method(condition, config.database, &config.fields)
     }).collect::<Vec<_>>();
 
+    // Fields of the struct passed to `project`, so that unknown fields do not compile
+    let column_enum_name = quote::format_ident!("{}Column", struct_name);
+    let column_variants = config.fields.iter().map(|field| {
+        quote::format_ident!("{}", super::to_pascal_case(&get_field_name(field)))
+    }).collect::<Vec<_>>();
+    let column_literals = config.fields.iter().map(|field| {
+        Literal::string(&get_field_name_as_column(field, config.database))
+    }).collect::<Vec<_>>();
+    let column_enum_doc = Literal::string(&format!("Fields of `{}` selected by `project`", struct_name));
+
     // Generate column list for SELECT
     let column_names = config.fields.iter().map(|field| {
         crate::sqlx_template::get_field_name_as_column(field, config.database)
@@ -77,11 +121,167 @@ pub fn impl_select_builder(input: &DeriveInput, config: &super::BuilderConfig) -
     let count_base_sql = format!("SELECT COUNT(*) FROM {}", config.table_name);
     let count_base_literal = proc_macro2::Literal::string(&count_base_sql);
 
+    let (exists_base_sql, fetch_exists) = match config.database {
+        Database::Postgres | Database::Sqlite => (
+            format!("SELECT EXISTS(SELECT 1 FROM {}", config.table_name),
+            quote! {
+                sql.push(')');
+                sqlx::query_scalar_with(&sql, *self.where_args.0).fetch_one(executor).await
+            },
+        ),
+        // Stops at the first row, like EXISTS
+        Database::Mysql | Database::Any => (
+            format!("SELECT 1 FROM {}", config.table_name),
+            quote! {
+                sql.push_str(" LIMIT 1");
+                Ok(sqlx::query_with(&sql, *self.where_args.0).fetch_optional(executor).await?.is_some())
+            },
+        ),
+    };
+    let exists_base_literal = proc_macro2::Literal::string(&exists_base_sql);
+
     // Generate placeholder replacement function based on database type
     let placeholder_replacement_fn = generate_placeholder_replacement_fn(config.database);
+    let (constructor_doc, constructor_params, constructor_return, constructor_body, unscoped_constructor) =
+        generate_builder_constructor(config, &builder_name, "builder_select", None);
+
+    // Row locking, which SQLite does not support
+    let lock_methods = generate_lock_methods(config.database);
+    let (soft_delete_field, soft_delete_init, soft_delete_clone, soft_delete_conditions) = generate_soft_delete_scope(config);
+    let soft_delete_methods = generate_soft_delete_select_methods(config);
+
+    // Projection into a struct implementing `sqlx_template_runtime::Columns`, which needs the runtime crate
+    let find_all_as = if config.find_all_as {
+        let all_fields = config.fields.iter().collect::<Vec<_>>();
+        let columns = quote! { <T as sqlx_template_runtime::Columns>::COLUMNS };
+        // Checked when the generic function is instantiated, as the columns depend on `T`
+        let check = crate::sqlx_template::gen_projection_check(&columns, "the projection", &struct_name.to_string(), &all_fields);
+        let select_list = crate::sqlx_template::gen_projection_select_list(&columns, &all_fields, config.database);
+        Some(quote! {
+            /// Execute query and return all results as `T`, selecting only the columns named by its `Columns` derive
+            pub async fn find_all_as<'c, T>(
+                self,
+                executor: impl sqlx::Executor<'c, Database = #database_type>,
+            ) -> Result<Vec<T>, sqlx::Error>
+            where
+                T: sqlx_template_runtime::Columns + for<'r> sqlx::FromRow<'r, <#database_type as sqlx::Database>::Row> + Send + Unpin,
+            {
+                const #check;
+                let sql = self.build_projection_sql(&#select_list);
+                sqlx::query_as_with(&sql, *self.query_args()?.0)
+                    .fetch_all(executor)
+                    .await
+            }
+        })
+    } else {
+        None
+    };
+
+    // LIMIT and OFFSET placeholders, numbered after the WHERE arguments on PostgreSQL
+    let limit_placeholder_fn = match config.database {
+        Database::Postgres => quote! {
+            fn limit_placeholder(index: usize) -> String {
+                format!("${}", index)
+            }
+        },
+        Database::Sqlite | Database::Mysql | Database::Any => quote! {
+            fn limit_placeholder(_index: usize) -> String {
+                "?".to_string()
+            }
+        },
+    };
+
+    // Keyset pagination over the `cursor` fields, bound from the cursor in find_after
+    let cursor_methods = if config.cursor_fields.is_empty() {
+        quote! {}
+    } else {
+        let cursor_name = quote::format_ident!("{}Cursor", struct_name);
+        let placeholder_literal = Literal::string(get_placeholder_template(config.database));
+        let cursor_field_names = config.cursor_fields.iter().map(|field| field.ident.as_ref().unwrap().to_string());
+        let cursor_param_arms = config.cursor_fields.iter().map(|field| {
+            let field_name = field.ident.as_ref().unwrap();
+            let field_literal = Literal::string(&field_name.to_string());
+            let error = Literal::string(&format!("Cursor has no value for {field_name}"));
+            quote! {
+                #field_literal => self.where_args.add_param(
+                    cursor.#field_name.as_ref().ok_or_else(|| sqlx::Error::InvalidArgument(#error.into()))?,
+                ),
+            }
+        }).collect::<Vec<_>>();
+        quote! {
+            fn add_cursor_param(&mut self, cursor: &'q #cursor_name, field: &str) -> Result<(), sqlx::Error> {
+                match field {
+                    #(#cursor_param_arms)*
+                    _ => Err(sqlx::Error::InvalidArgument(format!("Unknown cursor field {field}"))),
+                }
+            }
+
+            /// Keyset pagination: fetch up to `limit` rows after `cursor` following the `order_by_*` clauses,
+            /// and the cursor of the next page if there is one. The last `order_by_*` field should be unique.
+            pub async fn find_after<'c, E>(
+                mut self,
+                cursor: Option<&'q #cursor_name>,
+                limit: i32,
+                executor: E,
+            ) -> Result<(Vec<#struct_name>, Option<#cursor_name>), sqlx::Error>
+            where
+                E: sqlx::Executor<'c, Database = #database_type>,
+            {
+                if self.order_by_fields.is_empty() {
+                    return Err(sqlx::Error::InvalidArgument("find_after requires at least one order_by_* clause".into()));
+                }
+                if let Some((field, _, _)) = self.order_by_fields.iter().find(|x| ![#(#cursor_field_names),*].contains(&x.0)) {
+                    return Err(sqlx::Error::InvalidArgument(format!("{field} is not a cursor field")));
+                }
+                let order_by_fields = self.order_by_fields.clone();
+                if let Some(cursor) = cursor {
+                    let columns = order_by_fields.iter().map(|x| x.1).collect::<Vec<_>>();
+                    let ascending = order_by_fields[0].2;
+                    if order_by_fields.iter().all(|x| x.2 == ascending) {
+                        // (a, b) > (?, ?)
+                        for (field, _, _) in &order_by_fields {
+                            self.add_cursor_param(cursor, field)?;
+                        }
+                        let placeholders = vec![#placeholder_literal; columns.len()].join(", ");
+                        let op = if ascending { ">" } else { "<" };
+                        self.where_conditions.push(format!("({}) {} ({})", columns.join(", "), op, placeholders));
+                    } else {
+                        // (a > ?) OR (a = ? AND b < ?)
+                        let mut disjuncts = Vec::new();
+                        for i in 0..order_by_fields.len() {
+                            let mut conjuncts = Vec::new();
+                            for (j, (field, column, asc)) in order_by_fields[..=i].iter().enumerate() {
+                                let op = if j < i { "=" } else if *asc { ">" } else { "<" };
+                                conjuncts.push(format!("{} {} {}", column, op, #placeholder_literal));
+                                self.add_cursor_param(cursor, field)?;
+                            }
+                            disjuncts.push(format!("({})", conjuncts.join(" AND ")));
+                        }
+                        self.where_conditions.push(format!("({})", disjuncts.join(" OR ")));
+                    }
+                }
+
+                // One extra row tells whether there is a next page
+                let paging_limit = limit.max(0) as i64 + 1;
+                self.limit = Some(paging_limit);
+                self.offset = None;
+                let sql = self.build_sql();
+
+                let mut rows: Vec<#struct_name> = sqlx::query_as_with(&sql, *self.query_args()?.0).fetch_all(executor).await?;
+                let next_cursor = if rows.len() as i64 == paging_limit {
+                    rows.pop();
+                    let fields = order_by_fields.iter().map(|x| x.0).collect::<Vec<_>>();
+                    rows.last().map(|last| #cursor_name::from_row(last, &fields))
+                } else {
+                    None
+                };
+                Ok((rows, next_cursor))
+            }
+        }
+    };
 
     // Build builder with simple parameter storage and manual binding
-    quote! {
+    let builder = quote! {
         /// QueryBuilderArgs for parameter binding
 
         #[derive(Clone)]
@@ -108,13 +308,37 @@ pub fn impl_select_builder(input: &DeriveInput, config: &super::BuilderConfig) -
             }
         }
 
+        #[doc = #column_enum_doc]
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        pub enum #column_enum_name {
+            #(#column_variants,)*
+        }
+
+        impl #column_enum_name {
+            /// Column of the field
+            pub const fn column(self) -> &'static str {
+                match self {
+                    #(Self::#column_variants => #column_literals,)*
+                }
+            }
+        }
+
         /// Generated select builder
         pub struct #builder_name<'q> {
             table_name: String,
             where_conditions: Vec<String>,
             where_args: #args_struct_name<'q, #database_type>,
             order_by_clauses: Vec<String>,
+            // (field, column, ascending) of each ORDER BY clause, used for keyset pagination
+            order_by_fields: Vec<(&'static str, &'static str, bool)>,
+            // Row locking clause, e.g. " FOR UPDATE", and its " SKIP LOCKED" or " NOWAIT" option
+            lock_clause: &'static str,
+            lock_wait: &'static str,
+            // LIMIT and OFFSET, bound after the WHERE arguments
+            limit: Option<i64>,
+            offset: Option<i64>,
             stream_sql: String,
+            #soft_delete_field
         }
 
         impl <'q> #builder_name<'q> {
@@ -127,7 +351,13 @@ pub fn impl_select_builder(input: &DeriveInput, config: &super::BuilderConfig) -
                     where_conditions: self.where_conditions.clone(),
                     where_args: cloned_where_args,
                     order_by_clauses: self.order_by_clauses.clone(),
+                    order_by_fields: self.order_by_fields.clone(),
+                    lock_clause: self.lock_clause,
+                    lock_wait: self.lock_wait,
+                    limit: self.limit,
+                    offset: self.offset,
                     stream_sql: self.stream_sql.clone(),
+                    #soft_delete_clone
                 }
             }
 
@@ -137,40 +367,102 @@ pub fn impl_select_builder(input: &DeriveInput, config: &super::BuilderConfig) -
                     where_conditions: Vec::new(),
                     where_args: #args_struct_name::default(),
                     order_by_clauses: Vec::new(),
+                    order_by_fields: Vec::new(),
+                    lock_clause: "",
+                    lock_wait: "",
+                    limit: None,
+                    offset: None,
                     stream_sql: "".to_string(),
+                    #soft_delete_init
                 }
             }
 
             #(#field_methods)*
             #(#order_methods)*
             #(#custom_methods)*
+            #group_methods
+            #lock_methods
+            #soft_delete_methods
 
             // Add placeholder replacement function
             #placeholder_replacement_fn
+            #limit_placeholder_fn
+
+            /// Return at most `limit` rows, bound as a parameter. Replaced by `find_page` and `find_after`
+            pub fn limit(mut self, limit: i64) -> Result<Self, sqlx::Error> {
+                self.limit = Some(limit);
+                Ok(self)
+            }
+
+            /// Skip the first `offset` rows, bound as a parameter. Replaced by `find_page` and `find_after`
+            pub fn offset(mut self, offset: i64) -> Result<Self, sqlx::Error> {
+                self.offset = Some(offset);
+                Ok(self)
+            }
+
+            /// Return at most the first `n` rows, from the start
+            pub fn first_n(mut self, n: i64) -> Result<Self, sqlx::Error> {
+                self.limit = Some(n);
+                self.offset = None;
+                Ok(self)
+            }
+
+            /// Arguments of the query built by `build_sql`: the WHERE arguments, then LIMIT and OFFSET
+            fn query_args(&self) -> Result<#args_struct_name<'q, #database_type>, sqlx::Error> {
+                let mut args = #args_struct_name(Box::new(self.where_args.0.as_ref().clone()), self.where_args.1);
+                // SQLite and MySQL need a LIMIT before OFFSET
+                if self.limit.is_some() || self.offset.is_some() {
+                    args.add_param(self.limit.unwrap_or(i64::MAX))?;
+                }
+                if let Some(offset) = self.offset {
+                    args.add_param(offset)?;
+                }
+                Ok(args)
+            }
+
+            /// WHERE clause of the conditions, after the soft delete condition, empty without conditions
+            fn where_clause(&self) -> String {
+                #soft_delete_conditions
+                if where_conditions.is_empty() {
+                    return String::new();
+                }
+                let where_clause = where_conditions.join(" AND ");
+                format!(" WHERE {}", Self::replace_placeholders(&where_clause, self.where_args.len()))
+            }
 
             /// Build SQL query string
             pub fn build_sql(&self) -> String {
                 let mut sql = #select_base_literal.to_string();
-
-                if !self.where_conditions.is_empty() {
-                    sql.push_str(" WHERE ");
-                    let where_clause = self.where_conditions.join(" AND ");
-                    let replaced_where = Self::replace_placeholders(&where_clause, self.where_args.len());
-                    sql.push_str(&replaced_where);
-                }
+                sql.push_str(&self.where_clause());
 
                 if !self.order_by_clauses.is_empty() {
                     sql.push_str(" ORDER BY ");
                     sql.push_str(&self.order_by_clauses.join(", "));
                 }
 
+                let mut param_index = self.where_args.len();
+                if self.limit.is_some() || self.offset.is_some() {
+                    param_index += 1;
+                    sql.push_str(" LIMIT ");
+                    sql.push_str(&Self::limit_placeholder(param_index));
+                }
+                if self.offset.is_some() {
+                    param_index += 1;
+                    sql.push_str(" OFFSET ");
+                    sql.push_str(&Self::limit_placeholder(param_index));
+                }
+
+                // The locking clause comes after LIMIT on MySQL
+                sql.push_str(self.lock_clause);
+                sql.push_str(self.lock_wait);
                 sql
             }
 
             /// Build SQL query with parameter placeholders
             pub fn build_sql_with_params(&self) -> (String, usize) {
                 let sql = self.build_sql();
-                (sql, self.where_args.len())
+                let limit_params = usize::from(self.limit.is_some() || self.offset.is_some()) + usize::from(self.offset.is_some());
+                (sql, self.where_args.len() + limit_params)
             }
 
             /// Execute query và return single result
@@ -180,10 +472,10 @@ pub fn impl_select_builder(input: &DeriveInput, config: &super::BuilderConfig) -
                 E: sqlx::Executor<'c, Database = #database_type>,
             {
                 let sql = self.build_sql();
-                let where_args = self.where_args;
+                let query_args = self.query_args()?;
 
                 // Manually bind parameters
-                sqlx::query_as_with(&sql, *where_args.0).fetch_optional(executor).await
+                sqlx::query_as_with(&sql, *query_args.0).fetch_optional(executor).await
             }
 
             /// Execute query và return all results
@@ -193,51 +485,97 @@ pub fn impl_select_builder(input: &DeriveInput, config: &super::BuilderConfig) -
                 E: sqlx::Executor<'c, Database = #database_type>,
             {
                 let sql = self.build_sql();
-                let where_args = self.where_args;
+                let query_args = self.query_args()?;
 
                 // Manually bind parameters
-                sqlx::query_as_with(&sql, *where_args.0)
+                sqlx::query_as_with(&sql, *query_args.0)
                     .fetch_all(executor)
                     .await
             }
 
-            pub async fn find_page<'c, E>(
+            #find_all_as
+
+            /// Build SQL query string selecting only the given columns
+            fn build_projection_sql(&self, columns: &str) -> String {
+                self.build_sql().replacen(
+                    #select_base_literal,
+                    &format!("SELECT {} FROM {}", columns, #table_name),
+                    1,
+                )
+            }
+
+            /// Execute query and return the columns of the given fields of all results as `T`, usually a tuple
+            /// (e.g., `.project::<(i32, String)>(&[UserColumn::Id, UserColumn::Email], &pool)`). Fails without fields
+            pub async fn project<'c, T>(
                 self,
-                page: impl Into<(i64, i32, bool)>,
+                fields: &[#column_enum_name],
+                executor: impl sqlx::Executor<'c, Database = #database_type>,
+            ) -> Result<Vec<T>, sqlx::Error>
+            where
+                T: for<'r> sqlx::FromRow<'r, <#database_type as sqlx::Database>::Row> + Send + Unpin,
+            {
+                if fields.is_empty() {
+                    return Err(sqlx::Error::InvalidArgument("project requires at least one field".into()));
+                }
+                let columns = fields.iter().map(|field| field.column()).collect::<Vec<_>>();
+                let sql = self.build_projection_sql(&columns.join(", "));
+                sqlx::query_as_with(&sql, *self.query_args()?.0)
+                    .fetch_all(executor)
+                    .await
+            }
+
+            #(#select_column_methods)*
+            #pluck_id_method
+
+            pub async fn find_page<'c, E>(
+                mut self,
+                page: #page_request,
                 executor: E,
-            ) -> Result<(Vec<#struct_name>, Option<i64>), sqlx::Error>
+            ) -> Result<#page_response, sqlx::Error>
             where
                 E: sqlx::Executor<'c, Database = #database_type> +'c + Copy,
             {
-                let (offset, limit, count) = page.into();
+                // The data and count queries run on a Copy executor, a pool releasing the locks at once
+                if !self.lock_clause.is_empty() {
+                    return Err(sqlx::Error::InvalidArgument("find_page does not support row locking".into()));
+                }
+                #page_prelude
 
-                // Build base SQL with WHERE and ORDER BY
-                let mut sql = self.build_sql();
-                sql.push_str(&format!(" LIMIT {limit} OFFSET {offset}"));
+                // Build SQL with WHERE, ORDER BY and the bound LIMIT and OFFSET of the page
+                self.limit = Some(i64::from(limit));
+                self.offset = Some(offset);
+                let sql = self.build_sql();
+                let query_args = self.query_args()?;
 
-                let res = if count {
-                    let data = sqlx::query_as_with(&sql, *self.where_args.0.clone()).fetch_all(executor).await?;
+                let (data, count) = if count {
+                    let data = sqlx::query_as_with(&sql, *query_args.0).fetch_all(executor).await?;
                     if data.is_empty() && offset == 0 {
                         (data, Some(0))
                     } else {
                         // Build count SQL
                         let mut count_sql = #count_base_literal.to_string();
-                        if !self.where_conditions.is_empty() {
-                            count_sql.push_str(" WHERE ");
-                            let where_clause = self.where_conditions.join(" AND ");
-                            let replaced_where = Self::replace_placeholders(&where_clause, self.where_args.len());
-                            count_sql.push_str(&replaced_where);
-                        }
+                        count_sql.push_str(&self.where_clause());
                         let count = sqlx::query_scalar_with(&count_sql, *self.where_args.0).fetch_one(executor).await?;
                         (data, Some(count))
                     }
                 } else {
-                    let data = sqlx::query_as_with(&sql, *self.where_args.0).fetch_all(executor).await?;
+                    let data = sqlx::query_as_with(&sql, *query_args.0).fetch_all(executor).await?;
                     (data, None)
                 };
-                Ok(res)
+                #page_result
+            }
+
+            #cursor_methods
+
+            /// LIMIT and OFFSET apply to the returned rows, not to the counts and aggregates
+            fn check_no_limit(&self) -> Result<(), sqlx::Error> {
+                if self.limit.is_some() || self.offset.is_some() {
+                    return Err(sqlx::Error::InvalidArgument("limit and offset are not supported by counts and aggregates".into()));
+                }
+                Ok(())
             }
 
+            /// Count the matching rows, fails after `.limit()` or `.offset()`
             pub async fn count<'c, E>(
                 self,
                 executor: E,
@@ -245,16 +583,38 @@ pub fn impl_select_builder(input: &DeriveInput, config: &super::BuilderConfig) -
             where
                 E: sqlx::Executor<'c, Database = #database_type>,
             {
+                self.check_no_limit()?;
                 let mut count_sql = #count_base_literal.to_string();
-                if !self.where_conditions.is_empty() {
-                    count_sql.push_str(" WHERE ");
-                    let where_clause = self.where_conditions.join(" AND ");
-                    let replaced_where = Self::replace_placeholders(&where_clause, self.where_args.len());
-                    count_sql.push_str(&replaced_where);
-                }
+                count_sql.push_str(&self.where_clause());
                 sqlx::query_scalar_with(&count_sql, *self.where_args.0).fetch_one(executor).await
             }
 
+            /// Build the SQL of an aggregate over the matching rows, fails after `.limit()` or `.offset()`
+            fn build_aggregate_sql(&self, aggregate: &str) -> Result<String, sqlx::Error> {
+                self.check_no_limit()?;
+                let mut sql = format!("SELECT {} FROM {}", aggregate, #table_name);
+                sql.push_str(&self.where_clause());
+                Ok(sql)
+            }
+
+            #(#aggregate_methods)*
+
+            #(#group_count_methods)*
+
+            /// Whether at least one row matches, without counting all of them. Fails after `.limit()` or `.offset()`
+            pub async fn exists<'c, E>(
+                self,
+                executor: E,
+            ) -> Result<bool, sqlx::Error>
+            where
+                E: sqlx::Executor<'c, Database = #database_type>,
+            {
+                self.check_no_limit()?;
+                let mut sql = #exists_base_literal.to_string();
+                sql.push_str(&self.where_clause());
+                #fetch_exists
+            }
+
 
             pub async fn stream<E>(
                 &'q mut self,
@@ -263,19 +623,12 @@ pub fn impl_select_builder(input: &DeriveInput, config: &super::BuilderConfig) -
             where
                 E: sqlx::Executor<'q, Database = #database_type> + 'q,
             {
-                self.stream_sql.clear();
-                self.stream_sql.push_str(#select_base_literal);
-                if !self.where_conditions.is_empty() {
-                    self.stream_sql.push_str(" WHERE ");
-                    let where_clause = self.where_conditions.join(" AND ");
-                    let replaced_where = Self::replace_placeholders(&where_clause, self.where_args.len());
-                    self.stream_sql.push_str(&replaced_where);
-                }
-                if !self.order_by_clauses.is_empty() {
-                    self.stream_sql.push_str(" ORDER BY ");
-                    self.stream_sql.push_str(&self.order_by_clauses.join(", "));
-                }
-                sqlx::query_as_with(&self.stream_sql, *self.where_args.0.clone()).fetch(executor)
+                self.stream_sql = self.build_sql();
+                let query_args = match self.query_args() {
+                    Ok(query_args) => query_args,
+                    Err(e) => return Box::pin(futures::stream::once(async move { Err(e) })),
+                };
+                sqlx::query_as_with(&self.stream_sql, *query_args.0).fetch(executor)
             }
 
         }
@@ -287,7 +640,7 @@ pub fn impl_select_builder(input: &DeriveInput, config: &super::BuilderConfig) -
             /// - WHERE conditions using field methods (e.g., `.field_name(value)`, `.field_name_gt(value)`)
             /// - Custom WHERE conditions (if defined with `#[tp_select_builder(...)]`)
             /// - ORDER BY clauses using `.order_by_field_asc()` and `.order_by_field_desc()` methods
-            /// - Query execution methods: `.find_all()`, `.find_one()`, `.count()`, `.find_page()`, `.stream()`
+            /// - Query execution methods: `.find_all()`, `.find_all_as::<T>()`, `.project::<T>()`, `.select_field_name()`, `.find_one()`, `.count()`, `.exists()`, `.find_page()`, `.find_after()`, `.stream()`
             ///
             /// # Example
             ///
@@ -325,10 +678,43 @@ pub fn impl_select_builder(input: &DeriveInput, config: &super::BuilderConfig) -
             /// # Returns
             ///
             /// A new `SelectBuilder` instance ready for method chaining.
-            pub fn builder_select<'q>() -> #builder_name<'q> {
-                #builder_name::new()
+            #constructor_doc
+            pub fn builder_select<'q>(#constructor_params) -> #constructor_return {
+                #constructor_body
             }
+            #unscoped_constructor
+
+        }
+    };
+    check_method_clashes(&builder, &builder_name);
+    builder
+}
 
+/// Reject a builder whose impl defines a method twice, e.g. the equality method of a field named
+/// `limit` and the `limit` of the builder, with a message naming the method instead of a duplicate definition error
+fn check_method_clashes(builder: &TokenStream, builder_name: &Ident) {
+    let Ok(file) = syn::parse2::<syn::File>(builder.clone()) else {
+        return;
+    };
+    let mut names = HashSet::new();
+    for item in file.items {
+        let syn::Item::Impl(item) = item else {
+            continue;
+        };
+        let is_builder = matches!(&*item.self_ty, SynType::Path(path) if path.path.segments.last().is_some_and(|x| x.ident == *builder_name));
+        if !is_builder || item.trait_.is_some() {
+            continue;
+        }
+        for item in item.items {
+            if let syn::ImplItem::Method(method) = item {
+                let name = method.sig.ident.to_string();
+                if !names.insert(name.clone()) {
+                    panic!(
+                        "`{name}` of {builder_name} is generated twice, for a field or custom condition and as a builder method. \
+                         Rename the field or the condition"
+                    );
+                }
+            }
         }
     }
 }
@@ -342,12 +728,170 @@ fn generate_field_methods(field: &Field, database: Database) -> TokenStream {
     // Determine field type category
     let type_str = quote!(#field_type).to_string();
 
-    if is_string_type(&type_str) {
+    // The values of `Option` fields have the inner type, `IS NULL` is checked with `_is_null`
+    let value_type = super::super::unwrap_option_type(field_type);
+    let methods = if is_string_type(&type_str) {
         generate_string_methods(field_name, &column_name, database)
     } else if is_numeric_or_datetime_type(&type_str) {
-        generate_numeric_datetime_methods(field_name, &column_name, database, field_type)
+        generate_numeric_datetime_methods(field_name, &column_name, database, value_type)
     } else {
-        generate_basic_methods(field_name, &column_name, database, field_type)
+        generate_basic_methods(field_name, &column_name, database, value_type)
+    };
+    let in_methods = generate_in_methods(field_name, "", &column_name, database, value_type);
+    let null_methods = generate_null_methods(field, "", &column_name);
+    quote! {
+        #methods
+        #in_methods
+        #null_methods
+    }
+}
+
+/// Generate `_is_null` and `_is_not_null` methods of an `Option` field
+fn generate_null_methods(field: &Field, method_prefix: &str, column_name: &str) -> TokenStream {
+    if !matches!(&field.ty, SynType::Path(type_path) if super::super::is_option_type(type_path)) {
+        return quote! {};
+    }
+    let field_name = field.ident.as_ref().unwrap();
+    let is_null_method = quote::format_ident!("{}{}_is_null", method_prefix, field_name);
+    let is_not_null_method = quote::format_ident!("{}{}_is_not_null", method_prefix, field_name);
+    let is_null_condition_literal = Literal::string(&format!("{} IS NULL", column_name));
+    let is_not_null_condition_literal = Literal::string(&format!("{} IS NOT NULL", column_name));
+
+    quote! {
+        /// IS NULL condition
+        pub fn #is_null_method(mut self) -> Result<Self, sqlx::Error> {
+            self.where_conditions.push(#is_null_condition_literal.to_string());
+            Ok(self)
+        }
+
+        /// IS NOT NULL condition
+        pub fn #is_not_null_method(mut self) -> Result<Self, sqlx::Error> {
+            self.where_conditions.push(#is_not_null_condition_literal.to_string());
+            Ok(self)
+        }
+    }
+}
+
+/// Generate `_in` and `_not_in` methods taking a slice, whose placeholders are expanded at runtime.
+/// An empty slice matches no rows with `_in` and all rows with `_not_in`
+fn generate_in_methods(field_name: &Ident, method_prefix: &str, column_name: &str, database: Database, field_type: &SynType) -> TokenStream {
+    let in_method = quote::format_ident!("{}{}_in", method_prefix, field_name);
+    let not_in_method = quote::format_ident!("{}{}_not_in", method_prefix, field_name);
+
+    let placeholder = get_placeholder_template(database);
+    let in_condition_literal = Literal::string(&format!("{} IN ({{}})", column_name));
+    let not_in_condition_literal = Literal::string(&format!("{} NOT IN ({{}})", column_name));
+
+    quote! {
+        /// IN condition, matching no rows for an empty slice
+        pub fn #in_method(mut self, values: &'q [#field_type]) -> Result<Self, sqlx::Error> {
+            if values.is_empty() {
+                self.where_conditions.push("1 = 0".to_string());
+                return Ok(self);
+            }
+            for value in values {
+                self.where_args.add_param(value)?;
+            }
+            self.where_conditions.push(format!(#in_condition_literal, vec![#placeholder; values.len()].join(", ")));
+            Ok(self)
+        }
+
+        /// NOT IN condition, matching all rows for an empty slice
+        pub fn #not_in_method(mut self, values: &'q [#field_type]) -> Result<Self, sqlx::Error> {
+            if values.is_empty() {
+                self.where_conditions.push("1 = 1".to_string());
+                return Ok(self);
+            }
+            for value in values {
+                self.where_args.add_param(value)?;
+            }
+            self.where_conditions.push(format!(#not_in_condition_literal, vec![#placeholder; values.len()].join(", ")));
+            Ok(self)
+        }
+    }
+}
+
+/// Escape character of the LIKE patterns built from user input
+const LIKE_ESCAPE: char = '!';
+
+/// Expression escaping the LIKE wildcards of a `&str`, so that it only matches itself
+fn escape_like_pattern(value: TokenStream) -> TokenStream {
+    let escape = LIKE_ESCAPE.to_string();
+    let escaped_escape = format!("{LIKE_ESCAPE}{LIKE_ESCAPE}");
+    let escaped_percent = format!("{LIKE_ESCAPE}%");
+    let escaped_underscore = format!("{LIKE_ESCAPE}_");
+    quote! {
+        #value.replace(#escape, #escaped_escape).replace('%', #escaped_percent).replace('_', #escaped_underscore)
+    }
+}
+
+/// Doc of the case sensitivity of LIKE, which depends on the database
+fn like_case_doc(database: Database) -> &'static str {
+    match database {
+        Database::Postgres => "LIKE is case-sensitive on PostgreSQL, see `_ilike`",
+        Database::Sqlite => "LIKE is case-insensitive for ASCII letters on SQLite",
+        Database::Mysql => "LIKE follows the collation of the column on MySQL, case-insensitive by default",
+        Database::Any => {
+            "LIKE is case-insensitive for ASCII letters on SQLite, follows the collation of the column on MySQL \
+             and is case-sensitive on PostgreSQL"
+        }
+    }
+}
+
+/// Generate the case-insensitive `_ilike` and the substring `_contains` methods of a string field
+fn generate_search_methods(field_name: &Ident, method_prefix: &str, column_name: &str, database: Database) -> TokenStream {
+    let ilike_method = quote::format_ident!("{}{}_ilike", method_prefix, field_name);
+    let contains_method = quote::format_ident!("{}{}_contains", method_prefix, field_name);
+    let placeholder = get_placeholder_template(database);
+    let like_case = like_case_doc(database);
+
+    // Only PostgreSQL has ILIKE
+    let ilike_condition = match database {
+        Database::Postgres => format!("{} ILIKE {} ESCAPE '{}'", column_name, placeholder, LIKE_ESCAPE),
+        Database::Sqlite | Database::Mysql | Database::Any => {
+            format!("LOWER({}) LIKE LOWER({}) ESCAPE '{}'", column_name, placeholder, LIKE_ESCAPE)
+        }
+    };
+    let contains_condition = format!("{} LIKE {} ESCAPE '{}'", column_name, placeholder, LIKE_ESCAPE);
+
+    let ilike_condition_literal = Literal::string(&ilike_condition);
+    let contains_condition_literal = Literal::string(&contains_condition);
+    let contains_pattern = escape_like_pattern(quote! { value });
+
+    quote! {
+        /// Case-insensitive LIKE condition, `!` escapes `%`, `_` and itself in the pattern
+        pub fn #ilike_method(mut self, pattern: &'q str) -> Result<Self, sqlx::Error> {
+            self.where_conditions.push(#ilike_condition_literal.to_string());
+            self.where_args.add_param(pattern)?;
+            Ok(self)
+        }
+
+        /// CONTAINS condition, `%` and `_` in the value are matched literally
+        ///
+        #[doc = #like_case]
+        pub fn #contains_method(mut self, value: &'q str) -> Result<Self, sqlx::Error> {
+            self.where_conditions.push(#contains_condition_literal.to_string());
+            self.where_args.add_param(format!("%{}%", #contains_pattern))?;
+            Ok(self)
+        }
+    }
+}
+
+/// Generate the `_between` method of a numeric/datetime field, including both bounds
+fn generate_between_method(field_name: &Ident, method_prefix: &str, column_name: &str, database: Database, field_type: &SynType) -> TokenStream {
+    let between_method = quote::format_ident!("{}{}_between", method_prefix, field_name);
+    let placeholder = get_placeholder_template(database);
+    let between_condition = format!("{} BETWEEN {} AND {}", column_name, placeholder, placeholder);
+    let between_condition_literal = Literal::string(&between_condition);
+
+    quote! {
+        /// BETWEEN condition
+        pub fn #between_method(mut self, low: &'q #field_type, high: &'q #field_type) -> Result<Self, sqlx::Error> {
+            self.where_conditions.push(#between_condition_literal.to_string());
+            self.where_args.add_param(low)?;
+            self.where_args.add_param(high)?;
+            Ok(self)
+        }
     }
 }
 
@@ -358,6 +902,8 @@ fn generate_string_methods(field_name: &Ident, column_name: &str, database: Data
     let like_method = quote::format_ident!("{}_like", field_name);
     let start_with_method = quote::format_ident!("{}_start_with", field_name);
     let end_with_method = quote::format_ident!("{}_end_with", field_name);
+    let search_methods = generate_search_methods(field_name, "", column_name, database);
+    let like_case = like_case_doc(database);
 
     // Generate placeholder based on database type
     let placeholder = get_placeholder_template(database);
@@ -366,10 +912,14 @@ fn generate_string_methods(field_name: &Ident, column_name: &str, database: Data
     let eq_condition = format!("{} = {}", column_name, placeholder);
     let neq_condition = format!("{} != {}", column_name, placeholder);
     let like_condition = format!("{} LIKE {}", column_name, placeholder);
+    let escaped_like_condition = format!("{} LIKE {} ESCAPE '{}'", column_name, placeholder, LIKE_ESCAPE);
 
     let eq_condition_literal = Literal::string(&eq_condition);
     let neq_condition_literal = Literal::string(&neq_condition);
     let like_condition_literal = Literal::string(&like_condition);
+    let escaped_like_condition_literal = Literal::string(&escaped_like_condition);
+    let start_with_pattern = escape_like_pattern(quote! { value });
+    let end_with_pattern = escape_like_pattern(quote! { value });
 
     quote! {
         /// Equality condition
@@ -394,18 +944,24 @@ fn generate_string_methods(field_name: &Ident, column_name: &str, database: Data
         }
 
         /// STARTS WITH condition
+        ///
+        #[doc = #like_case]
         pub fn #start_with_method(mut self, value: &'q str) -> Result<Self, sqlx::Error> {
-            self.where_conditions.push(#like_condition_literal.to_string());
-            self.where_args.add_param(format!("{}%", value))?;
+            self.where_conditions.push(#escaped_like_condition_literal.to_string());
+            self.where_args.add_param(format!("{}%", #start_with_pattern))?;
             Ok(self)
         }
 
         /// ENDS WITH condition
+        ///
+        #[doc = #like_case]
         pub fn #end_with_method(mut self, value: &'q str) -> Result<Self, sqlx::Error> {
-            self.where_conditions.push(#like_condition_literal.to_string());
-            self.where_args.add_param(format!("%{}", value))?;
+            self.where_conditions.push(#escaped_like_condition_literal.to_string());
+            self.where_args.add_param(format!("%{}", #end_with_pattern))?;
             Ok(self)
         }
+
+        #search_methods
     }
 }
 
@@ -417,6 +973,7 @@ fn generate_numeric_datetime_methods(field_name: &Ident, column_name: &str, data
     let gte_method = quote::format_ident!("{}_gte", field_name);
     let lt_method = quote::format_ident!("{}_lt", field_name);
     let lte_method = quote::format_ident!("{}_lte", field_name);
+    let between_method = generate_between_method(field_name, "", column_name, database, field_type);
 
     // Generate placeholder based on database type
     let placeholder = get_placeholder_template(database);
@@ -478,6 +1035,8 @@ fn generate_numeric_datetime_methods(field_name: &Ident, column_name: &str, data
             self.where_args.add_param(value)?;
             Ok(self)
         }
+
+        #between_method
     }
 }
 
@@ -513,6 +1072,274 @@ fn generate_basic_methods(field_name: &Ident, column_name: &str, database: Datab
     }
 }
 
+/// Soft delete scope of a builder, which skips the soft deleted rows unless changed by `.with_deleted()`,
+/// `.only_deleted()` or `.hard_delete()`. It is kept apart from the conditions of the calls and comes first
+/// in the WHERE clause. Returns the field, its initial value, its clone and the statement declaring the
+/// `where_conditions` of the query
+fn generate_soft_delete_scope(config: &super::BuilderConfig) -> (TokenStream, TokenStream, TokenStream, TokenStream) {
+    match &config.soft_delete {
+        Some(soft_delete) => {
+            let alive = soft_delete.alive_condition();
+            (
+                quote! { soft_delete_condition: Option<&'static str>, },
+                quote! { soft_delete_condition: Some(#alive), },
+                quote! { soft_delete_condition: self.soft_delete_condition, },
+                quote! {
+                    let where_conditions = self
+                        .soft_delete_condition
+                        .iter()
+                        .map(|x| x.to_string())
+                        .chain(self.where_conditions.iter().cloned())
+                        .collect::<Vec<_>>();
+                },
+            )
+        }
+        None => (quote! {}, quote! {}, quote! {}, quote! { let where_conditions = &self.where_conditions; }),
+    }
+}
+
+/// Generate the doc, parameters, return type and body of a `builder_*` constructor, and its unscoped variant.
+/// The constructor of a tenant table takes the tenant and only accesses its rows, with the condition method
+/// of the tenant field or `scope_method`, while `unscoped_builder_*` accesses the rows of all tenants.
+/// `unscoped_builder_insert` leaves the tenant column to its database default instead
+fn generate_builder_constructor(
+    config: &super::BuilderConfig,
+    builder_name: &Ident,
+    fn_name: &str,
+    scope_method: Option<&str>,
+) -> (TokenStream, TokenStream, TokenStream, TokenStream, TokenStream) {
+    let Some(tenant) = &config.tenant else {
+        return (
+            quote! {},
+            quote! {},
+            quote! { #builder_name<'q> },
+            quote! { #builder_name::new() },
+            quote! {},
+        );
+    };
+    let tenant_name = tenant.field.ident.as_ref().unwrap();
+    let tenant_method = match scope_method {
+        Some(scope_method) => quote::format_ident!("{}", scope_method),
+        None => tenant_name.clone(),
+    };
+    let unscoped_fn_name = quote::format_ident!("unscoped_{}", fn_name);
+    let (doc, unscoped_doc) = if fn_name == "builder_insert" {
+        (
+            format!("\n# Tenant\n\nInserts the row into `{tenant_name}`, `{unscoped_fn_name}` leaves the tenant column to its database default."),
+            "Create a new builder leaving the tenant column to its database default",
+        )
+    } else {
+        (
+            format!("\n# Tenant\n\nOnly accesses the rows of `{tenant_name}`, `{unscoped_fn_name}` accesses the rows of all tenants."),
+            "Create a new builder accessing the rows of all tenants",
+        )
+    };
+    (
+        quote! { #[doc = #doc] },
+        tenant.gen_arg(quote! { 'q }),
+        quote! { Result<#builder_name<'q>, sqlx::Error> },
+        quote! { #builder_name::new().#tenant_method(#tenant_name) },
+        quote! {
+            #[doc = #unscoped_doc]
+            pub fn #unscoped_fn_name<'q>() -> #builder_name<'q> {
+                #builder_name::new()
+            }
+        },
+    )
+}
+
+/// Generate `.or()`, `.and_group()` and `.not()`, which wrap the conditions added by a closure into a parenthesized
+/// group. The closure continues the builder, so its arguments are bound in the order of the calls like the other conditions
+fn generate_group_methods() -> TokenStream {
+    quote! {
+        /// Join the conditions added by the closure with OR, e.g. `.or(|b| b.email("a")?.name("b"))`
+        /// for `(email = ? OR name = ?)`
+        pub fn or<F>(self, f: F) -> Result<Self, sqlx::Error>
+        where
+            F: FnOnce(Self) -> Result<Self, sqlx::Error>,
+        {
+            self.push_condition_group(f, " OR ", "(")
+        }
+
+        /// Group the conditions added by the closure with AND, e.g. inside `.or()`
+        pub fn and_group<F>(self, f: F) -> Result<Self, sqlx::Error>
+        where
+            F: FnOnce(Self) -> Result<Self, sqlx::Error>,
+        {
+            self.push_condition_group(f, " AND ", "(")
+        }
+
+        /// Negate the conditions added by the closure, joined with AND, e.g. `.not(|b| b.status("closed"))`
+        pub fn not<F>(self, f: F) -> Result<Self, sqlx::Error>
+        where
+            F: FnOnce(Self) -> Result<Self, sqlx::Error>,
+        {
+            self.push_condition_group(f, " AND ", "NOT (")
+        }
+
+        fn push_condition_group<F>(mut self, f: F, separator: &str, open: &str) -> Result<Self, sqlx::Error>
+        where
+            F: FnOnce(Self) -> Result<Self, sqlx::Error>,
+        {
+            let conditions = std::mem::take(&mut self.where_conditions);
+            let mut builder = f(self)?;
+            let group = std::mem::replace(&mut builder.where_conditions, conditions);
+            if !group.is_empty() {
+                builder.where_conditions.push(format!("{open}{})", group.join(separator)));
+            }
+            Ok(builder)
+        }
+    }
+}
+
+/// Generate `.with_deleted()` and `.only_deleted()` of a soft deleted table, which change its soft delete scope
+fn generate_soft_delete_select_methods(config: &super::BuilderConfig) -> TokenStream {
+    let Some(soft_delete) = &config.soft_delete else {
+        return quote! {};
+    };
+    let deleted = soft_delete.deleted_condition();
+    quote! {
+        /// Include the soft deleted rows
+        pub fn with_deleted(mut self) -> Result<Self, sqlx::Error> {
+            self.soft_delete_condition = None;
+            Ok(self)
+        }
+
+        /// Only the soft deleted rows
+        pub fn only_deleted(mut self) -> Result<Self, sqlx::Error> {
+            self.soft_delete_condition = Some(#deleted);
+            Ok(self)
+        }
+    }
+}
+
+/// Generate the row locking methods of the select builder, only for databases supporting them
+fn generate_lock_methods(database: Database) -> TokenStream {
+    let no_key_update = match database {
+        Database::Postgres => quote! {
+            /// Lock the selected rows with `FOR NO KEY UPDATE`
+            pub fn for_no_key_update(mut self) -> Result<Self, sqlx::Error> {
+                self.lock_clause = " FOR NO KEY UPDATE";
+                Ok(self)
+            }
+        },
+        Database::Mysql => quote! {},
+        Database::Sqlite | Database::Any => return quote! {},
+    };
+    quote! {
+        /// Lock the selected rows with `FOR UPDATE`
+        pub fn for_update(mut self) -> Result<Self, sqlx::Error> {
+            self.lock_clause = " FOR UPDATE";
+            Ok(self)
+        }
+
+        /// Lock the selected rows with `FOR SHARE`
+        pub fn for_share(mut self) -> Result<Self, sqlx::Error> {
+            self.lock_clause = " FOR SHARE";
+            Ok(self)
+        }
+
+        #no_key_update
+
+        /// Skip the rows locked by other transactions, after `.for_update()` or `.for_share()`
+        pub fn skip_locked(mut self) -> Result<Self, sqlx::Error> {
+            if self.lock_clause.is_empty() {
+                return Err(sqlx::Error::InvalidArgument("skip_locked requires a locking clause".into()));
+            }
+            self.lock_wait = " SKIP LOCKED";
+            Ok(self)
+        }
+
+        /// Fail instead of waiting for the rows locked by other transactions, after `.for_update()` or `.for_share()`
+        pub fn nowait(mut self) -> Result<Self, sqlx::Error> {
+            if self.lock_clause.is_empty() {
+                return Err(sqlx::Error::InvalidArgument("nowait requires a locking clause".into()));
+            }
+            self.lock_wait = " NOWAIT";
+            Ok(self)
+        }
+    }
+}
+
+/// Generate the aggregate terminals of a field: `.sum_<field>()` and `.avg_<field>()` for numeric
+/// fields, `.min_<field>()` and `.max_<field>()` for numeric, string and date/time fields
+fn generate_aggregate_methods(field: &Field, database: Database) -> TokenStream {
+    let field_name = field.ident.as_ref().unwrap();
+    let field_type = &field.ty;
+    let type_str = quote!(#field_type).to_string();
+    let column_name = get_field_name_as_column(field, database);
+    let kind = crate::sqlx_template::get_agg_kind(field_type);
+    let database_type = get_database_type(database);
+    let mut funcs = Vec::new();
+    if kind != crate::sqlx_template::AggKind::Other {
+        funcs.extend(["sum", "avg"]);
+    }
+    if kind != crate::sqlx_template::AggKind::Other || is_string_type(&type_str) || is_numeric_or_datetime_type(&type_str) {
+        funcs.extend(["min", "max"]);
+    }
+    let methods = funcs.into_iter().map(|func| {
+        let (agg_expr, agg_type) = crate::sqlx_template::gen_aggregate(func, &column_name, field_type, database);
+        let method = quote::format_ident!("{}_{}", func, field_name);
+        let mut doc = format!("{} of `{}` over the matching rows, `None` without rows", func.to_uppercase(), field_name);
+        if func == "sum" && kind == crate::sqlx_template::AggKind::Integer {
+            doc.push_str(". The query fails beyond the range of `i64`");
+        }
+        quote! {
+            #[doc = #doc]
+            pub async fn #method<'c, E>(self, executor: E) -> Result<Option<#agg_type>, sqlx::Error>
+            where
+                E: sqlx::Executor<'c, Database = #database_type>,
+            {
+                let sql = self.build_aggregate_sql(#agg_expr)?;
+                sqlx::query_scalar_with(&sql, *self.where_args.0).fetch_one(executor).await
+            }
+        }
+    });
+    quote! { #(#methods)* }
+}
+
+/// Generate a terminal returning the field of the matching rows, e.g. `.select_<field>()`
+fn generate_select_column_method(field: &Field, method: &Ident, database: Database) -> TokenStream {
+    let field_name = field.ident.as_ref().unwrap();
+    let field_type = &field.ty;
+    let column_literal = Literal::string(&get_field_name_as_column(field, database));
+    let database_type = get_database_type(database);
+    let doc = format!("Execute query and return the `{}` of all results", field_name);
+    quote! {
+        #[doc = #doc]
+        pub async fn #method<'c, E>(self, executor: E) -> Result<Vec<#field_type>, sqlx::Error>
+        where
+            E: sqlx::Executor<'c, Database = #database_type>,
+        {
+            let sql = self.build_projection_sql(#column_literal);
+            sqlx::query_scalar_with(&sql, *self.query_args()?.0).fetch_all(executor).await
+        }
+    }
+}
+
+/// Generate `.count_group_by_<field>()`, counting the matching rows for each value of the field
+fn generate_group_count_method(field: &Field, database: Database) -> TokenStream {
+    let field_name = field.ident.as_ref().unwrap();
+    let field_type = &field.ty;
+    let column_name = get_field_name_as_column(field, database);
+    let database_type = get_database_type(database);
+    let method = quote::format_ident!("count_group_by_{}", field_name);
+    let select_literal = Literal::string(&format!("{column_name}, COUNT(*)"));
+    let group_literal = Literal::string(&format!(" GROUP BY {column_name} ORDER BY {column_name}"));
+    let doc = format!("Count the matching rows for each value of `{}`, ordered by the value", field_name);
+    quote! {
+        #[doc = #doc]
+        pub async fn #method<'c, E>(self, executor: E) -> Result<Vec<(#field_type, i64)>, sqlx::Error>
+        where
+            E: sqlx::Executor<'c, Database = #database_type>,
+        {
+            let mut sql = self.build_aggregate_sql(#select_literal)?;
+            sql.push_str(#group_literal);
+            sqlx::query_as_with(&sql, *self.where_args.0).fetch_all(executor).await
+        }
+    }
+}
+
 /// Generate order by methods
 fn generate_order_methods(field: &Field, database: Database) -> TokenStream {
     let field_name = field.ident.as_ref().unwrap(); // Get &Ident directly
@@ -528,23 +1355,28 @@ fn generate_order_methods(field: &Field, database: Database) -> TokenStream {
 
     let asc_clause_literal = Literal::string(&asc_clause);
     let desc_clause_literal = Literal::string(&desc_clause);
+    let field_literal = Literal::string(&field_name.to_string());
+    let column_literal = Literal::string(&column_name);
 
     quote! {
         /// Order by field ascending (default)
         pub fn #order_asc_method(mut self) -> Result<Self, sqlx::Error> {
             self.order_by_clauses.push(#asc_clause_literal.to_string());
+            self.order_by_fields.push((#field_literal, #column_literal, true));
             Ok(self)
         }
 
         /// Order by field ascending (explicit)
         pub fn #order_asc_explicit_method(mut self) -> Result<Self, sqlx::Error> {
             self.order_by_clauses.push(#asc_clause_literal.to_string());
+            self.order_by_fields.push((#field_literal, #column_literal, true));
             Ok(self)
         }
 
         /// Order by field descending
         pub fn #order_desc_method(mut self) -> Result<Self, sqlx::Error> {
             self.order_by_clauses.push(#desc_clause_literal.to_string());
+            self.order_by_fields.push((#field_literal, #column_literal, false));
             Ok(self)
         }
     }
@@ -770,15 +1602,25 @@ pub fn impl_update_builder(input: &DeriveInput, config: &super::BuilderConfig) -
         // Determine field type category
         let type_str = quote!(#field_type).to_string();
 
-        if is_string_type(&type_str) {
+        let value_type = super::super::unwrap_option_type(field_type);
+        let methods = if is_string_type(&type_str) {
             generate_update_string_methods(field_name, &column_name, config.database)
         } else if is_numeric_or_datetime_type(&type_str) {
-            generate_update_numeric_datetime_methods(field_name, &column_name, config.database, field_type)
+            generate_update_numeric_datetime_methods(field_name, &column_name, config.database, value_type)
         } else {
-            generate_update_basic_methods(field_name, &column_name, config.database, field_type)
+            generate_update_basic_methods(field_name, &column_name, config.database, value_type)
+        };
+        let in_methods = generate_in_methods(field_name, "by_", &column_name, config.database, value_type);
+        let null_methods = generate_null_methods(field, "by_", &column_name);
+        quote! {
+            #methods
+            #in_methods
+            #null_methods
         }
     }).collect::<Vec<_>>();
 
+    let group_methods = generate_group_methods();
+
     // Generate custom condition methods
     let custom_methods = config.custom_conditions.iter().map(|condition| {
         generate_custom_condition_method(condition, config.database, &config.fields)
@@ -788,10 +1630,71 @@ pub fn impl_update_builder(input: &DeriveInput, config: &super::BuilderConfig) -
     let update_base_sql = format!("UPDATE {}", config.table_name);
     let update_base_literal = proc_macro2::Literal::string(&update_base_sql);
 
+    // Soft deleted rows are not updated, unless `.with_deleted()` is called
+    let (soft_delete_field, soft_delete_init, soft_delete_clone, soft_delete_conditions) = generate_soft_delete_scope(config);
+    let soft_delete_methods = config.soft_delete.as_ref().map(|_| {
+        quote! {
+            /// Also update the soft deleted rows
+            pub fn with_deleted(mut self) -> Result<Self, sqlx::Error> {
+                self.soft_delete_condition = None;
+                Ok(self)
+            }
+        }
+    });
+
+    // The tenant condition is added last, as the arguments are bound in the order of the calls, SET clauses first
+    let (tenant_field, tenant_init, tenant_clone, tenant_methods, tenant_condition, tenant_bind) = match &config.tenant {
+        Some(tenant) => {
+            let tenant_type = tenant.gen_type(quote! { 'q });
+            let tenant_condition = format!(
+                "{} = {}",
+                get_field_name_as_column(&tenant.field, config.database),
+                get_placeholder_template(config.database)
+            );
+            (
+                quote! { tenant: Option<#tenant_type>, },
+                quote! { tenant: None, },
+                quote! { tenant: self.tenant, },
+                quote! {
+                    fn scope_tenant(mut self, tenant: #tenant_type) -> Result<Self, sqlx::Error> {
+                        self.tenant = Some(tenant);
+                        Ok(self)
+                    }
+                },
+                quote! {
+                    let mut where_conditions = where_conditions.to_vec();
+                    let mut param_count = self.where_args.len();
+                    if self.tenant.is_some() {
+                        where_conditions.push(#tenant_condition.to_string());
+                        param_count += 1;
+                    }
+                },
+                quote! {
+                    let mut where_args = where_args;
+                    if let Some(tenant) = self.tenant {
+                        where_args.add_param(tenant)?;
+                    }
+                },
+            )
+        }
+        None => (
+            quote! {},
+            quote! {},
+            quote! {},
+            quote! {},
+            quote! {
+                let param_count = self.where_args.len();
+            },
+            quote! {},
+        ),
+    };
+
     // Generate placeholder replacement function based on database type
     let placeholder_replacement_fn = generate_placeholder_replacement_fn(config.database);
+    let (constructor_doc, constructor_params, constructor_return, constructor_body, unscoped_constructor) =
+        generate_builder_constructor(config, &builder_name, "builder_update", Some("scope_tenant"));
 
-    quote! {
+    let builder = quote! {
         /// UpdateBuilderArgs for parameter binding
 
         #[derive(Clone)]
@@ -825,6 +1728,8 @@ pub fn impl_update_builder(input: &DeriveInput, config: &super::BuilderConfig) -
             set_clauses: Vec<String>,
             where_conditions: Vec<String>,
             where_args: #args_struct_name<'q, #database_type>,
+            #tenant_field
+            #soft_delete_field
         }
 
         impl <'q> #builder_name<'q> {
@@ -837,6 +1742,8 @@ pub fn impl_update_builder(input: &DeriveInput, config: &super::BuilderConfig) -
                     where_conditions: self.where_conditions.clone(),
                     where_args: cloned_where_args,
                     set_clauses: self.set_clauses.clone(),
+                    #tenant_clone
+                    #soft_delete_clone
                 }
             }
 
@@ -846,12 +1753,17 @@ pub fn impl_update_builder(input: &DeriveInput, config: &super::BuilderConfig) -
                     set_clauses: Vec::new(),
                     where_conditions: Vec::new(),
                     where_args: #args_struct_name::default(),
+                    #tenant_init
+                    #soft_delete_init
                 }
             }
 
             #(#on_methods)*
             #(#by_methods)*
             #(#custom_methods)*
+            #group_methods
+            #soft_delete_methods
+            #tenant_methods
 
             // Add placeholder replacement function
             #placeholder_replacement_fn
@@ -866,13 +1778,15 @@ pub fn impl_update_builder(input: &DeriveInput, config: &super::BuilderConfig) -
                 sql.push_str(" SET ");
                 sql.push_str(&self.set_clauses.join(", "));
 
-                if !self.where_conditions.is_empty() {
+                #soft_delete_conditions
+                #tenant_condition
+                if !where_conditions.is_empty() {
                     sql.push_str(" WHERE ");
-                    sql.push_str(&self.where_conditions.join(" AND "));
+                    sql.push_str(&where_conditions.join(" AND "));
                 }
 
                 // Replace all placeholders at once with correct positions
-                Self::replace_placeholders(&sql, self.where_args.len())
+                Self::replace_placeholders(&sql, param_count)
             }
 
             /// Execute update query
@@ -882,6 +1796,7 @@ pub fn impl_update_builder(input: &DeriveInput, config: &super::BuilderConfig) -
             {
                 let sql = self.build_sql();
                 let where_args = self.where_args;
+                #tenant_bind
 
                 let result = sqlx::query_with(&sql, *where_args.0).execute(executor).await?;
                 Ok(result.rows_affected())
@@ -935,11 +1850,15 @@ pub fn impl_update_builder(input: &DeriveInput, config: &super::BuilderConfig) -
             /// # Returns
             ///
             /// A new `UpdateBuilder` instance ready for method chaining.
-            pub fn builder_update<'q>() -> #builder_name<'q> {
-                #builder_name::new()
+            #constructor_doc
+            pub fn builder_update<'q>(#constructor_params) -> #constructor_return {
+                #constructor_body
             }
+            #unscoped_constructor
         }
-    }
+    };
+    check_method_clashes(&builder, &builder_name);
+    builder
 }
 
 /// Generate by_* methods cho string fields trong update builder
@@ -949,6 +1868,8 @@ fn generate_update_string_methods(field_name: &Ident, column_name: &str, databas
     let by_like_method = quote::format_ident!("by_{}_like", field_name);
     let by_start_with_method = quote::format_ident!("by_{}_start_with", field_name);
     let by_end_with_method = quote::format_ident!("by_{}_end_with", field_name);
+    let search_methods = generate_search_methods(field_name, "by_", column_name, database);
+    let like_case = like_case_doc(database);
 
     // Generate placeholder based on database type
     let placeholder = get_placeholder_template(database);
@@ -957,10 +1878,14 @@ fn generate_update_string_methods(field_name: &Ident, column_name: &str, databas
     let eq_condition = format!("{} = {}", column_name, placeholder);
     let neq_condition = format!("{} != {}", column_name, placeholder);
     let like_condition = format!("{} LIKE {}", column_name, placeholder);
+    let escaped_like_condition = format!("{} LIKE {} ESCAPE '{}'", column_name, placeholder, LIKE_ESCAPE);
 
     let eq_condition_literal = Literal::string(&eq_condition);
     let neq_condition_literal = Literal::string(&neq_condition);
     let like_condition_literal = Literal::string(&like_condition);
+    let escaped_like_condition_literal = Literal::string(&escaped_like_condition);
+    let start_with_pattern = escape_like_pattern(quote! { value });
+    let end_with_pattern = escape_like_pattern(quote! { value });
 
     quote! {
         /// WHERE equality condition
@@ -985,18 +1910,24 @@ fn generate_update_string_methods(field_name: &Ident, column_name: &str, databas
         }
 
         /// WHERE STARTS WITH condition
+        ///
+        #[doc = #like_case]
         pub fn #by_start_with_method(mut self, value: &'q str) -> Result<Self, sqlx::Error> {
-            self.where_conditions.push(#like_condition_literal.to_string());
-            self.where_args.add_param(format!("{}%", value))?;
+            self.where_conditions.push(#escaped_like_condition_literal.to_string());
+            self.where_args.add_param(format!("{}%", #start_with_pattern))?;
             Ok(self)
         }
 
         /// WHERE ENDS WITH condition
+        ///
+        #[doc = #like_case]
         pub fn #by_end_with_method(mut self, value: &'q str) -> Result<Self, sqlx::Error> {
-            self.where_conditions.push(#like_condition_literal.to_string());
-            self.where_args.add_param(format!("%{}", value))?;
+            self.where_conditions.push(#escaped_like_condition_literal.to_string());
+            self.where_args.add_param(format!("%{}", #end_with_pattern))?;
             Ok(self)
         }
+
+        #search_methods
     }
 }
 
@@ -1008,6 +1939,7 @@ fn generate_update_numeric_datetime_methods(field_name: &Ident, column_name: &st
     let by_gte_method = quote::format_ident!("by_{}_gte", field_name);
     let by_lt_method = quote::format_ident!("by_{}_lt", field_name);
     let by_lte_method = quote::format_ident!("by_{}_lte", field_name);
+    let between_method = generate_between_method(field_name, "by_", column_name, database, field_type);
 
     // Generate placeholder based on database type
     let placeholder = get_placeholder_template(database);
@@ -1069,6 +2001,8 @@ fn generate_update_numeric_datetime_methods(field_name: &Ident, column_name: &st
             self.where_args.add_param(value)?;
             Ok(self)
         }
+
+        #between_method
     }
 }
 
@@ -1104,6 +2038,260 @@ fn generate_update_basic_methods(field_name: &Ident, column_name: &str, database
     }
 }
 
+/// Implement insert builder macro
+pub fn impl_insert_builder(input: &DeriveInput, config: &super::BuilderConfig) -> TokenStream {
+    let struct_name = &input.ident;
+    let builder_name = quote::format_ident!("{}InsertBuilder", struct_name);
+    let args_struct_name = quote::format_ident!("{}InsertBuilderArgs", struct_name);
+    let table_name = &config.table_name;
+    let database_type = get_database_type(config.database);
+
+    // Parse fields to generate methods
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => panic!("Only named fields are supported"),
+        },
+        _ => panic!("Only structs are supported"),
+    };
+
+    // Generate on_* methods (columns of the INSERT), unset columns fall back to database defaults.
+    // The values are kept per field and bound when building, so that setting a field again replaces it
+    let values_struct_name = quote::format_ident!("{}InsertValues", struct_name);
+    let mut value_fields = Vec::new();
+    let mut on_methods = Vec::new();
+    let mut column_pushes = Vec::new();
+    let mut value_binds = Vec::new();
+    for field in fields.iter() {
+        let field_name = field.ident.as_ref().unwrap();
+        let column_literal = Literal::string(&get_field_name_as_column(field, config.database));
+        let on_method = quote::format_ident!("on_{}", field_name);
+        let field_type = &field.ty;
+        let value_type = if is_string_type(&quote!(#field_type).to_string()) {
+            quote! { str }
+        } else {
+            quote! { #field_type }
+        };
+        value_fields.push(quote! { #field_name: Option<&'q #value_type>, });
+        // The tenant is only set by the constructor, so that a scoped builder can not insert into another tenant
+        if config.tenant.as_ref().is_some_and(|tenant| tenant.field.ident.as_ref() == Some(field_name)) {
+            on_methods.push(quote! {
+                fn scope_tenant(mut self, value: &'q #value_type) -> Result<Self, sqlx::Error> {
+                    self.values.#field_name = Some(value);
+                    Ok(self)
+                }
+            });
+        } else {
+            on_methods.push(quote! {
+                /// Set field value for INSERT, replacing a previously set value
+                pub fn #on_method(mut self, value: &'q #value_type) -> Result<Self, sqlx::Error> {
+                    self.values.#field_name = Some(value);
+                    Ok(self)
+                }
+            });
+        }
+        column_pushes.push(quote! {
+            if self.values.#field_name.is_some() {
+                columns.push(#column_literal);
+            }
+        });
+        value_binds.push(quote! {
+            if let Some(value) = self.values.#field_name {
+                args.add_param(value)?;
+            }
+        });
+    }
+
+    // Pre-generate INSERT SQL templates
+    let insert_base_literal = Literal::string(&format!("INSERT INTO {}", config.table_name));
+    let placeholder_literal = Literal::string(get_placeholder_template(config.database));
+    let default_values_literal = Literal::string(match config.database {
+        Database::Mysql => " () VALUES ()",
+        Database::Postgres | Database::Sqlite | Database::Any => " DEFAULT VALUES",
+    });
+
+    // Generate placeholder replacement function based on database type
+    let placeholder_replacement_fn = generate_placeholder_replacement_fn(config.database);
+    let (constructor_doc, constructor_params, constructor_return, constructor_body, unscoped_constructor) =
+        generate_builder_constructor(config, &builder_name, "builder_insert", Some("scope_tenant"));
+
+    let execute_returning = if matches!(config.database, Database::Postgres | Database::Sqlite) {
+        quote! {
+            /// Execute insert query and return the inserted record
+            pub async fn execute_returning<'c, E>(self, executor: E) -> Result<#struct_name, sqlx::Error>
+            where
+                E: sqlx::Executor<'c, Database = #database_type>,
+            {
+                let sql = format!("{} RETURNING *", self.build_sql());
+                let args = self.build_args()?;
+
+                sqlx::query_as_with::<_, #struct_name, _>(&sql, *args.0).fetch_one(executor).await
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    quote! {
+        /// InsertBuilderArgs for parameter binding
+
+        #[derive(Clone)]
+        pub struct #args_struct_name<'q, DB: sqlx::Database>(pub Box<DB::Arguments<'q>>, usize);
+        impl<'q, DB: sqlx::Database> Default for #args_struct_name<'q, DB> {
+            fn default() -> Self {
+                Self(Box::default(), 0)
+            }
+        }
+
+        impl<'q, DB: sqlx::Database> #args_struct_name<'q, DB> {
+            pub fn new() -> Self {
+                Self::default()
+            }
+
+            pub fn add_param<T: 'q + Send + sqlx::Encode<'q, DB> + sqlx::Type<DB>>(&mut self, arg: T) -> Result<(), sqlx::Error> {
+                use sqlx::Arguments;
+                self.0.add(arg).map_err(|e| sqlx::Error::Encode(e))?;
+                self.1 += 1;
+                Ok(())
+            }
+            pub fn len(&self) -> usize {
+                self.1
+            }
+        }
+
+
+        /// Values set on the insert builder, `None` for the columns left to their database defaults
+        #[derive(Clone, Default)]
+        struct #values_struct_name<'q> {
+            #(#value_fields)*
+        }
+
+        /// Generated insert builder
+        pub struct #builder_name<'q> {
+            table_name: String,
+            values: #values_struct_name<'q>,
+        }
+
+        impl <'q> #builder_name<'q> {
+
+            #[inline]
+            pub fn clone(&self) -> #builder_name<'q> {
+                #builder_name {
+                    table_name: self.table_name.clone(),
+                    values: self.values.clone(),
+                }
+            }
+
+            pub fn new() -> Self {
+                Self {
+                    table_name: #table_name.to_string(),
+                    values: #values_struct_name::default(),
+                }
+            }
+
+            #(#on_methods)*
+
+            // Add placeholder replacement function
+            #placeholder_replacement_fn
+
+            /// Columns of the set values, in field order
+            fn columns(&self) -> Vec<&'static str> {
+                let mut columns = Vec::new();
+                #(#column_pushes)*
+                columns
+            }
+
+            /// Arguments of the set values, in the order of `columns`
+            fn build_args(&self) -> Result<#args_struct_name<'q, #database_type>, sqlx::Error> {
+                let mut args = #args_struct_name::default();
+                #(#value_binds)*
+                Ok(args)
+            }
+
+            /// Build SQL query string
+            pub fn build_sql(&self) -> String {
+                let mut sql = #insert_base_literal.to_string();
+                let columns = self.columns();
+                if columns.is_empty() {
+                    sql.push_str(#default_values_literal);
+                    return sql;
+                }
+
+                sql.push('(');
+                sql.push_str(&columns.join(", "));
+                sql.push_str(") VALUES (");
+                sql.push_str(&vec![#placeholder_literal; columns.len()].join(", "));
+                sql.push(')');
+
+                // Replace all placeholders at once with correct positions
+                Self::replace_placeholders(&sql, columns.len())
+            }
+
+            /// Execute insert query
+            pub async fn execute<'c, E>(self, executor: E) -> Result<u64, sqlx::Error>
+            where
+                E: sqlx::Executor<'c, Database = #database_type>,
+            {
+                let sql = self.build_sql();
+                let args = self.build_args()?;
+
+                let result = sqlx::query_with(&sql, *args.0).execute(executor).await?;
+                Ok(result.rows_affected())
+            }
+
+            #execute_returning
+        }
+
+        impl #struct_name {
+            /// Create a new INSERT query builder for this table.
+            ///
+            /// Only the columns set with `.on_field_name(value)` methods are part of the INSERT,
+            /// all other columns fall back to their database defaults.
+            ///
+            /// # Example
+            ///
+            /// ```rust,no_run
+            /// # use sqlx_template::SqliteTemplate;
+            /// # use sqlx::{FromRow, SqlitePool};
+            /// # #[derive(SqliteTemplate, FromRow, Debug, Clone)]
+            /// # #[table("users")]
+            /// # #[tp_insert_builder]
+            /// # pub struct User {
+            /// #     pub id: i32,
+            /// #     pub email: String,
+            /// #     pub active: bool,
+            /// # }
+            /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+            /// # let pool = SqlitePool::connect(":memory:").await?;
+            /// // `active` is left to its database default
+            /// let affected_rows = User::builder_insert()
+            ///     .on_email("john@example.com")?         // INSERT INTO users(email) VALUES (?)
+            ///     .execute(&pool)
+            ///     .await?;
+            ///
+            /// // Override the default and get the inserted record back
+            /// let user = User::builder_insert()
+            ///     .on_email("jane@example.com")?
+            ///     .on_active(&false)?
+            ///     .execute_returning(&pool)
+            ///     .await?;
+            /// # Ok(())
+            /// # }
+            /// ```
+            ///
+            /// # Returns
+            ///
+            /// A new `InsertBuilder` instance ready for method chaining.
+            #constructor_doc
+            pub fn builder_insert<'q>(#constructor_params) -> #constructor_return {
+                #constructor_body
+            }
+
+            #unscoped_constructor
+        }
+    }
+}
+
 /// Implement delete builder macro
 pub fn impl_delete_builder(input: &DeriveInput, config: &super::BuilderConfig) -> TokenStream {
     let struct_name = &input.ident;
@@ -1126,6 +2314,8 @@ pub fn impl_delete_builder(input: &DeriveInput, config: &super::BuilderConfig) -
         generate_field_methods(field, config.database)
     }).collect::<Vec<_>>();
 
+    let group_methods = generate_group_methods();
+
     // Generate custom condition methods
     let custom_methods = config.custom_conditions.iter().map(|condition| {
         generate_custom_condition_method(condition, config.database, &config.fields)
@@ -1135,10 +2325,36 @@ pub fn impl_delete_builder(input: &DeriveInput, config: &super::BuilderConfig) -
     let delete_base_sql = format!("DELETE FROM {}", config.table_name);
     let delete_base_literal = proc_macro2::Literal::string(&delete_base_sql);
 
+    // A soft delete sets the soft delete column of the rows which are not deleted yet,
+    // `.hard_delete()` removes the matching rows, deleted or not
+    let (soft_delete_field, soft_delete_init, soft_delete_clone, soft_delete_conditions) = generate_soft_delete_scope(config);
+    let (hard_delete_field, hard_delete_init, hard_delete_clone, soft_delete_methods, base_sql) = match &config.soft_delete {
+        Some(soft_delete) => {
+            let soft_delete_sql = format!("UPDATE {} SET {}", config.table_name, soft_delete.delete_set());
+            (
+                quote! { hard_delete: bool, },
+                quote! { hard_delete: false, },
+                quote! { hard_delete: self.hard_delete, },
+                quote! {
+                    /// Remove the matching rows instead of setting their soft delete column
+                    pub fn hard_delete(mut self) -> Result<Self, sqlx::Error> {
+                        self.soft_delete_condition = None;
+                        self.hard_delete = true;
+                        Ok(self)
+                    }
+                },
+                quote! { if self.hard_delete { #delete_base_literal } else { #soft_delete_sql } },
+            )
+        }
+        None => (quote! {}, quote! {}, quote! {}, quote! {}, quote! { #delete_base_literal }),
+    };
+
     // Generate placeholder replacement function based on database type
     let placeholder_replacement_fn = generate_placeholder_replacement_fn(config.database);
+    let (constructor_doc, constructor_params, constructor_return, constructor_body, unscoped_constructor) =
+        generate_builder_constructor(config, &builder_name, "builder_delete", None);
 
-    quote! {
+    let builder = quote! {
         /// DeleteBuilderArgs for parameter binding
 
         #[derive(Clone)]
@@ -1172,6 +2388,8 @@ pub fn impl_delete_builder(input: &DeriveInput, config: &super::BuilderConfig) -
             table_name: String,
             where_conditions: Vec<String>,
             where_args: #args_struct_name<'q, #database_type>,
+            #hard_delete_field
+            #soft_delete_field
         }
 
         impl <'q> #builder_name<'q> {
@@ -1183,6 +2401,8 @@ pub fn impl_delete_builder(input: &DeriveInput, config: &super::BuilderConfig) -
                     table_name: self.table_name.clone(),
                     where_conditions: self.where_conditions.clone(),
                     where_args: cloned_where_args,
+                    #hard_delete_clone
+                    #soft_delete_clone
                 }
             }
 
@@ -1191,22 +2411,27 @@ pub fn impl_delete_builder(input: &DeriveInput, config: &super::BuilderConfig) -
                     table_name: #table_name.to_string(),
                     where_conditions: Vec::new(),
                     where_args: #args_struct_name::default(),
+                    #hard_delete_init
+                    #soft_delete_init
                 }
             }
 
             #(#field_methods)*
             #(#custom_methods)*
+            #group_methods
+            #soft_delete_methods
 
             // Add placeholder replacement function
             #placeholder_replacement_fn
 
             /// Build SQL query string
             pub fn build_sql(&self) -> String {
-                let mut sql = #delete_base_literal.to_string();
+                let mut sql = #base_sql.to_string();
 
-                if !self.where_conditions.is_empty() {
+                #soft_delete_conditions
+                if !where_conditions.is_empty() {
                     sql.push_str(" WHERE ");
-                    let where_clause = self.where_conditions.join(" AND ");
+                    let where_clause = where_conditions.join(" AND ");
                     let replaced_where = Self::replace_placeholders(&where_clause, self.where_args.len());
                     sql.push_str(&replaced_where);
                 }
@@ -1282,11 +2507,15 @@ pub fn impl_delete_builder(input: &DeriveInput, config: &super::BuilderConfig) -
             /// # Returns
             ///
             /// A new `DeleteBuilder` instance ready for method chaining.
-            pub fn builder_delete<'q>() -> #builder_name<'q> {
-                #builder_name::new()
+            #constructor_doc
+            pub fn builder_delete<'q>(#constructor_params) -> #constructor_return {
+                #constructor_body
             }
+            #unscoped_constructor
         }
-    }
+    };
+    check_method_clashes(&builder, &builder_name);
+    builder
 }
 
 
@@ -8,7 +8,7 @@ use crate::sqlx_template::{Database, get_field_name, get_field_name_as_column, g
 pub mod macro_impl;
 
 /// Convert CamelCase to snake_case
-fn to_snake_case(s: &str) -> String {
+pub(super) fn to_snake_case(s: &str) -> String {
     let mut result = String::new();
     let mut chars = s.chars().peekable();
 
@@ -26,6 +26,19 @@ fn to_snake_case(s: &str) -> String {
     result
 }
 
+/// Convert snake_case to CamelCase
+pub(super) fn to_pascal_case(s: &str) -> String {
+    s.split('_')
+        .map(|part| {
+            let mut chars = part.chars();
+            chars
+                .next()
+                .map(|first| first.to_uppercase().chain(chars).collect::<String>())
+                .unwrap_or_default()
+        })
+        .collect()
+}
+
 
 
 /// Common traits and utilities for builder pattern
@@ -157,8 +170,17 @@ pub struct BuilderConfig {
     pub table_name: String,
     pub database: Database,
     pub debug_slow: Option<i32>,
+    pub max_page_size: Option<i32>,
+    pub soft_delete: Option<super::SoftDelete>,
+    pub tenant: Option<super::Tenant>,
     pub fields: Vec<Field>,
     pub custom_conditions: Vec<CustomCondition>,
+    /// Fields the select builder's `find_after` can order by, from `cursor = "..."`
+    pub cursor_fields: Vec<Field>,
+    /// Whether the select builder's `find_page` returns a `Page`, from `page_type = true`
+    pub page_type: bool,
+    /// Whether the select builder has `find_all_as`, from `find_all_as = true`
+    pub find_all_as: bool,
 }
 
 impl BuilderConfig {
@@ -166,6 +188,9 @@ impl BuilderConfig {
         let struct_name = ast.ident.to_string();
         let table_name = super::get_table_name(ast);
         let debug_slow = super::get_debug_slow_from_table_scope(ast);
+        let max_page_size = super::get_max_page_size_from_table_scope(ast);
+        let soft_delete = super::get_soft_delete_from_table_scope(ast, db);
+        let tenant = super::get_tenant_from_table_scope(ast);
         
         let fields = if let syn::Data::Struct(syn::DataStruct {
             fields: syn::Fields::Named(syn::FieldsNamed { ref named, .. }),
@@ -182,8 +207,14 @@ impl BuilderConfig {
             table_name,
             database: db,
             debug_slow,
+            max_page_size,
+            soft_delete,
+            tenant,
             fields,
             custom_conditions: Vec::new(),
+            cursor_fields: Vec::new(),
+            page_type: false,
+            find_all_as: false,
         }
     }
 
@@ -194,6 +225,9 @@ impl BuilderConfig {
 
         // Parse custom conditions from tp_select_builder attributes
         config.custom_conditions = Self::parse_custom_conditions(ast, &config.fields, db, "tp_select_builder")?;
+        config.cursor_fields = Self::parse_cursor_fields(ast, &config.fields)?;
+        config.page_type = Self::parse_bool_option(ast, "page_type")?;
+        config.find_all_as = Self::parse_bool_option(ast, "find_all_as")?;
 
         Ok(config)
     }
@@ -207,6 +241,11 @@ impl BuilderConfig {
         Ok(config)
     }
 
+    pub fn from_insert_attributes(ast: &DeriveInput, db: Database) -> Result<Self, syn::Error> {
+        // INSERT has no WHERE clause, so tp_insert_builder does not take custom conditions
+        Ok(Self::from_ast(ast, db))
+    }
+
     pub fn from_delete_attributes(ast: &DeriveInput, db: Database) -> Result<Self, syn::Error> {
         let mut config = Self::from_ast(ast, db);
 
@@ -216,6 +255,73 @@ impl BuilderConfig {
         Ok(config)
     }
 
+    /// Parse the `cursor = "field1, field2"` fields of the `tp_select_builder` attributes
+    fn parse_cursor_fields(ast: &DeriveInput, fields: &[Field]) -> Result<Vec<Field>, syn::Error> {
+        use syn::{Meta, NestedMeta, Lit};
+
+        let mut names = HashSet::new();
+        for attr in &ast.attrs {
+            if let Ok(Meta::List(meta_list)) = attr.parse_meta() {
+                if meta_list.path.is_ident("tp_select_builder") {
+                    for nested in &meta_list.nested {
+                        if let NestedMeta::Meta(Meta::NameValue(name_value)) = nested {
+                            if !name_value.path.is_ident("cursor") {
+                                continue;
+                            }
+                            let Lit::Str(lit_str) = &name_value.lit else {
+                                return Err(syn::Error::new_spanned(&name_value.lit, "Expected string literal"));
+                            };
+                            for name in lit_str.value().split(',') {
+                                let name = name.trim();
+                                let Some(field) = fields.iter().find(|f| f.ident.as_ref().is_some_and(|x| x == name)) else {
+                                    return Err(syn::Error::new_spanned(lit_str, format!("{name} is not a field in struct")));
+                                };
+                                if !std::ptr::eq(super::unwrap_option_type(&field.ty), &field.ty) {
+                                    return Err(syn::Error::new_spanned(
+                                        lit_str,
+                                        format!("{name} is an Option field, which can not order a keyset pagination"),
+                                    ));
+                                }
+                                names.insert(name.to_string());
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        Ok(fields
+            .iter()
+            .filter(|f| f.ident.as_ref().is_some_and(|x| names.contains(&x.to_string())))
+            .cloned()
+            .collect())
+    }
+
+    /// Parse a `name = true|false` option of the `tp_select_builder` attributes
+    fn parse_bool_option(ast: &DeriveInput, name: &str) -> Result<bool, syn::Error> {
+        use syn::{Meta, NestedMeta, Lit};
+
+        let mut value = false;
+        for attr in &ast.attrs {
+            if let Ok(Meta::List(meta_list)) = attr.parse_meta() {
+                if meta_list.path.is_ident("tp_select_builder") {
+                    for nested in &meta_list.nested {
+                        if let NestedMeta::Meta(Meta::NameValue(name_value)) = nested {
+                            if name_value.path.is_ident(name) {
+                                let Lit::Bool(lit_bool) = &name_value.lit else {
+                                    return Err(syn::Error::new_spanned(&name_value.lit, format!("Expected bool value {name} = true|false")));
+                                };
+                                value = lit_bool.value;
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        Ok(value)
+    }
+
     /// Parse custom conditions from builder attributes
     fn parse_custom_conditions(ast: &DeriveInput, fields: &[Field], db: Database, attr_name: &str) -> Result<Vec<CustomCondition>, syn::Error> {
         use syn::{Meta, NestedMeta, Lit};
@@ -236,6 +342,10 @@ impl BuilderConfig {
                             let method_name = name_value.path.get_ident()
                                 .ok_or_else(|| syn::Error::new_spanned(&name_value.path, "Expected identifier"))?
                                 .to_string();
+                            // Options of the select builder, not conditions
+                            if attr_name == "tp_select_builder" && matches!(method_name.as_str(), "cursor" | "page_type" | "serde" | "find_all_as") {
+                                continue;
+                            }
 
                             if let Lit::Str(lit_str) = &name_value.lit {
                                 let sql_expression = lit_str.value();
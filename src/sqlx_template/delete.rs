@@ -20,6 +20,8 @@ pub fn derive_delete(ast: &DeriveInput, for_path: Option<&syn::Path>, scope: sup
     let db = db.or_else(|| Some(get_database_from_ast(&ast))).expect("Missing db config");
     
     let debug_slow = super::get_debug_slow_from_table_scope(&ast);
+    let soft_delete = super::get_soft_delete_from_table_scope(&ast, db);
+    let tenant = super::get_tenant_from_table_scope(&ast);
     let all_fields = if let syn::Data::Struct(syn::DataStruct {
         fields: syn::Fields::Named(syn::FieldsNamed { ref named, .. }),
         ..
@@ -32,14 +34,15 @@ pub fn derive_delete(ast: &DeriveInput, for_path: Option<&syn::Path>, scope: sup
     let all_columns_name = all_fields.iter().map(|x| get_field_name_as_column(x, db)).collect::<Vec<_>>();
     let mut functions = Vec::new();
     
-    for attr in &ast.attrs {
-        if let Ok(Meta::List(MetaList {
+    for meta in super::get_metas_with_primary_key(ast, &[("tp_delete", "delete_by_pk")]) {
+        if let Meta::List(MetaList {
             ref path,
             ref nested,
             ..
-        })) = attr.parse_meta()
+        }) = meta
         {
             let mut by_fields = Vec::new();
+            let mut by_conditions = HashMap::new();
             let mut fn_name_attr = None;
             let mut return_entity = None;
             let mut debug_slow = debug_slow.clone();
@@ -50,18 +53,13 @@ pub fn derive_delete(ast: &DeriveInput, for_path: Option<&syn::Path>, scope: sup
                         NestedMeta::Meta(Meta::NameValue(nv)) => {
                             if nv.path.is_ident("by") {
                                 if let Lit::Str(lit) = &nv.lit {
-                                    let lit = lit.value();
-                                    let fields_str =
-                                        lit.split(',').map(|x| x.trim()).collect::<Vec<_>>();
-                                    by_fields = super::check_fields(&fields_str, all_fields.clone());
-                                    if super::has_duplicates(&by_fields) {
-                                        panic!("Found duplicated fields: {:?}", fields_str);
+                                    (by_fields, by_conditions) =
+                                        super::parse_by_fields(&lit.value(), all_fields.clone());
+                                    if by_conditions.values().any(|x| x.op == super::ByOp::In) {
+                                        panic!("by = \"<field> in\" is only supported by tp_select_all");
                                     }
-                                    if by_fields.len() != fields_str.len() {
-                                        panic!(
-                                            "One of those value is duplicated or not a field in struct: {:?}",
-                                            fields_str
-                                        );
+                                    if by_conditions.values().any(|x| x.optional) {
+                                        panic!("Optional by fields are not supported by tp_delete");
                                     }
                                 } else {
                                     panic!("Expected string value by = \"...\"");
@@ -112,70 +110,41 @@ pub fn derive_delete(ast: &DeriveInput, for_path: Option<&syn::Path>, scope: sup
                 }
 
                 by_fields.sort_by_key(|x| x.ident.clone());
-                let (fn_name , fn_name_return, fn_name_return_stream)= if let Some(fn_name) = fn_name_attr {
-                    (
-                        Ident::new(
-                            &fn_name,
-                            proc_macro2::Span::call_site(),
-                        ),
-                        Ident::new(
-                            &fn_name,
-                            proc_macro2::Span::call_site(),
-                        ),
-                        Ident::new(
-                            &format!("{fn_name}_stream"),
-                            proc_macro2::Span::call_site(),
-                        ),
-                    )
+                // Checked before the tenant is added to the conditions
+                let unfiltered = by_fields.is_empty() && where_stmt_str.is_none();
+                let prefix = if super::is_unscoped(nested, tenant.as_ref()) {
+                    "unscoped_"
                 } else {
-                    (
-                        Ident::new(
-                            &format!(
-                                "delete_by_{}",
-                                by_fields
-                                    .iter()
-                                    .map(|f| f.ident.as_ref().expect("Must be ident").to_string())
-                                    .collect::<Vec<_>>()
-                                    .join("_and_")
-                            ),
-                            proc_macro2::Span::call_site(),
-                        ),
-                        Ident::new(
-                            &format!(
-                                "delete_by_{}_return",
-                                by_fields
-                                    .iter()
-                                    .map(|f| f.ident.as_ref().expect("Must be ident").to_string())
-                                    .collect::<Vec<_>>()
-                                    .join("_and_")
-                            ),
-                            proc_macro2::Span::call_site(),
-                        ),
-                        Ident::new(
-                            &format!(
-                                "delete_by_{}_return_stream",
-                                by_fields
-                                    .iter()
-                                    .map(|f| f.ident.as_ref().expect("Must be ident").to_string())
-                                    .collect::<Vec<_>>()
-                                    .join("_and_")
-                            ),
-                            proc_macro2::Span::call_site(),
-                        )
-                    )
+                    if let Some(tenant) = &tenant {
+                        tenant.scope(&mut by_fields, &mut by_conditions);
+                    }
+                    ""
                 };
+                let by_names = super::get_by_names(&by_fields, &by_conditions);
+                // Names of the hard delete and restore functions of a soft deleted table
+                let (hard_fn_name, restore_fn_name) = match &fn_name_attr {
+                    Some(fn_name) => (format!("{prefix}hard_{fn_name}"), format!("{prefix}restore_{fn_name}")),
+                    None => (format!("{prefix}hard_delete_by_{by_names}"), format!("{prefix}restore_by_{by_names}")),
+                };
+                let (fn_name, fn_name_return, fn_name_return_stream) = match fn_name_attr {
+                    Some(fn_name) => (
+                        format!("{prefix}{fn_name}"),
+                        format!("{prefix}{fn_name}"),
+                        format!("{prefix}{fn_name}_stream"),
+                    ),
+                    None => (
+                        format!("{prefix}delete_by_{by_names}"),
+                        format!("{prefix}delete_by_{by_names}_return"),
+                        format!("{prefix}delete_by_{by_names}_return_stream"),
+                    ),
+                };
+                let fn_name = Ident::new(&fn_name, proc_macro2::Span::call_site());
+                let fn_name_return = Ident::new(&fn_name_return, proc_macro2::Span::call_site());
+                let fn_name_return_stream = Ident::new(&fn_name_return_stream, proc_macro2::Span::call_site());
 
                 let mut fn_args = by_fields
                     .iter()
-                    .map(|field| {
-                        let arg_name = field.ident.as_ref().unwrap();
-                        let arg_type = &field.ty;
-                        if &arg_type.to_token_stream().to_string() == "String" {
-                            quote! { #arg_name: &'c str }
-                        } else {
-                            quote! { #arg_name: &'c #arg_type }
-                        }
-                    })
+                    .map(|field| super::get_by_condition(&by_conditions, field).gen_arg(field))
                     .collect::<Vec<_>>();
                 let mut binds = by_fields.iter().map(|field| {
                     let arg_name = field.ident.as_ref().unwrap();
@@ -187,10 +156,10 @@ pub fn derive_delete(ast: &DeriveInput, for_path: Option<&syn::Path>, scope: sup
                     .iter()
                     .enumerate()
                     .map(|(index, field)| {
-                        format!(
-                            "{} = ${}",
-                            get_field_name_as_column(field, db),
-                            index + 1
+                        super::get_by_condition(&by_conditions, field).condition(
+                            &get_field_name_as_column(field, db),
+                            &format!("${}", index + 1),
+                            db,
                         )
                     })
                     .collect::<Vec<_>>()
@@ -286,18 +255,29 @@ pub fn derive_delete(ast: &DeriveInput, for_path: Option<&syn::Path>, scope: sup
                         binds.append(&mut bind_vec);
                         let start_counter = by_fields.len() + 1;
                         let (sql, params) = parser::replace_placeholder_with_db(&where_stmt_str, par_res.placeholder_vars, Some(start_counter as i32), db);
-                        where_condition.push(sql);
+                        where_condition.push(format!("({sql})"));
                     
                     } else {
-                        where_condition.push(where_stmt_str);
+                        where_condition.push(format!("({where_stmt_str})"));
                     }
                     
                 }
-                if by_fields.is_empty() && where_condition.is_empty() {
+                if unfiltered {
                     panic!("`by` fields or `where` attribute must not empty");
                 }
                 let mut where_condition =  where_condition.join(" AND ");
-                let sql = format!("DELETE FROM {} WHERE {}", &table_name, where_condition);
+                let hard_sql = format!("DELETE FROM {} WHERE {}", &table_name, where_condition);
+                // A soft delete marks the rows which are not deleted yet
+                let sql = match &soft_delete {
+                    Some(soft_delete) => format!(
+                        "UPDATE {} SET {} WHERE {} AND {}",
+                        &table_name,
+                        soft_delete.delete_set(),
+                        where_condition,
+                        soft_delete.alive_condition()
+                    ),
+                    None => hard_sql.clone(),
+                };
                 
                 super::check_valid_single_sql(&sql, db);
                 let (dbg_before, dbg_after) = super::gen_debug_code(debug_slow);
@@ -307,7 +287,7 @@ pub fn derive_delete(ast: &DeriveInput, for_path: Option<&syn::Path>, scope: sup
                 } else {
                     quote! {#(#fn_args),* ,}
                 };
-                let generated = if return_entity.is_some() && matches!(db, Database::Postgres) {
+                let generated = if return_entity.is_some() && matches!(db, Database::Postgres | Database::Sqlite) {
                     let binds_return = binds.clone();
                     
                     let return_entity = return_entity.unwrap();
@@ -323,7 +303,7 @@ pub fn derive_delete(ast: &DeriveInput, for_path: Option<&syn::Path>, scope: sup
                             (quote! {(#(#field_types),*)}, field_columns.join(", "), quote! {query_as})
                         }
                     };
-                    let sql_return = format!("DELETE FROM {} WHERE {} RETURNING {}", &table_name, where_condition, return_columns);
+                    let sql_return = format!("{} RETURNING {}", sql, return_columns);
                     quote! {
                         pub async fn #fn_name_return<'c, E: sqlx::Executor<'c, Database = #database>>(#args_signature conn: E) -> core::result::Result<Vec<#return_type>, sqlx::Error> {
                             let sql = #sql_return;
@@ -361,6 +341,32 @@ pub fn derive_delete(ast: &DeriveInput, for_path: Option<&syn::Path>, scope: sup
                     }
                 };
                 functions.push(super::gen_with_doc(generated));
+
+                if let Some(soft_delete) = &soft_delete {
+                    let restore_sql = format!(
+                        "UPDATE {} SET {} WHERE {} AND {}",
+                        &table_name,
+                        soft_delete.restore_set(),
+                        where_condition,
+                        soft_delete.deleted_condition()
+                    );
+                    super::check_valid_single_sql(&restore_sql, db);
+                    for (fn_name, sql) in [(hard_fn_name, hard_sql), (restore_fn_name, restore_sql)] {
+                        let fn_name = Ident::new(&fn_name, proc_macro2::Span::call_site());
+                        functions.push(super::gen_with_doc(quote! {
+                            pub async fn #fn_name<'c, E: sqlx::Executor<'c, Database = #database>>(#args_signature conn: E) -> core::result::Result<u64, sqlx::Error> {
+                                let sql = #sql;
+                                #dbg_before
+                                let query = sqlx::query(sql)
+                                    #(#binds)*
+                                    .execute(conn)
+                                    .await;
+                                #dbg_after
+                                Ok(query?.rows_affected())
+                            }
+                        }));
+                    }
+                }
             } 
             
         }
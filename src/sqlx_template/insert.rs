@@ -15,6 +15,7 @@ pub fn derive_insert(ast: &DeriveInput, for_path: Option<&Path>, scope: Scope, d
         None => quote! {#struct_name},
     };
     let mut fields = vec![];
+    let mut all_fields = vec![];
     let debug_slow = super::get_debug_slow_from_table_scope(&ast);
     if let syn::Data::Struct(syn::DataStruct {
         fields: syn::Fields::Named(syn::FieldsNamed { ref named, .. }),
@@ -22,6 +23,7 @@ pub fn derive_insert(ast: &DeriveInput, for_path: Option<&Path>, scope: Scope, d
     }) = ast.data
     {
         named.iter().for_each(|f| {
+            all_fields.push(f);
             if !has_auto_attribute(f) {
                 if let Some(ident) = f.ident.as_ref() {
                     fields.push(ident);
@@ -68,6 +70,8 @@ pub fn derive_insert(ast: &DeriveInput, for_path: Option<&Path>, scope: Scope, d
     });
 
     let binds_return = binds.clone();
+    let binds_many = binds.clone();
+    let binds_many_return = binds.clone();
 
     let database = super::get_database_type(db);
     let (dbg_before, dbg_after) = super::gen_debug_code(debug_slow);
@@ -86,7 +90,7 @@ pub fn derive_insert(ast: &DeriveInput, for_path: Option<&Path>, scope: Scope, d
     let insert = super::gen_with_doc(insert);
 
     
-    let insert_returning = if matches!(db, Database::Postgres) {
+    let insert_returning = if matches!(db, Database::Postgres | Database::Sqlite) {
         super::check_valid_single_sql(&sql_return, db);
         let insert_returning = quote! {
             pub async fn insert_return<'c, E: sqlx::Executor<'c, Database = #database>>(re: &#struct_name, conn: E) -> Result<#struct_name, sqlx::Error> {
@@ -106,18 +110,283 @@ pub fn derive_insert(ast: &DeriveInput, for_path: Option<&Path>, scope: Scope, d
     };
 
 
+    // Multi-row insert: rows are split into chunks so that a single statement never
+    // exceeds the bind parameter limit of the target database. `fields` is not empty, checked above
+    let field_count = fields.len();
+    let chunk_size = max_bind_params(db) / field_count;
+    let chunk_doc = "The rows are inserted in chunks, one statement each, all in one transaction (a savepoint when `conn` \
+        is already a transaction): when a chunk fails, no row is inserted";
+    let sql_many_prefix = format!("INSERT INTO {table_name}({sql_fields}) VALUES ");
+    super::check_valid_single_sql(&format!("{sql_many_prefix}({sql_placeholders}), ({sql_placeholders})"), db);
+    let values_many = gen_values_expr(db, field_count);
+    let insert_many = quote! {
+        #[doc = #chunk_doc]
+        pub async fn insert_many<'c, A: sqlx::Acquire<'c, Database = #database>>(rows: &[#struct_name], conn: A) -> Result<u64, sqlx::Error> {
+            if rows.is_empty() {
+                return Ok(0);
+            }
+            let mut tx = conn.begin().await?;
+            let mut rows_affected = 0;
+            for chunk in rows.chunks(#chunk_size) {
+                let sql = format!("{}{}", #sql_many_prefix, #values_many);
+                #dbg_before
+                let mut query = sqlx::query(&sql);
+                for re in chunk {
+                    query = query #(#binds_many)*;
+                }
+                let query = query.execute(&mut *tx).await;
+                #dbg_after
+                rows_affected += query?.rows_affected();
+            }
+            tx.commit().await?;
+            Ok(rows_affected)
+        }
+    };
+    let insert_many = super::gen_with_doc(insert_many);
+
+    let insert_many_returning = if matches!(db, Database::Postgres | Database::Sqlite) {
+        super::check_valid_single_sql(&format!("{sql_many_prefix}({sql_placeholders}), ({sql_placeholders}) RETURNING *"), db);
+        let insert_many_returning = quote! {
+            #[doc = #chunk_doc]
+            pub async fn insert_many_return<'c, A: sqlx::Acquire<'c, Database = #database>>(rows: &[#struct_name], conn: A) -> Result<Vec<#struct_name>, sqlx::Error> {
+                if rows.is_empty() {
+                    return Ok(vec![]);
+                }
+                let mut tx = conn.begin().await?;
+                let mut res = Vec::with_capacity(rows.len());
+                for chunk in rows.chunks(#chunk_size) {
+                    let sql = format!("{}{} RETURNING *", #sql_many_prefix, #values_many);
+                    #dbg_before
+                    let mut query = sqlx::query_as::<_, #struct_name>(&sql);
+                    for re in chunk {
+                        query = query #(#binds_many_return)*;
+                    }
+                    let query = query.fetch_all(&mut *tx).await;
+                    #dbg_after
+                    res.extend(query?);
+                }
+                tx.commit().await?;
+                Ok(res)
+            }
+        };
+        super::gen_with_doc(insert_many_returning)
+    } else {
+        quote! {}
+    };
+
+    let copy = if matches!(db, Database::Postgres) {
+        gen_copy(&struct_name, &table_name, &sql_fields, &fields, &all_fields, has_copy_out_attribute(ast), debug_slow)
+    } else {
+        quote! {}
+    };
+
+    let mut functions = Vec::new();
+    for attr in &ast.attrs {
+        if let Ok(Meta::List(MetaList {
+            ref path,
+            ref nested,
+            ..
+        })) = attr.parse_meta()
+        {
+            if !path.is_ident("tp_insert") {
+                continue;
+            }
+            let mut fn_name_attr = None;
+            let mut return_entity = None;
+            let mut on_conflict_ignore = false;
+            let mut debug_slow = debug_slow.clone();
+            for meta in nested {
+                match meta {
+                    NestedMeta::Meta(Meta::NameValue(nv)) => {
+                        if nv.path.is_ident("fn_name") {
+                            if let Lit::Str(lit) = &nv.lit {
+                                let lit = lit.value();
+                                fn_name_attr.replace(lit);
+                            }
+                        } else if nv.path.is_ident("returning") {
+                            if let Lit::Str(lit) = &nv.lit {
+                                let lit = lit.value();
+                                let fields_str =
+                                    lit.split(',').map(|x| x.trim()).collect::<Vec<_>>();
+                                let return_fields = super::check_fields(&fields_str, all_fields.clone());
+                                if super::has_duplicates(&return_fields) {
+                                    panic!("Found duplicated fields: {:?}", fields_str);
+                                }
+                                if return_fields.len() != fields_str.len() {
+                                    panic!(
+                                        "One of those value is duplicated or not a field in struct: {:?}",
+                                        fields_str
+                                    );
+                                }
+                                return_entity.replace(return_fields);
+                            } else if let Lit::Bool(lit) = &nv.lit {
+                                if lit.value() {
+                                    return_entity.replace(vec![]);
+                                }
+                            }
+                        } else if nv.path.is_ident("on_conflict") {
+                            if let Lit::Str(lit) = &nv.lit {
+                                match lit.value().trim() {
+                                    "ignore" => on_conflict_ignore = true,
+                                    other => panic!("Unsupported on_conflict value: {other}. Expected \"ignore\""),
+                                }
+                            } else {
+                                panic!("Expected string value on_conflict = \"...\"");
+                            }
+                        } else if nv.path.is_ident("debug") {
+                            if let Lit::Int(lit) = &nv.lit {
+                                let slow_in_ms = lit.base10_parse().expect("Invalid debug value. Must be integer");
+                                debug_slow.replace(slow_in_ms);
+                            }
+                        }
+                    }
+                    _ => {}
+                }
+            }
+
+            let fn_name = match fn_name_attr {
+                Some(fn_name) => fn_name,
+                None => {
+                    let mut fn_name = String::from("insert");
+                    if on_conflict_ignore {
+                        fn_name.push_str("_or_ignore");
+                    }
+                    match &return_entity {
+                        Some(return_fields) if return_fields.is_empty() => fn_name.push_str("_return"),
+                        Some(return_fields) => fn_name.push_str(&format!(
+                            "_return_{}",
+                            return_fields
+                                .iter()
+                                .map(|f| f.ident.as_ref().expect("Must be ident").to_string())
+                                .collect::<Vec<_>>()
+                                .join("_and_")
+                        )),
+                        None => {}
+                    }
+                    if fn_name == "insert" || fn_name == "insert_return" {
+                        panic!("tp_insert would generate `{fn_name}` which already exists. Please specify fn_name");
+                    }
+                    fn_name
+                }
+            };
+            let fn_name = Ident::new(&fn_name, proc_macro2::Span::call_site());
+
+            let sql = if on_conflict_ignore {
+                match db {
+                    Database::Postgres => format!(
+                        "INSERT INTO {table_name}({sql_fields}) VALUES ({sql_placeholders}) ON CONFLICT DO NOTHING"
+                    ),
+                    Database::Sqlite => format!(
+                        "INSERT OR IGNORE INTO {table_name}({sql_fields}) VALUES ({sql_placeholders})"
+                    ),
+                    Database::Mysql => format!(
+                        "INSERT IGNORE INTO {table_name}({sql_fields}) VALUES ({sql_placeholders})"
+                    ),
+                    Database::Any => panic!("on_conflict is not supported for Any database"),
+                }
+            } else {
+                format!("INSERT INTO {table_name}({sql_fields}) VALUES ({sql_placeholders})")
+            };
+            let binds = fields.iter().map(|field| {
+                quote! {
+                    .bind(&re.#field)
+                }
+            });
+            let (dbg_before, dbg_after) = super::gen_debug_code(debug_slow);
+
+            let generated = if let Some(return_entity) = return_entity {
+                if !matches!(db, Database::Postgres | Database::Sqlite) {
+                    panic!("returning is only supported on PostgreSQL and SQLite");
+                }
+                let (return_type, return_columns, query_func) = match return_entity.len() {
+                    0 => (quote! {#struct_name}, "*".into(), quote! {query_as}),
+                    1 => {
+                        let field_type = return_entity[0].clone().ty;
+                        (quote! {#field_type}, super::get_field_name_as_column(&return_entity[0], db), quote! {query_scalar})
+                    }
+                    _ => {
+                        let field_types = return_entity.iter().map(|field| &field.ty);
+                        let field_columns = return_entity.iter().map(|field| super::get_field_name_as_column(field, db)).collect::<Vec<_>>();
+                        (quote! {(#(#field_types),*)}, field_columns.join(", "), quote! {query_as})
+                    }
+                };
+                let sql_return = format!("{sql} RETURNING {return_columns}");
+                super::check_valid_single_sql(&sql_return, db);
+                // An ignored row returns nothing, so the result becomes optional
+                let (fn_return_type, fetch_func) = if on_conflict_ignore {
+                    (quote! {Option<#return_type>}, quote! {fetch_optional})
+                } else {
+                    (return_type.clone(), quote! {fetch_one})
+                };
+                quote! {
+                    pub async fn #fn_name<'c, E: sqlx::Executor<'c, Database = #database>>(re: &#struct_name, conn: E) -> Result<#fn_return_type, sqlx::Error> {
+                        let sql = #sql_return;
+                        #dbg_before
+                        let res = sqlx::#query_func::<_, #return_type>(sql)
+                            #(#binds)*
+                            .#fetch_func(conn)
+                            .await;
+                        #dbg_after
+                        Ok(res?)
+                    }
+                }
+            } else {
+                super::check_valid_single_sql(&sql, db);
+                quote! {
+                    pub async fn #fn_name<'c, E: sqlx::Executor<'c, Database = #database>>(re: &#struct_name, conn: E) -> Result<u64, sqlx::Error> {
+                        let sql = #sql;
+                        #dbg_before
+                        let query = sqlx::query(sql)
+                            #(#binds)*
+                            .execute(conn)
+                            .await;
+                        #dbg_after
+                        Ok(query?.rows_affected())
+                    }
+                }
+            };
+            functions.push(super::gen_with_doc(generated));
+        }
+    }
+
+    // Check for tp_insert_builder attribute and generate builder if present
+    let builder_code = if super::has_attribute(ast, "tp_insert_builder") {
+        let config = super::builder::BuilderConfig::from_insert_attributes(ast, db)?;
+        Some(super::builder::macro_impl::impl_insert_builder(ast, &config))
+    } else {
+        None
+    };
+
     let gen = match scope {
         Scope::Struct => quote! {
             impl #struct_name {
                 #insert
 
                 #insert_returning
+
+                #insert_many
+
+                #insert_many_returning
+
+                #copy
+
+                #(#functions)*
             }
+            #builder_code
         },
         Scope::Mod => quote! {
             #insert
 
             #insert_returning
+
+            #insert_many
+
+            #insert_many_returning
+
+            #copy
+
+            #(#functions)*
+            #builder_code
         },
         super::Scope::NewMod => {
             let new_mod = super::create_ident(&table_name);
@@ -126,6 +395,15 @@ pub fn derive_insert(ast: &DeriveInput, for_path: Option<&Path>, scope: Scope, d
                     #insert
 
                     #insert_returning
+
+                    #insert_many
+
+                    #insert_many_returning
+
+                    #copy
+
+                    #(#functions)*
+                    #builder_code
                 }
             }
         },
@@ -134,6 +412,164 @@ pub fn derive_insert(ast: &DeriveInput, for_path: Option<&Path>, scope: Scope, d
     Ok(gen.into())
 }
 
+/// Maximum number of bind parameters accepted in a single statement
+pub(super) fn max_bind_params(db: Database) -> usize {
+    match db {
+        Database::Postgres => 65535,
+        Database::Mysql => 65535,
+        // SQLite >= 3.32 (bundled with sqlx). Older versions only accept 999
+        Database::Sqlite => 32766,
+        Database::Any => 999,
+    }
+}
+
+/// Generate an expression building the `VALUES` list for the rows of `chunk` at runtime
+fn gen_values_expr(db: Database, field_count: usize) -> TokenStream {
+    match db {
+        Database::Postgres => quote! {
+            (0..chunk.len())
+                .map(|row| {
+                    let placeholders = (1..=#field_count)
+                        .map(|i| format!("${}", row * #field_count + i))
+                        .collect::<Vec<_>>()
+                        .join(", ");
+                    format!("({placeholders})")
+                })
+                .collect::<Vec<_>>()
+                .join(", ")
+        },
+        Database::Sqlite | Database::Mysql | Database::Any => {
+            let row = format!(
+                "({})",
+                (1..=field_count).map(|_| "?").collect::<Vec<_>>().join(", ")
+            );
+            quote! {
+                vec![#row; chunk.len()].join(", ")
+            }
+        }
+    }
+}
+
+/// Generate the PostgreSQL `copy_in` and `copy_out` functions using the binary COPY format.
+/// `copy_out` is only generated with `#[tp_copy_out]`
+fn gen_copy(
+    struct_name: &TokenStream,
+    table_name: &str,
+    sql_fields: &str,
+    fields: &[&Ident],
+    all_fields: &[&Field],
+    copy_out: bool,
+    debug_slow: Option<i32>,
+) -> TokenStream {
+    let db = Database::Postgres;
+    let sql_copy_in = format!("COPY {table_name} ({sql_fields}) FROM STDIN (FORMAT binary)");
+    let sql_all_fields = all_fields
+        .iter()
+        .map(|f| super::get_field_name_as_column(f, db))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let sql_copy_out = format!("COPY {table_name} ({sql_all_fields}) TO STDOUT (FORMAT binary)");
+    // `FROM STDIN` expects inline data when parsed, so only the export statement is validated
+    super::check_valid_single_sql(&sql_copy_out, db);
+    let field_count = fields.len() as i16;
+    let (dbg_before, dbg_after) = super::gen_debug_code(debug_slow);
+
+    // Each field is written as its length followed by the binary value, or -1 for NULL
+    let encodes = fields.iter().map(|field| {
+        quote! {
+            let mut buf = sqlx::postgres::PgArgumentBuffer::default();
+            match <_ as sqlx::Encode<'_, sqlx::Postgres>>::encode_by_ref(&re.#field, &mut buf) {
+                Ok(sqlx::encode::IsNull::No) => {
+                    data.extend_from_slice(&(buf.len() as i32).to_be_bytes());
+                    data.extend_from_slice(&buf);
+                }
+                Ok(sqlx::encode::IsNull::Yes) => data.extend_from_slice(&(-1i32).to_be_bytes()),
+                Err(e) => {
+                    copy.abort(e.to_string()).await?;
+                    return Err(sqlx::Error::Encode(e));
+                }
+            }
+        }
+    });
+
+    let copy_in = quote! {
+        pub async fn copy_in<'r>(rows: impl IntoIterator<Item = &'r #struct_name>, conn: &mut sqlx::PgConnection) -> Result<u64, sqlx::Error> {
+            let sql = #sql_copy_in;
+            #dbg_before
+            let mut copy = conn.copy_in_raw(sql).await?;
+            // Header: signature, flags and length of the header extension area
+            let mut data = b"PGCOPY\n\xff\r\n\0".to_vec();
+            data.extend_from_slice(&0i32.to_be_bytes());
+            data.extend_from_slice(&0i32.to_be_bytes());
+            for re in rows {
+                data.extend_from_slice(&#field_count.to_be_bytes());
+                #(#encodes)*
+                if data.len() >= sqlx::postgres::PG_COPY_MAX_DATA_LEN {
+                    copy.send(std::mem::take(&mut data)).await?;
+                }
+            }
+            // Trailer
+            data.extend_from_slice(&(-1i16).to_be_bytes());
+            copy.send(data).await?;
+            let res = copy.finish().await;
+            #dbg_after
+            res
+        }
+    };
+
+    // Decoding the rows needs the `CopyDecode` implementations of the runtime crate
+    let copy_out = if copy_out {
+        let decodes = all_fields.iter().enumerate().map(|(index, field)| {
+            let field = field.ident.as_ref().unwrap();
+            quote! { #field: row.get(#index).map_err(sqlx::Error::Decode)?, }
+        });
+        let copy_out = quote! {
+            pub async fn copy_out<'c>(conn: &'c mut sqlx::PgConnection) -> Result<futures::stream::BoxStream<'c, Result<#struct_name, sqlx::Error>>, sqlx::Error> {
+                let sql = #sql_copy_out;
+                #dbg_before
+                let stream = conn.copy_out_raw(sql).await;
+                #dbg_after
+                let mut reader = sqlx_template_runtime::CopyReader::new();
+                let rows = futures::StreamExt::flat_map(stream?, move |chunk| {
+                    let mut rows = Vec::new();
+                    match chunk {
+                        Ok(data) => {
+                            reader.push(&data);
+                            loop {
+                                match reader.next_row() {
+                                    Ok(Some(row)) => rows.push((|| Ok(#struct_name { #(#decodes)* }))()),
+                                    Ok(None) => break,
+                                    Err(e) => {
+                                        rows.push(Err(sqlx::Error::Decode(e)));
+                                        break;
+                                    }
+                                }
+                            }
+                        }
+                        Err(e) => rows.push(Err(e)),
+                    }
+                    futures::stream::iter(rows)
+                });
+                Ok(futures::StreamExt::boxed(rows))
+            }
+        };
+        super::gen_with_doc(copy_out)
+    } else {
+        quote! {}
+    };
+
+    let copy_in = super::gen_with_doc(copy_in);
+    quote! {
+        #copy_in
+
+        #copy_out
+    }
+}
+
 fn has_auto_attribute(field: &Field) -> bool {
     field.attrs.iter().any(|attr| attr.path.is_ident("auto"))
 }
+
+fn has_copy_out_attribute(ast: &DeriveInput) -> bool {
+    ast.attrs.iter().any(|attr| attr.path.is_ident("tp_copy_out"))
+}
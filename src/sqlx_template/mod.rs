@@ -3,7 +3,7 @@ use std::{
 };
 
 use proc_macro2::{Span, TokenStream};
-use quote::quote;
+use quote::{quote, ToTokens};
 use rust_format::{Formatter, RustFmt};
 use sqlparser::{dialect::{Dialect, GenericDialect, MySqlDialect, PostgreSqlDialect, SQLiteDialect}, parser::Parser};
 use syn::{
@@ -21,6 +21,7 @@ pub mod raw;
 pub mod ddl;
 pub mod proc;
 pub mod builder;
+pub mod relation;
 
 #[derive(Debug, Default, Clone, Copy)]
 pub(super) enum Scope {
@@ -215,6 +216,314 @@ pub fn get_database_from_input_fn(input: &ItemFn) -> Database {
 }
 
 
+pub fn get_max_page_size_from_table_scope(ast: &DeriveInput) -> Option<i32> {
+    let max_page_sizes: Vec<i32> = ast
+        .attrs
+        .iter()
+        .filter_map(|attr| {
+            if let Ok(Meta::NameValue(MetaNameValue {
+                path,
+                lit: Lit::Int(max_page_size),
+                ..
+            })) = attr.parse_meta()
+            {
+                if path.is_ident("max_page_size") {
+                    Some(max_page_size.base10_parse().expect("Invalid max_page_size value"))
+                } else {
+                    None
+                }
+            } else {
+                None
+            }
+        })
+        .collect::<Vec<_>>();
+    match max_page_sizes.len() {
+        0 => None,
+        1 => Some(*max_page_sizes.first().unwrap()),
+        _ => panic!("More than one max_page_size attribute was found"),
+    }
+}
+
+/// Soft delete column of the struct-level `#[tp_soft_delete(column = "deleted_at")]`. Selects and
+/// updates skip the rows where it is set, and deletes set it instead of removing the rows
+#[derive(Clone)]
+pub struct SoftDelete {
+    pub column: String,
+    /// Field of the column, which updates do not set
+    pub field: Field,
+}
+
+impl SoftDelete {
+    /// Condition of the rows which are not deleted
+    pub fn alive_condition(&self) -> String {
+        format!("{} IS NULL", self.column)
+    }
+
+    /// Condition of the deleted rows
+    pub fn deleted_condition(&self) -> String {
+        format!("{} IS NOT NULL", self.column)
+    }
+
+    /// SET clause of a soft delete
+    pub fn delete_set(&self) -> String {
+        format!("{} = CURRENT_TIMESTAMP", self.column)
+    }
+
+    /// SET clause of a restore
+    pub fn restore_set(&self) -> String {
+        format!("{} = NULL", self.column)
+    }
+}
+
+pub fn get_soft_delete_from_table_scope(ast: &DeriveInput, db: Database) -> Option<SoftDelete> {
+    let columns = ast
+        .attrs
+        .iter()
+        .filter_map(|attr| match attr.parse_meta() {
+            Ok(Meta::List(MetaList { path, nested, .. })) if path.is_ident("tp_soft_delete") => {
+                let column = nested.iter().find_map(|meta| match meta {
+                    NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident("column") => match &nv.lit {
+                        Lit::Str(lit) => Some(lit.value()),
+                        _ => panic!("Expected string value column = \"...\""),
+                    },
+                    _ => None,
+                });
+                Some(column.expect("Missing column of tp_soft_delete, e.g. #[tp_soft_delete(column = \"deleted_at\")]"))
+            }
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    let column = match columns.len() {
+        0 => return None,
+        1 => columns[0].trim().to_string(),
+        _ => panic!("More than one tp_soft_delete attribute was found"),
+    };
+    let Data::Struct(syn::DataStruct { fields: Fields::Named(named), .. }) = &ast.data else {
+        panic!("tp_soft_delete only works with structs with named fields");
+    };
+    let field = named
+        .named
+        .iter()
+        .find(|field| get_field_name(field) == column)
+        .unwrap_or_else(|| panic!("Soft delete column {column} is not a field in struct"));
+    Some(SoftDelete {
+        column: get_field_name_as_column(field, db),
+        field: field.clone(),
+    })
+}
+
+/// Tenant field of the struct-level `#[tp_tenant(column = "tenant_id")]`. Generated functions take the
+/// tenant as their first argument and only access its rows, except the `unscoped_` ones
+#[derive(Clone)]
+pub struct Tenant {
+    pub field: Field,
+}
+
+impl Tenant {
+    /// Adds the tenant as the first `by` field of a scoped function, left out of its default name
+    pub(super) fn scope(&self, by_fields: &mut Vec<Field>, by_conditions: &mut HashMap<String, ByCondition>) {
+        let name = get_field_name(&self.field);
+        if by_fields.iter().any(|x| get_field_name(x) == name) {
+            panic!("The tenant column {name} is added to the conditions of scoped functions and must not be in by = \"...\"");
+        }
+        by_fields.insert(0, self.field.clone());
+        by_conditions.insert(name, ByCondition { op: ByOp::Eq, optional: false, tenant: true });
+    }
+
+    /// Argument of the tenant, `&str` for a `String` field
+    pub fn gen_arg(&self, lifetime: TokenStream) -> TokenStream {
+        let arg_name = self.field.ident.as_ref().unwrap();
+        let arg_type = self.gen_type(lifetime);
+        quote! { #arg_name: #arg_type }
+    }
+
+    /// Type of the tenant argument
+    pub fn gen_type(&self, lifetime: TokenStream) -> TokenStream {
+        let arg_type = &self.field.ty;
+        if &arg_type.to_token_stream().to_string() == "String" {
+            quote! { &#lifetime str }
+        } else {
+            quote! { &#lifetime #arg_type }
+        }
+    }
+}
+
+pub fn get_tenant_from_table_scope(ast: &DeriveInput) -> Option<Tenant> {
+    let columns = ast
+        .attrs
+        .iter()
+        .filter_map(|attr| match attr.parse_meta() {
+            Ok(Meta::List(MetaList { path, nested, .. })) if path.is_ident("tp_tenant") => {
+                let column = nested.iter().find_map(|meta| match meta {
+                    NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident("column") => match &nv.lit {
+                        Lit::Str(lit) => Some(lit.value()),
+                        _ => panic!("Expected string value column = \"...\""),
+                    },
+                    _ => None,
+                });
+                Some(column.expect("Missing column of tp_tenant, e.g. #[tp_tenant(column = \"tenant_id\")]"))
+            }
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    let column = match columns.len() {
+        0 => return None,
+        1 => columns[0].trim().to_string(),
+        _ => panic!("More than one tp_tenant attribute was found"),
+    };
+    let Data::Struct(syn::DataStruct { fields: Fields::Named(named), .. }) = &ast.data else {
+        panic!("tp_tenant only works with structs with named fields");
+    };
+    let field = named
+        .named
+        .iter()
+        .find(|field| get_field_name(field) == column)
+        .unwrap_or_else(|| panic!("Tenant column {column} is not a field in struct"));
+    Some(Tenant { field: field.clone() })
+}
+
+/// Whether a `tp_*` attribute is declared with `unscoped = true`, opting out of the tenant scope
+pub(super) fn is_unscoped(nested: &syn::punctuated::Punctuated<NestedMeta, Token![,]>, tenant: Option<&Tenant>) -> bool {
+    let unscoped = nested.iter().any(|meta| match meta {
+        NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident("unscoped") => match &nv.lit {
+            Lit::Bool(lit) => lit.value,
+            _ => panic!("Expected bool value unscoped = true|false"),
+        },
+        _ => false,
+    });
+    if unscoped && tenant.is_none() {
+        panic!("unscoped requires a struct-level tp_tenant attribute");
+    }
+    unscoped
+}
+
+/// Fields marked with `#[primary_key]`, sorted by name like the `by` fields
+pub fn get_primary_key_fields(ast: &DeriveInput) -> Vec<Field> {
+    let Data::Struct(data) = &ast.data else {
+        return Vec::new();
+    };
+    let mut fields = data
+        .fields
+        .iter()
+        .filter(|field| field.attrs.iter().any(|attr| attr.path.is_ident("primary_key")))
+        .cloned()
+        .collect::<Vec<_>>();
+    fields.sort_by_key(|x| x.ident.clone());
+    fields
+}
+
+/// Attributes of the struct, followed by the attributes of the functions by primary key,
+/// e.g. `tp_update(by = "id", fn_name = "update_by_pk")` for `("tp_update", "update_by_pk")`
+pub(super) fn get_metas_with_primary_key(ast: &DeriveInput, pk_functions: &[(&str, &str)]) -> Vec<Meta> {
+    let mut metas = ast.attrs.iter().filter_map(|attr| attr.parse_meta().ok()).collect::<Vec<_>>();
+    let pk_fields = get_primary_key_fields(ast);
+    if pk_fields.is_empty() {
+        return metas;
+    }
+    let by = pk_fields.iter().map(get_field_name).collect::<Vec<_>>().join(", ");
+    for (attr_name, fn_name) in pk_functions {
+        let path = quote::format_ident!("{}", attr_name);
+        metas.push(syn::parse_quote! { #path(by = #by, fn_name = #fn_name) });
+    }
+    metas
+}
+
+/// Paging code shared by the generated paging functions. With `page_type = true`, they take an
+/// `impl Into<sqlx_template_runtime::PageRequest>` and return a `sqlx_template_runtime::Page<T>`,
+/// otherwise an `impl Into<(i64, i32, bool)>` and a `(Vec<T>, Option<i64>)`
+pub struct PageCode {
+    /// Type of the `page` argument
+    pub request: TokenStream,
+    /// Type of the returned page
+    pub response: TokenStream,
+    /// Reads `page` into `offset: i64`, `limit: i32` (rows to fetch) and `count: bool`
+    pub prelude: TokenStream,
+    /// Builds the returned page from `data: Vec<T>` and `count: Option<i64>`
+    pub result: TokenStream,
+}
+
+pub fn gen_page_code(struct_name: &TokenStream, max_page_size: Option<i32>, page_type: bool) -> PageCode {
+    if page_type {
+        let clamp = max_page_size.map(|max| quote! { let page = page.clamp_size(#max); });
+        PageCode {
+            request: quote! { impl Into<sqlx_template_runtime::PageRequest> },
+            response: quote! { sqlx_template_runtime::Page<#struct_name> },
+            prelude: quote! {
+                let page: sqlx_template_runtime::PageRequest = page.into();
+                #clamp
+                let offset = page.row_offset();
+                // One extra row tells whether there is a next page
+                let limit = page.limit().saturating_add(1);
+                let count = page.with_total;
+            },
+            result: quote! { Ok(sqlx_template_runtime::Page::new(data, count, &page)) },
+        }
+    } else {
+        let clamp = max_page_size.map(|max| quote! { let limit = limit.min(#max); });
+        PageCode {
+            request: quote! { impl Into<(i64, i32, bool)> },
+            response: quote! { (Vec<#struct_name>, Option<i64>) },
+            prelude: quote! {
+                let (offset, limit, count) = page.into();
+                #clamp
+            },
+            result: quote! { Ok((data, count)) },
+        }
+    }
+}
+
+/// Constant block of a projection into another struct, whose field names are given by the
+/// `columns` expression (the `COLUMNS` of its `sqlx_template_runtime::Columns` impl), failing the compilation
+/// if one of those fields is not a field of the source struct
+pub fn gen_projection_check(columns: &TokenStream, into_name: &str, struct_name: &str, all_fields: &[&Field]) -> TokenStream {
+    let field_names = all_fields.iter().map(|x| get_field_name(x));
+    let error = format!("Every field of {into_name} must be a field of {struct_name}");
+    quote! {
+        {
+            let columns: &[&str] = #columns;
+            let fields: &[&str] = &[#(#field_names),*];
+            let mut i = 0;
+            while i < columns.len() {
+                let column = columns[i].as_bytes();
+                let mut found = false;
+                let mut j = 0;
+                while !found && j < fields.len() {
+                    let field = fields[j].as_bytes();
+                    if field.len() == column.len() {
+                        let mut k = 0;
+                        while k < field.len() && field[k] == column[k] {
+                            k += 1;
+                        }
+                        found = k == field.len();
+                    }
+                    j += 1;
+                }
+                if !found {
+                    panic!(#error);
+                }
+                i += 1;
+            }
+        }
+    }
+}
+
+/// Expression of the comma separated column list of a projection, whose field names are given
+/// by the `columns` expression
+pub fn gen_projection_select_list(columns: &TokenStream, all_fields: &[&Field], db: Database) -> TokenStream {
+    let field_names = all_fields.iter().map(|x| get_field_name(x));
+    let column_names = all_fields.iter().map(|x| get_field_name_as_column(x, db));
+    quote! {
+        #columns
+            .iter()
+            .map(|column| match *column {
+                #(#field_names => #column_names,)*
+                _ => column,
+            })
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
 pub fn get_debug_slow_from_table_scope(ast: &DeriveInput) -> Option<i32> {
     let struct_name = &ast.ident;
     let debug_slows : Vec<i32> = ast
@@ -262,6 +571,223 @@ fn check_fields<'a>(fields_from_attr: &Vec<&'a str>, all_fields: Vec<&'a Field>)
     by_fields
 }
 
+/// Comparison of a `by` field with its argument, e.g. `by = "org, created_at >=, email like"`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(super) enum ByOp {
+    Eq,
+    /// `by = "id in"`: the argument is a slice of values
+    In,
+    Ne,
+    Gt,
+    Gte,
+    Lt,
+    Lte,
+    Like,
+    /// Case insensitive `LIKE`, `LOWER(column) LIKE LOWER(?)` outside of PostgreSQL
+    ILike,
+}
+
+impl ByOp {
+    /// Suffix of the field in default function names, e.g. `created_at_gte`
+    pub(super) fn name_suffix(self) -> &'static str {
+        match self {
+            ByOp::Eq => "",
+            ByOp::In => "_in",
+            ByOp::Ne => "_ne",
+            ByOp::Gt => "_gt",
+            ByOp::Gte => "_gte",
+            ByOp::Lt => "_lt",
+            ByOp::Lte => "_lte",
+            ByOp::Like => "_like",
+            ByOp::ILike => "_ilike",
+        }
+    }
+
+    /// Condition comparing a column with a placeholder. `In` conditions are built by the callers
+    pub(super) fn condition(self, column: &str, placeholder: &str, db: Database) -> String {
+        let op = match self {
+            ByOp::Eq => "=",
+            ByOp::In => panic!("`in` fields are not supported here"),
+            ByOp::Ne => "<>",
+            ByOp::Gt => ">",
+            ByOp::Gte => ">=",
+            ByOp::Lt => "<",
+            ByOp::Lte => "<=",
+            ByOp::Like => "LIKE",
+            ByOp::ILike => match db {
+                Database::Postgres => "ILIKE",
+                Database::Sqlite | Database::Mysql | Database::Any => {
+                    return format!("LOWER({column}) LIKE LOWER({placeholder})")
+                }
+            },
+        };
+        format!("{column} {op} {placeholder}")
+    }
+
+    /// Argument of a `by` field. Patterns are `&str`, and comparisons take the inner type of `Option` fields
+    pub(super) fn gen_arg(self, field: &Field) -> TokenStream {
+        let arg_name = field.ident.as_ref().unwrap();
+        let arg_type = match self {
+            ByOp::Eq => &field.ty,
+            ByOp::In => {
+                let arg_type = &field.ty;
+                return quote! { #arg_name: &'c [#arg_type] };
+            }
+            ByOp::Like | ByOp::ILike => return quote! { #arg_name: &'c str },
+            ByOp::Ne | ByOp::Gt | ByOp::Gte | ByOp::Lt | ByOp::Lte => unwrap_option_type(&field.ty),
+        };
+        if &arg_type.to_token_stream().to_string() == "String" {
+            quote! { #arg_name: &'c str }
+        } else {
+            quote! { #arg_name: &'c #arg_type }
+        }
+    }
+}
+
+/// Condition of a `by` field: its comparison, whether a `None` argument drops it (`by = "status?"`),
+/// and whether it is the tenant added by `tp_tenant`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(super) struct ByCondition {
+    pub(super) op: ByOp,
+    pub(super) optional: bool,
+    pub(super) tenant: bool,
+}
+
+impl ByCondition {
+    /// Condition of the field. An optional condition also holds when the argument is `NULL`,
+    /// e.g. `($2 IS NULL OR status = $2)`, and needs its argument bound twice outside of PostgreSQL
+    pub(super) fn condition(self, column: &str, placeholder: &str, db: Database) -> String {
+        let condition = self.op.condition(column, placeholder, db);
+        if self.optional {
+            format!("({placeholder} IS NULL OR {condition})")
+        } else {
+            condition
+        }
+    }
+
+    /// Argument of the field, wrapped in `Option` when the condition is optional
+    pub(super) fn gen_arg(self, field: &Field) -> TokenStream {
+        if !self.optional {
+            return self.op.gen_arg(field);
+        }
+        let arg_name = field.ident.as_ref().unwrap();
+        let arg_type = match self.op {
+            ByOp::Like | ByOp::ILike => quote! { str },
+            _ => {
+                let arg_type = unwrap_option_type(&field.ty);
+                if &arg_type.to_token_stream().to_string() == "String" {
+                    quote! { str }
+                } else {
+                    quote! { #arg_type }
+                }
+            }
+        };
+        quote! { #arg_name: Option<&'c #arg_type> }
+    }
+
+    /// Binding of the argument, twice for an optional condition with `?` placeholders
+    pub(super) fn gen_bind(self, arg_name: &Ident, db: Database) -> TokenStream {
+        match (self.optional, db) {
+            (true, Database::Sqlite | Database::Mysql | Database::Any) => quote! { .bind(#arg_name).bind(#arg_name) },
+            _ => quote! { .bind(#arg_name) },
+        }
+    }
+}
+
+fn extract_by_field_and_op(str: &str) -> (&str, ByCondition) {
+    // A trailing `?` makes the condition optional, e.g. `status?` or `created_at >=?`
+    let (str, optional) = match str.strip_suffix('?') {
+        Some(str) => (str.trim_end(), true),
+        None => (str, false),
+    };
+    // The operator may follow the field name without space, e.g. `score<`
+    let (field, op) = match str.find(|c: char| c.is_whitespace() || "<>=!".contains(c)) {
+        Some(index) => (&str[..index], str[index..].trim()),
+        None => (str, ""),
+    };
+    let op = match op.to_lowercase().as_str() {
+        "" | "=" => ByOp::Eq,
+        "in" => ByOp::In,
+        "!=" | "<>" => ByOp::Ne,
+        ">" => ByOp::Gt,
+        ">=" => ByOp::Gte,
+        "<" => ByOp::Lt,
+        "<=" => ByOp::Lte,
+        "like" => ByOp::Like,
+        "ilike" => ByOp::ILike,
+        _ => panic!("Expected by = \"<field name> [=|!=|>|>=|<|<=|like|ilike|in][?]\", found {str:?}"),
+    };
+    if optional && op == ByOp::In {
+        panic!("`in` fields can not be optional: {field}");
+    }
+    (field, ByCondition { op, optional, tenant: false })
+}
+
+/// Parse `by = "..."` into the fields, sorted by name, and the condition of each of them
+fn parse_by_fields(lit: &str, all_fields: Vec<&Field>) -> (Vec<Field>, HashMap<String, ByCondition>) {
+    let by_entries = lit
+        .split(',')
+        .map(|x| extract_by_field_and_op(x.trim()))
+        .collect::<Vec<_>>();
+    let fields_str = by_entries.iter().map(|(name, _)| *name).collect::<Vec<_>>();
+    let mut by_fields = check_fields(&fields_str, all_fields);
+    if has_duplicates(&by_fields) {
+        panic!("Found duplicated fields: {:?}", fields_str);
+    }
+    if by_fields.len() != fields_str.len() {
+        panic!(
+            "One of those value is duplicated or not a field in struct: {:?}",
+            fields_str
+        );
+    }
+    by_fields.sort_by_key(|x| x.ident.clone());
+    let by_conditions = by_entries
+        .into_iter()
+        .map(|(name, condition)| (name.to_string(), condition))
+        .collect::<HashMap<_, _>>();
+    (by_fields, by_conditions)
+}
+
+/// Condition of a `by` field, a mandatory `=` when not given
+fn get_by_condition(by_conditions: &HashMap<String, ByCondition>, field: &Field) -> ByCondition {
+    by_conditions
+        .get(&get_field_name(field))
+        .copied()
+        .unwrap_or(ByCondition { op: ByOp::Eq, optional: false, tenant: false })
+}
+
+/// Name of a `by` field in default function names, e.g. `created_at_gte`
+fn get_by_field_name(by_conditions: &HashMap<String, ByCondition>, field: &Field) -> String {
+    format!("{}{}", get_field_name(field), get_by_condition(by_conditions, field).op.name_suffix())
+}
+
+/// Names of the `by` fields in default function names, without the tenant, e.g. `created_at_gte_and_org`
+fn get_by_names(by_fields: &[Field], by_conditions: &HashMap<String, ByCondition>) -> String {
+    by_fields
+        .iter()
+        .filter(|field| !get_by_condition(by_conditions, field).tenant)
+        .map(|field| get_by_field_name(by_conditions, field))
+        .collect::<Vec<_>>()
+        .join("_and_")
+}
+
+/// Documents the optional `by` fields of a generated function
+fn gen_optional_by_doc(by_fields: &[Field], by_conditions: &HashMap<String, ByCondition>) -> TokenStream {
+    let optional_fields = by_fields
+        .iter()
+        .filter(|field| get_by_condition(by_conditions, field).optional)
+        .map(|field| format!("`{}`", get_field_name(field)))
+        .collect::<Vec<_>>();
+    if optional_fields.is_empty() {
+        return quote! {};
+    }
+    let doc = format!(
+        "Optional filters: {}. A `None` argument drops its condition.\n",
+        optional_fields.join(", ")
+    );
+    quote! { #[doc = #doc] }
+}
+
 fn is_integer_type(ty: &Type) -> bool {
     match ty {
         Type::Path(type_path) => {
@@ -298,6 +824,66 @@ fn is_option_type(type_path: &syn::TypePath) -> bool {
     }
 }
 
+/// Kind of a field type for aggregate functions
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(super) enum AggKind {
+    Integer,
+    Float,
+    /// Decimal types, summed and averaged as themselves
+    Decimal,
+    /// Other types, which only support MIN and MAX
+    Other,
+}
+
+/// Inner type of an `Option` field, or the field type itself
+pub(super) fn unwrap_option_type(ty: &Type) -> &Type {
+    if let Type::Path(type_path) = ty {
+        if is_option_type(type_path) {
+            if let PathArguments::AngleBracketed(args) = &type_path.path.segments.last().unwrap().arguments {
+                if let Some(GenericArgument::Type(inner)) = args.args.first() {
+                    return inner;
+                }
+            }
+        }
+    }
+    ty
+}
+
+pub(super) fn get_agg_kind(ty: &Type) -> AggKind {
+    match unwrap_option_type(ty) {
+        Type::Path(type_path) => match type_path.path.segments.last().map(|x| x.ident.to_string()).as_deref() {
+            Some("i8" | "i16" | "i32" | "i64" | "isize" | "u8" | "u16" | "u32" | "u64" | "usize") => AggKind::Integer,
+            Some("f32" | "f64") => AggKind::Float,
+            Some("Decimal" | "BigDecimal") => AggKind::Decimal,
+            _ => AggKind::Other,
+        },
+        _ => AggKind::Other,
+    }
+}
+
+/// SQL expression and Rust type of the aggregate `func` (sum, avg, min or max) of a column.
+/// SUM and AVG of integers and floats are widened to `i64` and `f64`, with casts where the
+/// database returns another type (e.g. `NUMERIC` for `SUM(BIGINT)` on PostgreSQL). An integer
+/// sum beyond the range of `i64` fails the query (`bigint out of range` on PostgreSQL, an integer
+/// overflow on SQLite) and is truncated with a warning by MySQL
+pub(super) fn gen_aggregate(func: &str, column: &str, ty: &Type, db: Database) -> (String, TokenStream) {
+    let inner = unwrap_option_type(ty);
+    match (func, get_agg_kind(ty), db) {
+        ("min" | "max", _, _) => (format!("{}({column})", func.to_uppercase()), quote! { #inner }),
+        ("sum", AggKind::Integer, Database::Postgres) => (format!("CAST(SUM({column}) AS BIGINT)"), quote! { i64 }),
+        ("sum", AggKind::Integer, Database::Mysql) => (format!("CAST(SUM({column}) AS SIGNED)"), quote! { i64 }),
+        ("sum", AggKind::Integer, _) => (format!("SUM({column})"), quote! { i64 }),
+        ("sum", AggKind::Float, Database::Postgres) => (format!("CAST(SUM({column}) AS DOUBLE PRECISION)"), quote! { f64 }),
+        ("sum", AggKind::Float, _) => (format!("SUM({column})"), quote! { f64 }),
+        ("avg", AggKind::Integer | AggKind::Float, Database::Postgres) => (format!("CAST(AVG({column}) AS DOUBLE PRECISION)"), quote! { f64 }),
+        ("avg", AggKind::Integer | AggKind::Float, Database::Mysql) => (format!("CAST(AVG({column}) AS DOUBLE)"), quote! { f64 }),
+        ("avg", AggKind::Integer | AggKind::Float, _) => (format!("AVG({column})"), quote! { f64 }),
+        ("sum" | "avg", AggKind::Decimal, _) => (format!("{}({column})", func.to_uppercase()), quote! { #inner }),
+        ("sum" | "avg", AggKind::Other, _) => panic!("{func} of {column} requires a numeric field"),
+        _ => panic!("Expected func = \"sum|avg|min|max\""),
+    }
+}
+
 fn gen_debug_code(debug_slow: Option<i32>) -> (TokenStream, TokenStream) {
     match debug_slow {
         Some(0) => {
@@ -358,16 +944,47 @@ fn gen_debug_code(debug_slow: Option<i32>) -> (TokenStream, TokenStream) {
 
 
 
-pub fn table_name_derive(ast: &DeriveInput) -> syn::Result<TokenStream> {
+pub fn table_name_derive(ast: &DeriveInput, db: Option<Database>) -> syn::Result<TokenStream> {
     let struct_name = &ast.ident;
 
     let table_name = get_table_name(&ast);
+    // The rows visible to the relation loaders of other structs, like the default selects
+    let db = db.unwrap_or_else(|| match has_attribute(ast, "db") {
+        true => get_database_from_ast(ast),
+        false => Database::Any,
+    });
+    let soft_delete = get_soft_delete_from_table_scope(ast, db);
+    let tenant = get_tenant_from_table_scope(ast);
+    let filter = select::RowFilter::new(soft_delete.as_ref(), tenant.as_ref(), false, false, false);
+    let condition = match filter.condition {
+        Some(condition) => quote! { Some(#condition) },
+        None => quote! { None },
+    };
+    let tenant_column = match filter.tenant {
+        Some(tenant) => {
+            let column = check_column_name(get_field_name(&tenant.field), db);
+            quote! { Some(#column) }
+        }
+        None => quote! { None },
+    };
     let expanded = quote!{
         impl #struct_name {
             #[inline]
             pub const fn table_name() -> &'static str {
                 #table_name
             }
+
+            /// Condition on the `tp_soft_delete` column skipping the deleted rows
+            #[inline]
+            pub const fn soft_delete_condition() -> Option<&'static str> {
+                #condition
+            }
+
+            /// Column of the `tp_tenant` scope
+            #[inline]
+            pub const fn tenant_column() -> Option<&'static str> {
+                #tenant_column
+            }
         }
     };
 
@@ -455,7 +1072,7 @@ pub fn has_attribute(input: &DeriveInput, attr_name: &str) -> bool {
 }
 
 pub fn derive_all(input: &DeriveInput, for_path: Option<&syn::Path>, scope: Scope, db: Option<Database>) -> syn::Result<TokenStream> {
-    let table_name = table_name_derive(&input)?;
+    let table_name = table_name_derive(&input, db)?;
     let insert = insert::derive_insert(&input, for_path, scope, db)?;
     let update = update::derive_update(&input, for_path, scope, db)?;
     let select = select::derive_select(&input, for_path, scope, db)?;
@@ -226,12 +226,23 @@ pub fn query_derive(input: ItemFn, args: AttributeArgs, mode: Option<Mode>, db:
     // Generate bind statement by param extracted from query
 
 
+    // A `sqlx_template_runtime::Page<T>` return type takes a `PageRequest` and returns a `Page<T>`,
+    // a `Page<T>` one keeps the `(i64, i32, bool)` tuple and returns `(Vec<T>, Option<i64>)`
+    let mut page_type = false;
+
     // Extract the return type and determine the SQLx fetch function
     let (output, fetch_call, return_type, query_type, data_type) = match &input.sig.output {
         ReturnType::Type(_, ty) => {
             match ty.as_ref() {
                 Type::Path(type_path) => {
-                    let segment = &type_path.path.segments.first().unwrap();
+                    let mut segment = type_path.path.segments.first().unwrap();
+                    if segment.ident == "sqlx_template_runtime" && type_path.path.segments.len() == 2 {
+                        segment = type_path.path.segments.last().unwrap();
+                        if segment.ident != "Page" {
+                            panic!("Only sqlx_template_runtime::Page<T> is supported as a return type from sqlx_template_runtime");
+                        }
+                        page_type = true;
+                    }
                     match segment.ident.to_string().as_str() {
                         "Option" => {
                             let generic = get_nested_type_to_token_stream(&segment.arguments).unwrap();
@@ -312,8 +323,9 @@ pub fn query_derive(input: ItemFn, args: AttributeArgs, mode: Option<Mode>, db:
                         },
                         "Page" => {
                             let generic = get_nested_type_to_token_stream(&segment.arguments).unwrap();
+                            let response = super::gen_page_code(&generic, None, page_type).response;
                             (
-                                quote! { Result<(Vec<#generic>, Option<i64>), sqlx::Error> },
+                                quote! { Result<#response, sqlx::Error> },
                                 quote! { },
                                 Some(generic),
                                 QueryType::Page,
@@ -535,18 +547,20 @@ pub fn query_derive(input: ItemFn, args: AttributeArgs, mode: Option<Mode>, db:
                 }
             }).collect::<Vec<_>>();
             let call_args_clone = call_args.clone();
-            
+            let super::PageCode {
+                request: page_request,
+                prelude: page_prelude,
+                result: page_result,
+                ..
+            } = super::gen_page_code(return_type.as_ref().unwrap(), None, page_type);
 
             quote! {
-                pub async fn #fn_name<'c, E: sqlx::Executor<'c, Database = #database> + Copy>(#fn_args_with_comma page: impl Into<(i64, i32, bool)>, conn: E) -> #output {
+                pub async fn #fn_name<'c, E: sqlx::Executor<'c, Database = #database> + Copy>(#fn_args_with_comma page: #page_request, conn: E) -> #output {
                     #data_query_fn
 
                     #count_query_fn
 
-                    let page = page.into();
-                    let offset = page.0;
-                    let limit = page.1;
-                    let count = page.2;
+                    #page_prelude
                     let data = data_query(#(#call_args),* , offset, limit, conn).await?;
                     let count = if count {
                         if data.is_empty() && offset == 0 {
@@ -558,7 +572,7 @@ pub fn query_derive(input: ItemFn, args: AttributeArgs, mode: Option<Mode>, db:
                     } else {
                         None
                     };
-                    Ok((data, count))
+                    #page_result
                 }   
             }
 
@@ -0,0 +1,368 @@
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+use syn::{DeriveInput, Field, Ident, Lit, Meta, MetaList, NestedMeta, Path, Type};
+
+use super::{check_column_name, get_field_name, select::RowFilter, Database};
+
+/// Relation declared by `tp_belongs_to` or `tp_has_many`
+enum Relation {
+    /// The foreign key is a field of this struct, referencing the key of the target
+    BelongsTo,
+    /// The foreign key is a field of the target, referencing a key of this struct
+    HasMany,
+}
+
+/// Generate the loaders of the relations declared with `#[tp_belongs_to(Target, fk = "...")]`
+/// and `#[tp_has_many(Target, fk = "...")]`. The target table name and the filter of its rows are read
+/// from its `TableName` derive
+pub fn build_relation_functions(
+    ast: &DeriveInput,
+    struct_name: &TokenStream,
+    all_fields: &Vec<&Field>,
+    self_receiver: bool,
+    debug_slow: Option<i32>,
+    db: Database,
+) -> Vec<TokenStream> {
+    let mut functions = Vec::new();
+    let tenant = super::get_tenant_from_table_scope(ast);
+    for attr in &ast.attrs {
+        let Ok(Meta::List(MetaList { path, nested, .. })) = attr.parse_meta() else {
+            continue;
+        };
+        let relation = if path.is_ident("tp_belongs_to") {
+            Relation::BelongsTo
+        } else if path.is_ident("tp_has_many") {
+            Relation::HasMany
+        } else {
+            continue;
+        };
+        let mut target = None;
+        let mut fk = None;
+        let mut key = None;
+        let mut name = None;
+        for meta in &nested {
+            match meta {
+                NestedMeta::Meta(Meta::Path(path)) => {
+                    if target.replace(path.clone()).is_some() {
+                        panic!("Expected a single target struct in {}", path.to_token_stream());
+                    }
+                }
+                NestedMeta::Meta(Meta::NameValue(nv)) if !nv.path.is_ident("unscoped") => {
+                    let Lit::Str(lit) = &nv.lit else {
+                        panic!("Expected string value {} = \"...\"", nv.path.to_token_stream());
+                    };
+                    if nv.path.is_ident("fk") {
+                        fk.replace(lit.value());
+                    } else if nv.path.is_ident("references") || nv.path.is_ident("key") {
+                        key.replace(lit.value());
+                    } else if nv.path.is_ident("name") {
+                        name.replace(lit.value());
+                    }
+                }
+                _ => {}
+            }
+        }
+        let target = target.expect("Missing target struct, e.g. #[tp_belongs_to(Organization, fk = \"org_id\")]");
+        let fk = fk.expect("Missing fk = \"...\"");
+        // The loaders of a tenant-scoped struct only load the rows of the tenant, except the `unscoped_` ones
+        let unscoped = super::is_unscoped(&nested, tenant.as_ref());
+        let filter = RowFilter::new(None, tenant.as_ref(), false, false, unscoped);
+        let (load_fn, load_for_fn) = match relation {
+            Relation::BelongsTo => {
+                let fk_field = find_field(all_fields, &fk);
+                let references = key.unwrap_or_else(|| "id".to_string());
+                let name = name.unwrap_or_else(|| fk.strip_suffix("_id").unwrap_or(&fk).to_string());
+                build_belongs_to(struct_name, &target, &filter, fk_field, &references, &name, self_receiver, debug_slow, db)
+            }
+            Relation::HasMany => {
+                // The key defaults to a single primary key field, then to `id`
+                let key = key.unwrap_or_else(|| match super::get_primary_key_fields(ast).as_slice() {
+                    [pk_field] => get_field_name(pk_field),
+                    _ => "id".to_string(),
+                });
+                let key_field = find_field(all_fields, &key);
+                let name = name.unwrap_or_else(|| {
+                    let target_name = target.segments.last().unwrap().ident.to_string();
+                    format!("{}s", super::builder::to_snake_case(&target_name))
+                });
+                build_has_many(struct_name, &target, &filter, key_field, &fk, &name, self_receiver, debug_slow, db)
+            }
+        };
+        functions.push(super::gen_with_doc(load_fn));
+        functions.push(super::gen_with_doc(load_for_fn));
+    }
+    functions
+}
+
+fn find_field<'a>(all_fields: &[&'a Field], name: &str) -> &'a Field {
+    all_fields
+        .iter()
+        .find(|field| field.ident.as_ref().is_some_and(|x| x == name))
+        .unwrap_or_else(|| panic!("{name} is not a field in struct"))
+}
+
+/// Whether the field is an `Option`, whose `None` values are not loaded
+fn is_optional(field: &Field) -> bool {
+    matches!(&field.ty, Type::Path(type_path) if super::is_option_type(type_path))
+}
+
+/// Chunks of the keys, SQL selecting the rows of the target whose `column` is one of the keys of a chunk,
+/// and the code expanding its placeholders at runtime. PostgreSQL binds all the keys as a single array,
+/// the other databases split them into chunks within their bind parameter limit
+fn build_in_sql(target: &Path, filter: &RowFilter, column: &str, db: Database) -> (TokenStream, TokenStream, TokenStream) {
+    let column = check_column_name(column.to_string(), db);
+    let (chunks, sql, binds) = match db {
+        Database::Postgres => {
+            let sql = format!("SELECT * FROM {{}} WHERE {column} = ANY($1)");
+            (
+                quote! { [keys.as_slice()] },
+                quote! { format!(#sql, #target::table_name()) },
+                quote! { let query = query.bind(keys); },
+            )
+        }
+        Database::Sqlite | Database::Mysql | Database::Any => {
+            // The tenant is bound after the keys
+            let chunk_size = super::insert::max_bind_params(db) - usize::from(filter.tenant.is_some());
+            let sql = format!("SELECT * FROM {{}} WHERE {column} IN ({{}})");
+            (
+                quote! { keys.chunks(#chunk_size) },
+                quote! { format!(#sql, #target::table_name(), vec!["?"; keys.len()].join(", ")) },
+                quote! { let query = keys.iter().fold(query, |query, key| query.bind(key)); },
+            )
+        }
+    };
+    let (sql, tenant_bind) = build_filter_sql(target, filter, sql, db);
+    (chunks, sql, quote! { #binds #tenant_bind })
+}
+
+/// SQL selecting the rows of the target whose `column` is the key, and the code binding the tenant after the key
+fn build_eq_sql(target: &Path, filter: &RowFilter, column: &str, db: Database) -> (TokenStream, TokenStream) {
+    let column = check_column_name(column.to_string(), db);
+    let placeholder = match db {
+        Database::Postgres => "$1",
+        Database::Sqlite | Database::Mysql | Database::Any => "?",
+    };
+    let sql = format!("SELECT * FROM {{}} WHERE {column} = {placeholder}");
+    build_filter_sql(target, filter, quote! { format!(#sql, #target::table_name()) }, db)
+}
+
+/// Appends the `tp_soft_delete` condition of the target and, in the tenant scope, its `tp_tenant` column bound
+/// to the tenant argument. The target of a struct without `tp_tenant` must not be tenant-scoped
+fn build_filter_sql(target: &Path, filter: &RowFilter, sql: TokenStream, db: Database) -> (TokenStream, TokenStream) {
+    let target_name = target.to_token_stream().to_string().replace(' ', "");
+    let (tenant_column, tenant_sql, tenant_bind) = match &filter.tenant {
+        Some(tenant) => {
+            let message = format!("{target_name} is not scoped by tp_tenant, load it with unscoped = true");
+            let placeholder = match db {
+                Database::Postgres => "$2",
+                Database::Sqlite | Database::Mysql | Database::Any => "?",
+            };
+            let tenant_sql = format!(" AND {{}} = {placeholder}");
+            let tenant_arg = tenant.field.ident.as_ref().unwrap();
+            (
+                quote! {
+                    const TENANT_COLUMN: &str = match #target::tenant_column() {
+                        Some(column) => column,
+                        None => panic!(#message),
+                    };
+                },
+                quote! { sql.push_str(&format!(#tenant_sql, TENANT_COLUMN)); },
+                quote! { let query = query.bind(#tenant_arg); },
+            )
+        }
+        None if filter.prefix.is_empty() => {
+            let message = format!("{target_name} is scoped by tp_tenant, add tp_tenant to this struct to load it");
+            (
+                quote! { const _: () = assert!(#target::tenant_column().is_none(), #message); },
+                quote! {},
+                quote! {},
+            )
+        }
+        None => (quote! {}, quote! {}, quote! {}),
+    };
+    let sql = quote! {
+        {
+            #tenant_column
+            let mut sql = #sql;
+            if let Some(condition) = #target::soft_delete_condition() {
+                sql.push_str(" AND ");
+                sql.push_str(condition);
+            }
+            #tenant_sql
+            sql
+        }
+    };
+    (sql, tenant_bind)
+}
+
+/// Tenant argument of the loaders in the tenant scope, before the connection
+fn tenant_arg(filter: &RowFilter) -> TokenStream {
+    match &filter.tenant {
+        Some(tenant) => {
+            let arg = tenant.gen_arg(quote! {});
+            quote! { #arg, }
+        }
+        None => quote! {},
+    }
+}
+
+fn receiver(struct_name: &TokenStream, self_receiver: bool) -> (TokenStream, TokenStream) {
+    if self_receiver {
+        (quote! { &self }, quote! { self })
+    } else {
+        (quote! { re: &#struct_name }, quote! { re })
+    }
+}
+
+fn build_belongs_to(
+    struct_name: &TokenStream,
+    target: &Path,
+    filter: &RowFilter,
+    fk_field: &Field,
+    references: &str,
+    name: &str,
+    self_receiver: bool,
+    debug_slow: Option<i32>,
+    db: Database,
+) -> (TokenStream, TokenStream) {
+    let database = super::get_database_type(db);
+    let (dbg_before, dbg_after) = super::gen_debug_code(debug_slow);
+    let fk = fk_field.ident.as_ref().unwrap();
+    let key_type = super::unwrap_option_type(&fk_field.ty);
+    let references_ident = Ident::new(references, proc_macro2::Span::call_site());
+    let load_fn = filter.fn_ident(quote::format_ident!("load_{}", name));
+    let load_for_fn = filter.fn_ident(quote::format_ident!("load_{}s_for", name));
+    let (receiver, record) = receiver(struct_name, self_receiver);
+    let tenant_arg = tenant_arg(filter);
+    let (eq_sql, tenant_bind) = build_eq_sql(target, filter, references, db);
+    let (chunks, in_sql, in_binds) = build_in_sql(target, filter, references, db);
+    let (fk_value, keys) = if is_optional(fk_field) {
+        (
+            quote! {
+                let Some(key) = &#record.#fk else {
+                    return Ok(None);
+                };
+            },
+            quote! { records.iter().filter_map(|record| record.#fk.clone()) },
+        )
+    } else {
+        (
+            quote! { let key = &#record.#fk; },
+            quote! { records.iter().map(|record| record.#fk.clone()) },
+        )
+    };
+    let doc = format!("Load the `{}` referenced by `{}`", target.to_token_stream(), get_field_name(fk_field));
+    let load_for_doc = format!(
+        "Load the `{}` referenced by `{}` of the records with a single query (one per chunk of keys within the bind parameter limit of SQLite and MySQL), by their `{references}`",
+        target.to_token_stream(),
+        get_field_name(fk_field)
+    );
+    let load = quote! {
+        #[doc = #doc]
+        pub async fn #load_fn<'c, E: sqlx::Executor<'c, Database = #database> + 'c>(#receiver, #tenant_arg conn: E) -> Result<Option<#target>, sqlx::Error> {
+            #fk_value
+            let sql = #eq_sql;
+            #dbg_before
+            let query = sqlx::query_as::<_, #target>(&sql).bind(key);
+            #tenant_bind
+            let query_result = query.fetch_optional(conn).await;
+            #dbg_after
+            Ok(query_result?)
+        }
+    };
+    let load_for = quote! {
+        #[doc = #load_for_doc]
+        pub async fn #load_for_fn<'c, A: sqlx::Acquire<'c, Database = #database>>(records: &[#struct_name], #tenant_arg conn: A) -> Result<std::collections::HashMap<#key_type, #target>, sqlx::Error> {
+            let keys = #keys
+                .collect::<std::collections::HashSet<#key_type>>()
+                .into_iter()
+                .collect::<Vec<_>>();
+            if keys.is_empty() {
+                return Ok(std::collections::HashMap::new());
+            }
+            let mut conn = conn.acquire().await?;
+            let mut targets = std::collections::HashMap::new();
+            for keys in #chunks {
+                let sql = #in_sql;
+                #dbg_before
+                let query = sqlx::query_as::<_, #target>(&sql);
+                #in_binds
+                let query_result = query.fetch_all(&mut *conn).await;
+                #dbg_after
+                targets.extend(query_result?.into_iter().map(|row| (row.#references_ident.clone(), row)));
+            }
+            Ok(targets)
+        }
+    };
+    (load, load_for)
+}
+
+fn build_has_many(
+    struct_name: &TokenStream,
+    target: &Path,
+    filter: &RowFilter,
+    key_field: &Field,
+    fk: &str,
+    name: &str,
+    self_receiver: bool,
+    debug_slow: Option<i32>,
+    db: Database,
+) -> (TokenStream, TokenStream) {
+    let database = super::get_database_type(db);
+    let (dbg_before, dbg_after) = super::gen_debug_code(debug_slow);
+    let key = key_field.ident.as_ref().unwrap();
+    let key_type = &key_field.ty;
+    let fk_ident = Ident::new(fk, proc_macro2::Span::call_site());
+    let load_fn = filter.fn_ident(quote::format_ident!("load_{}", name));
+    let load_for_fn = filter.fn_ident(quote::format_ident!("load_{}_for", name));
+    let (receiver, record) = receiver(struct_name, self_receiver);
+    let tenant_arg = tenant_arg(filter);
+    let (eq_sql, tenant_bind) = build_eq_sql(target, filter, fk, db);
+    let (chunks, in_sql, in_binds) = build_in_sql(target, filter, fk, db);
+    let doc = format!("Load the `{}` whose `{fk}` references `{}`", target.to_token_stream(), get_field_name(key_field));
+    let load_for_doc = format!(
+        "Load the `{}` of the records with a single query (one per chunk of keys within the bind parameter limit of SQLite and MySQL), grouped by their `{fk}`",
+        target.to_token_stream()
+    );
+    let load = quote! {
+        #[doc = #doc]
+        pub async fn #load_fn<'c, E: sqlx::Executor<'c, Database = #database> + 'c>(#receiver, #tenant_arg conn: E) -> Result<Vec<#target>, sqlx::Error> {
+            let sql = #eq_sql;
+            #dbg_before
+            let query = sqlx::query_as::<_, #target>(&sql).bind(&#record.#key);
+            #tenant_bind
+            let query_result = query.fetch_all(conn).await;
+            #dbg_after
+            Ok(query_result?)
+        }
+    };
+    let load_for = quote! {
+        #[doc = #load_for_doc]
+        pub async fn #load_for_fn<'c, A: sqlx::Acquire<'c, Database = #database>>(records: &[#struct_name], #tenant_arg conn: A) -> Result<std::collections::HashMap<#key_type, Vec<#target>>, sqlx::Error> {
+            let keys = records
+                .iter()
+                .map(|record| record.#key.clone())
+                .collect::<std::collections::HashSet<#key_type>>()
+                .into_iter()
+                .collect::<Vec<_>>();
+            if keys.is_empty() {
+                return Ok(std::collections::HashMap::new());
+            }
+            let mut conn = conn.acquire().await?;
+            let mut children = std::collections::HashMap::<#key_type, Vec<#target>>::new();
+            for keys in #chunks {
+                let sql = #in_sql;
+                #dbg_before
+                let query = sqlx::query_as::<_, #target>(&sql);
+                #in_binds
+                let query_result = query.fetch_all(&mut *conn).await;
+                #dbg_after
+                for row in query_result? {
+                    children.entry(row.#fk_ident.clone()).or_default().push(row);
+                }
+            }
+            Ok(children)
+        }
+    };
+    (load, load_for)
+}
@@ -4,7 +4,7 @@ use proc_macro2::TokenStream;
 use quote::{quote, ToTokens};
 use syn::{
     parse_macro_input, token::Eq, Attribute, Data, DeriveInput, Field, Fields, Ident, Lit, LitStr,
-    Meta, MetaList, MetaNameValue, NestedMeta, Token,
+    Meta, MetaList, MetaNameValue, NestedMeta, Token, Type,
 };
 
 use crate::{
@@ -12,7 +12,7 @@ use crate::{
     sqlx_template::{check_column_name, get_database_from_ast, get_field_name, get_field_name_as_column, Database},
 };
 
-use super::{get_database_type, get_table_name, Scope};
+use super::{get_database_type, get_table_name, ByCondition, ByOp, Scope};
 
 #[derive(Debug, PartialEq)]
 enum SelectType {
@@ -21,6 +21,7 @@ enum SelectType {
     Page,
     Stream,
     Count,
+    Exists,
 }
 
 pub fn derive_select(
@@ -37,6 +38,9 @@ pub fn derive_select(
     let table_name = get_table_name(&ast);
     let db = db.or_else(|| Some(get_database_from_ast(&ast))).expect("Missing db config");
     let debug_slow = super::get_debug_slow_from_table_scope(&ast);
+    let max_page_size = super::get_max_page_size_from_table_scope(&ast);
+    let soft_delete = super::get_soft_delete_from_table_scope(&ast, db);
+    let tenant = super::get_tenant_from_table_scope(&ast);
     let all_fields = if let syn::Data::Struct(syn::DataStruct {
         fields: syn::Fields::Named(syn::FieldsNamed { ref named, .. }),
         ..
@@ -47,42 +51,50 @@ pub fn derive_select(
         panic!("SelectTemplate macro only works with structs with named fields");
     };
     let mut functions = Vec::new();
-    for attr in &ast.attrs {
-        if let Ok(Meta::List(MetaList {
+    // Item-level constants checking the fields of the `into` structs, evaluated even if the function is never used
+    let mut projection_checks = Vec::new();
+    for meta in super::get_metas_with_primary_key(ast, &[("tp_select_one", "find_by_pk"), ("tp_select_exists", "exists_by_pk")]) {
+        // A bare #[tp_select_exists] generates exists_all
+        let meta = match meta {
+            Meta::Path(path) if path.is_ident("tp_select_exists") => syn::parse_quote! { tp_select_exists() },
+            meta => meta,
+        };
+        if let Meta::List(MetaList {
             ref path,
             ref nested,
             ..
-        })) = attr.parse_meta()
+        }) = meta
         {
             if path.is_ident("tp_select_all")
                 || path.is_ident("tp_select_one")
                 || path.is_ident("tp_select_page")
                 || path.is_ident("tp_select_stream")
                 || path.is_ident("tp_select_count")
+                || path.is_ident("tp_select_exists")
             {
                 let mut by_fields = Vec::new();
                 let mut order_fields = Vec::new();
                 let mut fn_name = None;
                 let mut debug_slow = debug_slow.clone();
+                let mut max_page_size = max_page_size;
                 let mut where_stmt_str = None;
+                let mut into = None;
+                let mut by_conditions = HashMap::new();
+                let mut keep_order = false;
+                let mut lock = None;
+                let mut skip_locked = false;
+                let mut nowait = false;
+                let mut group_fields = Vec::new();
+                let mut with_deleted = false;
+                let mut only_deleted = false;
+                let mut page_type = false;
                 for meta in nested {
                     match meta {
                         NestedMeta::Meta(Meta::NameValue(nv)) => {
                             if nv.path.is_ident("by") {
                                 if let Lit::Str(lit) = &nv.lit {
-                                    let lit = lit.value();
-                                    let fields_str =
-                                        lit.split(',').map(|x| x.trim()).collect::<Vec<_>>();
-                                    by_fields = check_by_fields(&fields_str, all_fields.clone());
-                                    if super::has_duplicates(&by_fields) {
-                                        panic!("Found duplicated fields: {:?}", fields_str);
-                                    }
-                                    if by_fields.len() != fields_str.len() {
-                                        panic!(
-                                            "One of those value is duplicated or not a field in struct: {:?}",
-                                            fields_str
-                                        );
-                                    }
+                                    (by_fields, by_conditions) =
+                                        super::parse_by_fields(&lit.value(), all_fields.clone());
                                 } else {
                                     panic!("Expected string value by = \"...\"");
                                 }
@@ -130,14 +142,172 @@ pub fn derive_select(
                                         .expect("Invalid debug value. Must be integer");
                                     debug_slow.replace(slow_in_ms);
                                 }
+                            } else if nv.path.is_ident("into") {
+                                if let Lit::Str(lit) = &nv.lit {
+                                    let path: syn::Path = lit
+                                        .parse()
+                                        .unwrap_or_else(|_| panic!("Invalid into value: {}", lit.value()));
+                                    into.replace(path);
+                                } else {
+                                    panic!("Expected string value into = \"...\"");
+                                }
+                            } else if nv.path.is_ident("group_by") {
+                                if let Lit::Str(lit) = &nv.lit {
+                                    let lit = lit.value();
+                                    // Keep the declared order, it is the order of the key columns
+                                    group_fields = lit
+                                        .split(',')
+                                        .map(|x| x.trim())
+                                        .map(|name| {
+                                            let field = all_fields
+                                                .iter()
+                                                .find(|f| f.ident.as_ref().is_some_and(|x| x == name))
+                                                .unwrap_or_else(|| panic!("{name} is not a field in struct"));
+                                            (*field).clone()
+                                        })
+                                        .collect::<Vec<_>>();
+                                    if super::has_duplicates(&group_fields) {
+                                        panic!("Found duplicated fields: {:?}", lit);
+                                    }
+                                } else {
+                                    panic!("Expected string value group_by = \"...\"");
+                                }
+                            } else if nv.path.is_ident("keep_order") {
+                                if let Lit::Bool(lit) = &nv.lit {
+                                    keep_order = lit.value;
+                                } else {
+                                    panic!("Expected bool value keep_order = true|false");
+                                }
+                            } else if nv.path.is_ident("lock") {
+                                if let Lit::Str(lit) = &nv.lit {
+                                    lock.replace(lit.value());
+                                } else {
+                                    panic!("Expected string value lock = \"update|share|no_key_update\"");
+                                }
+                            } else if nv.path.is_ident("with_deleted") {
+                                if let Lit::Bool(lit) = &nv.lit {
+                                    with_deleted = lit.value;
+                                } else {
+                                    panic!("Expected bool value with_deleted = true|false");
+                                }
+                            } else if nv.path.is_ident("only_deleted") {
+                                if let Lit::Bool(lit) = &nv.lit {
+                                    only_deleted = lit.value;
+                                } else {
+                                    panic!("Expected bool value only_deleted = true|false");
+                                }
+                            } else if nv.path.is_ident("skip_locked") {
+                                if let Lit::Bool(lit) = &nv.lit {
+                                    skip_locked = lit.value;
+                                } else {
+                                    panic!("Expected bool value skip_locked = true|false");
+                                }
+                            } else if nv.path.is_ident("nowait") {
+                                if let Lit::Bool(lit) = &nv.lit {
+                                    nowait = lit.value;
+                                } else {
+                                    panic!("Expected bool value nowait = true|false");
+                                }
+                            } else if nv.path.is_ident("max_page_size") {
+                                if let Lit::Int(lit) = &nv.lit {
+                                    let size = lit
+                                        .base10_parse()
+                                        .expect("Invalid max_page_size value. Must be integer");
+                                    max_page_size.replace(size);
+                                }
+                            } else if nv.path.is_ident("page_type") {
+                                if let Lit::Bool(lit) = &nv.lit {
+                                    page_type = lit.value;
+                                } else {
+                                    panic!("Expected bool value page_type = true|false");
+                                }
                             }
                         }
                         _ => {}
                     }
                 }
 
-                by_fields.sort_by_key(|x| x.ident.clone());
+                if page_type && !path.is_ident("tp_select_page") {
+                    panic!("page_type is only supported by tp_select_page");
+                }
+                if let Some(into) = &into {
+                    let check = super::gen_projection_check(
+                        &quote! { <#into as sqlx_template_runtime::Columns>::COLUMNS },
+                        &into.to_token_stream().to_string().replace(' ', ""),
+                        &struct_name.to_string(),
+                        &all_fields,
+                    );
+                    projection_checks.push(quote! { const _: () = #check; });
+                }
                 order_fields.sort_by_key(|x| x.0.ident.clone());
+                let row_filter = RowFilter::new(
+                    soft_delete.as_ref(),
+                    tenant.as_ref(),
+                    with_deleted,
+                    only_deleted,
+                    super::is_unscoped(nested, tenant.as_ref()),
+                );
+                row_filter.scope(&mut by_fields, &mut by_conditions);
+                let in_fields = by_conditions
+                    .iter()
+                    .filter(|(_, condition)| condition.op == ByOp::In)
+                    .map(|(name, _)| name.clone())
+                    .collect::<Vec<_>>();
+                let optional_doc = super::gen_optional_by_doc(&by_fields, &by_conditions);
+
+                let lock_clause = build_lock_clause(lock.as_deref(), skip_locked, nowait, db);
+
+                if !group_fields.is_empty() {
+                    if !path.is_ident("tp_select_count") {
+                        panic!("group_by is only supported by tp_select_count");
+                    }
+                    if where_stmt_str.is_some() || !in_fields.is_empty() {
+                        panic!("group_by does not support where and `in` fields");
+                    }
+                    let generated = super::gen_with_doc(build_count_group_query(
+                        &table_name,
+                        by_fields,
+                        &by_conditions,
+                        group_fields,
+                        &row_filter,
+                        fn_name,
+                        debug_slow,
+                        db,
+                    ));
+                    functions.push(quote! { #optional_doc #generated });
+                    continue;
+                }
+
+                if keep_order && in_fields.len() != 1 {
+                    panic!("keep_order requires exactly one `in` field in by = \"...\"");
+                }
+                if !in_fields.is_empty() {
+                    if !path.is_ident("tp_select_all") {
+                        panic!("by = \"<field> in\" is only supported by tp_select_all");
+                    }
+                    if where_stmt_str.is_some() || into.is_some() {
+                        panic!("by = \"<field> in\" does not support where and into");
+                    }
+                    if keep_order && !order_fields.is_empty() {
+                        panic!("keep_order can not be used with order");
+                    }
+                    let generated = super::gen_with_doc(build_in_query(
+                        &struct_name,
+                        &table_name,
+                        &all_fields,
+                        by_fields,
+                        &by_conditions,
+                        order_fields,
+                        fn_name,
+                        keep_order,
+                        &lock_clause,
+                        &row_filter,
+                        debug_slow,
+                        db,
+                    ));
+                    functions.push(quote! { #optional_doc #generated });
+                    continue;
+                }
 
                 let generated = match path.get_ident().unwrap().to_string().as_str() {
                     "tp_select_all" => build_query(
@@ -146,10 +316,16 @@ pub fn derive_select(
                         &table_name,
                         &all_fields,
                         by_fields,
+                        &by_conditions,
                         order_fields,
                         fn_name,
                         where_stmt_str,
+                        into.clone(),
+                        &lock_clause,
+                        &row_filter,
                         debug_slow,
+                        max_page_size,
+                        page_type,
                         db,
                     )?,
                     "tp_select_one" => build_query(
@@ -158,10 +334,16 @@ pub fn derive_select(
                         &table_name,
                         &all_fields,
                         by_fields,
+                        &by_conditions,
                         order_fields,
                         fn_name,
                         where_stmt_str,
+                        into.clone(),
+                        &lock_clause,
+                        &row_filter,
                         debug_slow,
+                        max_page_size,
+                        page_type,
                         db,
                     )?,
                     "tp_select_page" => build_query(
@@ -170,10 +352,16 @@ pub fn derive_select(
                         &table_name,
                         &all_fields,
                         by_fields,
+                        &by_conditions,
                         order_fields,
                         fn_name,
                         where_stmt_str,
+                        into.clone(),
+                        &lock_clause,
+                        &row_filter,
                         debug_slow,
+                        max_page_size,
+                        page_type,
                         db,
                     )?,
                     "tp_select_stream" => build_query(
@@ -182,10 +370,16 @@ pub fn derive_select(
                         &table_name,
                         &all_fields,
                         by_fields,
+                        &by_conditions,
                         order_fields,
                         fn_name,
                         where_stmt_str,
+                        into.clone(),
+                        &lock_clause,
+                        &row_filter,
                         debug_slow,
+                        max_page_size,
+                        page_type,
                         db,
                     )?,
                     "tp_select_count" => build_query(
@@ -194,41 +388,159 @@ pub fn derive_select(
                         &table_name,
                         &all_fields,
                         by_fields,
+                        &by_conditions,
+                        order_fields,
+                        fn_name,
+                        where_stmt_str,
+                        into.clone(),
+                        &lock_clause,
+                        &row_filter,
+                        debug_slow,
+                        max_page_size,
+                        page_type,
+                        db,
+                    )?,
+                    "tp_select_exists" => build_query(
+                        SelectType::Exists,
+                        &struct_name,
+                        &table_name,
+                        &all_fields,
+                        by_fields,
+                        &by_conditions,
                         order_fields,
                         fn_name,
                         where_stmt_str,
+                        into.clone(),
+                        &lock_clause,
+                        &row_filter,
                         debug_slow,
+                        max_page_size,
+                        page_type,
                         db,
                     )?,
                     _ => None,
                 };
                 if let Some(generated) = generated {
-                    functions.push(super::gen_with_doc(generated));
+                    let generated = super::gen_with_doc(generated);
+                    functions.push(quote! { #optional_doc #generated });
                 }
-            } else {
+            } else if path.is_ident("tp_select_agg") {
+                functions.push(build_agg_query(
+                    &table_name,
+                    &all_fields,
+                    nested,
+                    &RowFilter::new(
+                        soft_delete.as_ref(),
+                        tenant.as_ref(),
+                        false,
+                        false,
+                        super::is_unscoped(nested, tenant.as_ref()),
+                    ),
+                    debug_slow,
+                    db,
+                ));
+            } else if path.is_ident("tp_select_cursor") {
+                functions.push(build_cursor_query(
+                    &ast.ident,
+                    &struct_name,
+                    &table_name,
+                    &all_fields,
+                    nested,
+                    &RowFilter::new(
+                        soft_delete.as_ref(),
+                        tenant.as_ref(),
+                        false,
+                        false,
+                        super::is_unscoped(nested, tenant.as_ref()),
+                    ),
+                    debug_slow,
+                    db,
+                ));
             }
         }
     }
-    functions.push(super::gen_with_doc(build_default_find_all_query(
+    let row_filter = RowFilter::new(soft_delete.as_ref(), tenant.as_ref(), false, false, false);
+    if row_filter.tenant.is_some() {
+        // The default functions of a tenant table only return the rows of the tenant
+        for (qtype, fn_name) in [
+            (SelectType::All, "find_all"),
+            (SelectType::Count, "count_all"),
+            (SelectType::Page, "find_page_all"),
+        ] {
+            let mut by_fields = Vec::new();
+            let mut by_conditions = HashMap::new();
+            row_filter.scope(&mut by_fields, &mut by_conditions);
+            let generated = build_query(
+                qtype,
+                &struct_name,
+                &table_name,
+                &all_fields,
+                by_fields,
+                &by_conditions,
+                Vec::new(),
+                Some(fn_name.to_string()),
+                None,
+                None,
+                "",
+                &row_filter,
+                debug_slow,
+                max_page_size,
+                false,
+                db,
+            )?;
+            functions.extend(generated.map(super::gen_with_doc));
+        }
+    } else {
+        functions.push(super::gen_with_doc(build_default_find_all_query(
+            &struct_name,
+            &table_name,
+            &row_filter,
+            debug_slow,
+            &all_fields,
+            db,
+        )));
+        functions.push(super::gen_with_doc(build_default_count_all_query(
+            &struct_name,
+            &table_name,
+            &row_filter,
+            debug_slow,
+            db,
+        )));
+        functions.push(super::gen_with_doc(build_default_find_page_all_query(
+            &struct_name,
+            &table_name,
+            &row_filter,
+            debug_slow,
+            max_page_size,
+            &all_fields,
+            db,
+        )));
+    }
+
+    let pk_fields = super::get_primary_key_fields(ast);
+    if !pk_fields.is_empty() {
+        let (get_fn, reload_fn) = build_pk_functions(
+            &struct_name,
+            &table_name,
+            &all_fields,
+            pk_fields,
+            &row_filter,
+            matches!(scope, super::Scope::Struct),
+            debug_slow,
+            db,
+        );
+        functions.push(super::gen_with_doc(get_fn));
+        functions.push(super::gen_with_doc(reload_fn));
+    }
+
+    functions.extend(super::relation::build_relation_functions(
+        ast,
         &struct_name,
-        &table_name,
-        debug_slow,
         &all_fields,
-        db,
-    )));
-    functions.push(super::gen_with_doc(build_default_count_all_query(
-        &struct_name,
-        &table_name,
-        debug_slow,
-        db,
-    )));
-    functions.push(super::gen_with_doc(build_default_find_page_all_query(
-        &struct_name,
-        &table_name,
+        matches!(scope, super::Scope::Struct),
         debug_slow,
-        &all_fields,
         db,
-    )));
+    ));
 
     // Check for tp_select_builder attribute and generate builder if present
     let builder_code = if super::has_attribute(ast, "tp_select_builder") {
@@ -238,15 +550,27 @@ pub fn derive_select(
         None
     };
 
+    // Cursor type shared by tp_select_cursor functions and the builder's find_after
+    let (cursor_fields, cursor_serde) = get_cursor_fields(ast, &all_fields);
+    let cursor_code = if cursor_fields.is_empty() {
+        None
+    } else {
+        Some(build_cursor_struct(&ast.ident, &struct_name, &cursor_fields, cursor_serde))
+    };
+
     let expanded = match scope {
         super::Scope::Struct => quote! {
             impl #struct_name {
                 #(#functions)*
             }
+            #(#projection_checks)*
+            #cursor_code
             #builder_code
         },
         super::Scope::Mod => quote! {
             #(#functions)*
+            #(#projection_checks)*
+            #cursor_code
             #builder_code
         },
         super::Scope::NewMod => {
@@ -254,6 +578,8 @@ pub fn derive_select(
             quote! {
                 pub mod #new_mod {
                     #(#functions)*
+                    #(#projection_checks)*
+                    #cursor_code
                     #builder_code
                 }
             }
@@ -263,9 +589,90 @@ pub fn derive_select(
     Ok(expanded.into())
 }
 
+/// Generate `get_by_pk`, failing with `RowNotFound` instead of returning `None`, and `reload`,
+/// which reads the record again by the primary key of its fields. `find_by_pk` and `exists_by_pk`
+/// are generated from their `tp_select_*` attributes
+fn build_pk_functions(
+    struct_name: &TokenStream,
+    table_name: &str,
+    all_fields: &Vec<&Field>,
+    pk_fields: Vec<Field>,
+    row_filter: &RowFilter,
+    self_receiver: bool,
+    debug_slow: Option<i32>,
+    db: Database,
+) -> (TokenStream, TokenStream) {
+    let database = get_database_type(db);
+    let (dbg_before, dbg_after) = super::gen_debug_code(debug_slow);
+    let mut by_fields = pk_fields;
+    let mut by_conditions = HashMap::new();
+    row_filter.scope(&mut by_fields, &mut by_conditions);
+    let mut where_condition = by_fields
+        .iter()
+        .enumerate()
+        .map(|(index, field)| {
+            let column = get_field_name_as_column(field, db);
+            match db {
+                Database::Postgres => format!("{column} = ${}", index + 1),
+                Database::Sqlite | Database::Mysql | Database::Any => format!("{column} = ?"),
+            }
+        })
+        .collect::<Vec<_>>();
+    where_condition.extend(row_filter.condition.clone());
+    let all_fields_str = all_fields
+        .iter()
+        .map(|x| get_field_name_as_column(x, db))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let sql = format!(
+        "SELECT {all_fields_str} FROM {table_name} WHERE {}",
+        where_condition.join(" AND ")
+    );
+    super::check_valid_single_sql(&sql, db);
+
+    let fn_args = by_fields
+        .iter()
+        .map(|field| super::get_by_condition(&by_conditions, field).gen_arg(field));
+    let arg_names = by_fields.iter().map(|field| field.ident.as_ref().unwrap()).collect::<Vec<_>>();
+    let (receiver, record) = if self_receiver {
+        (quote! { &mut self }, quote! { self })
+    } else {
+        (quote! { re: &mut #struct_name }, quote! { re })
+    };
+    let get_fn = quote! {
+        /// Find the record by its primary key, failing with `sqlx::Error::RowNotFound` if it does not exist
+        pub async fn get_by_pk<'c, E: sqlx::Executor<'c, Database = #database> + 'c>(#(#fn_args,)* conn: E) -> Result<#struct_name, sqlx::Error> {
+            let sql = #sql;
+            #dbg_before
+            let query_result = sqlx::query_as::<_, #struct_name>(sql)
+                #(.bind(#arg_names))*
+                .fetch_one(conn)
+                .await;
+            #dbg_after
+            Ok(query_result?)
+        }
+    };
+    let reload_fn = quote! {
+        /// Read the record again by its primary key, failing with `sqlx::Error::RowNotFound` if it no longer exists
+        pub async fn reload<'c, E: sqlx::Executor<'c, Database = #database> + 'c>(#receiver, conn: E) -> Result<(), sqlx::Error> {
+            let sql = #sql;
+            #dbg_before
+            let query_result = sqlx::query_as::<_, #struct_name>(sql)
+                #(.bind(&#record.#arg_names))*
+                .fetch_one(conn)
+                .await;
+            #dbg_after
+            *#record = query_result?;
+            Ok(())
+        }
+    };
+    (get_fn, reload_fn)
+}
+
 fn build_default_find_all_query(
     struct_name: &proc_macro2::TokenStream,
     table_name: &str,
+    row_filter: &RowFilter,
     debug_slow: Option<i32>,
     all_fields: &Vec<&Field>,
     db: Database,
@@ -275,7 +682,7 @@ fn build_default_find_all_query(
         .map(|x| get_field_name_as_column(x, db))
         .collect::<Vec<String>>();
     let all_fields_str = all_fields_str.join(", ");
-    let sql = format!("SELECT {all_fields_str} FROM {table_name}");
+    let sql = format!("SELECT {all_fields_str} FROM {table_name}{}", row_filter.where_clause());
     super::check_valid_single_sql(&sql, db);
     let database = super::get_database_type(db);
     let (dbg_before, dbg_after) = super::gen_debug_code(debug_slow);
@@ -296,7 +703,9 @@ fn build_default_find_all_query(
 fn build_default_find_page_all_query(
     struct_name: &proc_macro2::TokenStream,
     table_name: &str,
+    row_filter: &RowFilter,
     debug_slow: Option<i32>,
+    max_page_size: Option<i32>,
     all_fields: &Vec<&Field>,
     db: Database,
 ) -> proc_macro2::TokenStream {
@@ -307,14 +716,21 @@ fn build_default_find_page_all_query(
         .map(|x| get_field_name_as_column(x, db))
         .collect::<Vec<String>>();
     let all_fields_str = all_fields_str.join(", ");
+    let where_clause = row_filter.where_clause();
     let sql = match db {
-        Database::Postgres => format!("SELECT {all_fields_str} FROM {table_name} LIMIT $1 OFFSET $2"),
-        Database::Sqlite | Database::Mysql | Database::Any => format!("SELECT {all_fields_str} FROM {table_name} LIMIT ? OFFSET ?"),
+        Database::Postgres => format!("SELECT {all_fields_str} FROM {table_name}{where_clause} LIMIT $1 OFFSET $2"),
+        Database::Sqlite | Database::Mysql | Database::Any => format!("SELECT {all_fields_str} FROM {table_name}{where_clause} LIMIT ? OFFSET ?"),
     };
     super::check_valid_single_sql(&sql, db);
-    let count_sql = format!("SELECT COUNT(1) FROM {table_name}");
+    let count_sql = format!("SELECT COUNT(1) FROM {table_name}{where_clause}");
+    let super::PageCode {
+        request: page_request,
+        response: page_response,
+        prelude: page_prelude,
+        result: page_result,
+    } = super::gen_page_code(struct_name, max_page_size, false);
     let expanded = quote! {
-        pub async fn find_page_all<'c, E: sqlx::Executor<'c, Database = #database> + Copy>(page: impl Into<(i64, i32, bool)>, conn: E) -> Result<(Vec<#struct_name>, Option<i64>), sqlx::Error> {
+        pub async fn find_page_all<'c, E: sqlx::Executor<'c, Database = #database> + Copy>(page: #page_request, conn: E) -> Result<#page_response, sqlx::Error> {
             async fn data_query<'c, E: sqlx::Executor<'c, Database = #database>>(offset: i64, limit: i32, conn: E) -> Result<Vec<#struct_name>, sqlx::Error> {
                 let sql = #sql;
                 #dbg_before
@@ -327,7 +743,7 @@ fn build_default_find_page_all_query(
                 Ok(query_result?)
             }
             pub async fn count_query<'c, E: sqlx::Executor<'c, Database = #database>>( conn: E) -> Result<i64, sqlx::Error> {
-                let sql = #sql;
+                let sql = #count_sql;
                 #dbg_before
                 let count = sqlx::query_scalar(sql)
                     .fetch_one(conn)
@@ -335,10 +751,7 @@ fn build_default_find_page_all_query(
                 #dbg_after
                 Ok(count?)
             }
-            let page = page.into();
-            let offset = page.0;
-            let limit = page.1;
-            let count = page.2;
+            #page_prelude
             let data = data_query(offset, limit, conn).await?;
             let count = if count {
                 if data.is_empty() && offset == 0 {
@@ -350,7 +763,7 @@ fn build_default_find_page_all_query(
             } else {
                 None
             };
-            Ok((data, count))
+            #page_result
         }
     };
     expanded.into()
@@ -359,10 +772,11 @@ fn build_default_find_page_all_query(
 fn build_default_count_all_query(
     struct_name: &proc_macro2::TokenStream,
     table_name: &str,
+    row_filter: &RowFilter,
     debug_slow: Option<i32>,
     db: Database,
 ) -> proc_macro2::TokenStream {
-    let sql = format!("SELECT COUNT(1) FROM {table_name}");
+    let sql = format!("SELECT COUNT(1) FROM {table_name}{}", row_filter.where_clause());
     let database = super::get_database_type(db);
     let (dbg_before, dbg_after) = super::gen_debug_code(debug_slow);
     let expanded = quote! {
@@ -379,49 +793,116 @@ fn build_default_count_all_query(
     expanded.into()
 }
 
+/// Select list of a projection query, replaced at runtime by the columns of the `into` struct
+const PROJECTION_COLUMNS: &str = "$$COLUMNS$$";
+
+/// Expression of the SQL of a query, whose select list is built once on first use for a projection
+fn gen_sql_expr(sql: &str, projection: Option<&TokenStream>) -> TokenStream {
+    match projection {
+        Some(select_list) => quote! {
+            {
+                static SQL: std::sync::OnceLock<String> = std::sync::OnceLock::new();
+                SQL.get_or_init(|| #sql.replace(#PROJECTION_COLUMNS, &#select_list)).as_str()
+            }
+        },
+        None => quote! { #sql },
+    }
+}
+
 fn build_query(
     qtype: SelectType,
     struct_name: &proc_macro2::TokenStream,
     table_name: &str,
     all_fields: &Vec<&Field>,
     by_fields: Vec<Field>,
+    by_conditions: &HashMap<String, ByCondition>,
     order_fields: Vec<(Field, bool)>,
     fn_name: Option<String>,
     where_stmt_str: Option<String>,
+    into: Option<syn::Path>,
+    lock_clause: &str,
+    row_filter: &RowFilter,
     debug_slow: Option<i32>,
+    max_page_size: Option<i32>,
+    page_type: bool,
     db: Database,
 ) -> syn::Result<Option<proc_macro2::TokenStream>> {
+    if matches!(qtype, SelectType::Count | SelectType::Exists) && !lock_clause.is_empty() {
+        panic!("lock is not supported by tp_select_count and tp_select_exists");
+    }
+    if matches!(qtype, SelectType::Exists) && !order_fields.is_empty() {
+        panic!("order is not supported by tp_select_exists");
+    }
+    if matches!(qtype, SelectType::Page) && !lock_clause.is_empty() {
+        // The data and count queries run on a Copy executor, a pool releasing the locks at once
+        panic!("lock is not supported by tp_select_page, lock the rows with tp_select_all in a transaction");
+    }
     let database = super::get_database_type(db);
     let (dbg_before, dbg_after) = super::gen_debug_code(debug_slow);
+    let super::PageCode {
+        request: page_request,
+        response: page_response,
+        prelude: page_prelude,
+        result: page_result,
+    } = super::gen_page_code(struct_name, max_page_size, page_type);
     let all_fields_str = all_fields
         .iter()
         .map(|x| get_field_name_as_column(x, db))
         .collect::<Vec<String>>();
     let all_fields_str_join = all_fields_str.join(", ");
+    // A projection selects the columns of the `into` struct, listed by its `Columns` derive
+    let (row_type, select_str, projection) = match &into {
+        Some(into) => {
+            if !matches!(qtype, SelectType::All | SelectType::One) {
+                panic!("into is only supported by tp_select_all and tp_select_one");
+            }
+            let projection = super::gen_projection_select_list(&quote! { <#into as sqlx_template_runtime::Columns>::COLUMNS }, all_fields, db);
+            (quote! { #into }, PROJECTION_COLUMNS.to_string(), Some(projection))
+        }
+        None => (struct_name.clone(), all_fields_str_join.clone(), None),
+    };
+    let lock_post_fix = format!("{}{}", row_filter.post_fix, lock_post_fix(lock_clause));
+    let into_post_fix = into.as_ref().map(|into| {
+        let ident = &into.segments.last().unwrap().ident;
+        format!("_as_{}", super::builder::to_snake_case(&ident.to_string()))
+    }).unwrap_or_default();
+    // The tenant of a scoped select is left out of its default name
+    let by_names = super::get_by_names(&by_fields, by_conditions);
     match (
-        by_fields.is_empty() && where_stmt_str.is_none(),
+        by_names.is_empty() && where_stmt_str.is_none(),
         order_fields.is_empty(),
     ) {
-        (true, true) => {
+        (true, true) if by_fields.is_empty() && into.is_none() && lock_post_fix.is_empty() && row_filter.prefix.is_empty() && !matches!(qtype, SelectType::Exists) => {
             // Do nothing. Default implemention
         }
-        (true, false) => {
-            let mut post_fix = format!(
-                "order_by_{}",
-                order_fields
-                    .iter()
-                    .map(|f| {
-                        let mut field_str = get_field_name(&f.0);
-                        if f.1 {
-                            field_str.push_str("_asc")
-                        } else {
-                            field_str.push_str("_desc")
-                        }
-                        field_str
-                    })
-                    .collect::<Vec<_>>()
-                    .join("_and_")
-            );
+        (true, _) if by_fields.is_empty() => {
+            let mut post_fix = if order_fields.is_empty() {
+                // Projection or locking of all rows, e.g. find_as_user_summary or find_for_update
+                format!("{into_post_fix}{lock_post_fix}").trim_start_matches('_').to_string()
+            } else {
+                format!(
+                    "order_by_{}{}{}",
+                    order_fields
+                        .iter()
+                        .map(|f| {
+                            let mut field_str = get_field_name(&f.0);
+                            if f.1 {
+                                field_str.push_str("_asc")
+                            } else {
+                                field_str.push_str("_desc")
+                            }
+                            field_str
+                        })
+                        .collect::<Vec<_>>()
+                        .join("_and_"),
+                    into_post_fix,
+                    lock_post_fix
+                )
+            };
+            if post_fix.is_empty() {
+                // Unscoped default function, e.g. unscoped_find_all
+                post_fix = "all".to_string();
+            }
             let fn_name = match fn_name {
                 Some(fn_name) => Ident::new(&fn_name, proc_macro2::Span::call_site()),
                 None => match qtype {
@@ -445,8 +926,13 @@ fn build_query(
                         &format!("count_{}", post_fix),
                         proc_macro2::Span::call_site(),
                     ),
+                    SelectType::Exists => Ident::new(
+                        &format!("exists_{}", post_fix),
+                        proc_macro2::Span::call_site(),
+                    ),
                 },
             };
+            let fn_name = row_filter.fn_ident(fn_name);
 
             let order_str = order_fields
                 .iter()
@@ -459,17 +945,23 @@ fn build_query(
                 })
                 .collect::<Vec<_>>()
                 .join(", ");
-            let sql =
-                format!("SELECT {all_fields_str_join} FROM {table_name} ORDER BY {order_str}");
-            super::check_valid_single_sql(&sql, db);
-            let count_sql = format!("SELECT COUNT(1) FROM {table_name} ORDER BY {order_str}");
+            let where_clause = row_filter.where_clause();
+            let sql = if order_fields.is_empty() {
+                format!("SELECT {select_str} FROM {table_name}{where_clause}")
+            } else {
+                format!("SELECT {select_str} FROM {table_name}{where_clause} ORDER BY {order_str}")
+            };
+            super::check_valid_single_sql(&sql.replace(PROJECTION_COLUMNS, &all_fields_str_join), db);
+            let locked_sql = format!("{sql}{lock_clause}");
+            let sql_expr = gen_sql_expr(&locked_sql, projection.as_ref());
+            let count_sql = format!("SELECT COUNT(1) FROM {table_name}{where_clause}");
             let generated = match qtype {
                 SelectType::All => {
                     quote! {
-                        pub async fn #fn_name<'c, E: sqlx::Executor<'c, Database = #database> + 'c>( conn: E) -> core::result::Result<Vec<#struct_name>, sqlx::Error> {
-                            let sql = #sql;
+                        pub async fn #fn_name<'c, E: sqlx::Executor<'c, Database = #database> + 'c>( conn: E) -> core::result::Result<Vec<#row_type>, sqlx::Error> {
+                            let sql = #sql_expr;
                             #dbg_before
-                            let query_result = sqlx::query_as::<_, #struct_name>(sql)
+                            let query_result = sqlx::query_as::<_, #row_type>(sql)
                                 .fetch_all(conn)
                                 .await;
                             #dbg_after
@@ -479,10 +971,10 @@ fn build_query(
                 }
                 SelectType::One => {
                     quote! {
-                        pub async fn #fn_name<'c, E: sqlx::Executor<'c, Database = #database> + 'c>( conn: E) -> core::result::Result<Option<#struct_name>, sqlx::Error> {
-                            let sql = #sql;
+                        pub async fn #fn_name<'c, E: sqlx::Executor<'c, Database = #database> + 'c>( conn: E) -> core::result::Result<Option<#row_type>, sqlx::Error> {
+                            let sql = #sql_expr;
                             #dbg_before
-                            let query_result = sqlx::query_as::<_, #struct_name>(sql)
+                            let query_result = sqlx::query_as::<_, #row_type>(sql)
                                 .fetch_optional(conn)
                                 .await;
                             #dbg_after
@@ -512,7 +1004,7 @@ fn build_query(
                         .bind(paging_offset)
                     });
                     quote! {
-                        pub async fn #fn_name<'c, E: sqlx::Executor<'c, Database = #database> + Copy + 'c>( page: impl Into<(i64, i32, bool)>, conn: E) -> core::result::Result<(Vec<#struct_name>, Option<i64>), sqlx::Error> {
+                        pub async fn #fn_name<'c, E: sqlx::Executor<'c, Database = #database> + Copy + 'c>( page: #page_request, conn: E) -> core::result::Result<#page_response, sqlx::Error> {
                             pub async fn data_query<'c, E: sqlx::Executor<'c, Database = #database> + 'c>( paging_offset: i64, paging_limit: i32, conn: E) -> core::result::Result<Vec<#struct_name>, sqlx::Error> {
                                 let sql = #paging_sql;
                                 #dbg_before
@@ -524,20 +1016,16 @@ fn build_query(
                                 Ok(query_result?)
                             }
                             pub async fn count_query<'c, E: sqlx::Executor<'c, Database = #database> + 'c>( conn: E) -> core::result::Result<i64, sqlx::Error> {
-                                let sql = #sql;
+                                let sql = #count_sql;
                                 #dbg_before
                                 let count = sqlx::query_scalar(sql)
-                                    #(#total_binds)*
                                     .fetch_one(conn)
                                     .await;
                                 #dbg_after
-                                Ok(query_result?)
+                                Ok(count?)
                             }
 
-                            let page = page.into();
-                            let offset = page.0;
-                            let limit = page.1;
-                            let count = page.2;
+                            #page_prelude
                             let data = data_query(offset, limit, conn).await?;
                             let count = if count {
                                 if data.is_empty() && offset == 0 {
@@ -548,8 +1036,7 @@ fn build_query(
                             } else {
                                 None
                             };
-                            Ok((data, count))
-
+                            #page_result
                         }
 
                     }
@@ -557,7 +1044,7 @@ fn build_query(
                 SelectType::Stream => {
                     quote! {
                         pub fn #fn_name<'c, E: sqlx::Executor<'c, Database = #database> + 'c>( conn: E) -> futures::stream::BoxStream<'c, core::result::Result<#struct_name, sqlx::Error>> {
-                            let sql = #sql;
+                            let sql = #locked_sql;
                             #dbg_before
                             let query_result = sqlx::query_as(sql)
                                 .fetch(conn)
@@ -567,22 +1054,58 @@ fn build_query(
                         }
                     }
                 }
+                // Counts the rows of all tenants or with the deleted ones, e.g. unscoped_count_all or count_with_deleted
+                SelectType::Count if order_fields.is_empty() => {
+                    quote! {
+                        pub async fn #fn_name<'c, E: sqlx::Executor<'c, Database = #database> + 'c>( conn: E) -> Result<i64, sqlx::Error> {
+                            let sql = #count_sql;
+                            #dbg_before
+                            let count = sqlx::query_scalar(sql)
+                                .fetch_one(conn)
+                                .await;
+                            #dbg_after
+                            Ok(count?)
+                        }
+                    }
+                }
+                // Whether the table has any row, e.g. exists_all
+                SelectType::Exists => {
+                    let (exists_sql, fetch_exists) = match db {
+                        Database::Postgres | Database::Sqlite => (
+                            format!("SELECT EXISTS(SELECT 1 FROM {table_name}{where_clause})"),
+                            quote! { sqlx::query_scalar::<_, bool>(sql).fetch_one(conn).await },
+                        ),
+                        // Stops at the first row, like EXISTS
+                        Database::Mysql | Database::Any => (
+                            format!("SELECT 1 FROM {table_name}{where_clause} LIMIT 1"),
+                            quote! { sqlx::query(sql).fetch_optional(conn).await.map(|row| row.is_some()) },
+                        ),
+                    };
+                    super::check_valid_single_sql(&exists_sql, db);
+                    quote! {
+                        pub async fn #fn_name<'c, E: sqlx::Executor<'c, Database = #database> + 'c>( conn: E) -> Result<bool, sqlx::Error> {
+                            let sql = #exists_sql;
+                            #dbg_before
+                            let exists = #fetch_exists;
+                            #dbg_after
+                            Ok(exists?)
+                        }
+                    }
+                }
                 SelectType::Count => {
                     return Ok(None); // Do nothing
                 }
             };
             return Ok(Some(generated));
         }
-        (false, _) => {
-            let mut post_fix = format!(
-                "by_{}",
-                by_fields
-                    .iter()
-                    .map(|f| f.ident.as_ref().unwrap().to_string())
-                    .collect::<Vec<_>>()
-                    .join("_and_")
-            );
-            if !order_fields.is_empty() && qtype != SelectType::Count {
+        _ => {
+            let mut post_fix = if by_names.is_empty() && where_stmt_str.is_none() {
+                // Only the tenant of a scoped select
+                String::new()
+            } else {
+                format!("by_{by_names}")
+            };
+            if !order_fields.is_empty() && !matches!(qtype, SelectType::Count | SelectType::Exists) {
                 post_fix.push_str(&format!(
                     "_order_by_{}",
                     order_fields
@@ -600,6 +1123,13 @@ fn build_query(
                         .join("_and_")
                 ))
             }
+            post_fix.push_str(&into_post_fix);
+            post_fix.push_str(&lock_post_fix);
+            let post_fix = post_fix.trim_start_matches('_');
+            if post_fix.is_empty() && fn_name.is_none() {
+                // Nothing to name, e.g. the count of all the rows of the tenant, which is count_all
+                return Ok(None);
+            }
             let fn_name = match fn_name {
                 Some(fn_name) => Ident::new(&fn_name, proc_macro2::Span::call_site()),
                 None => match qtype {
@@ -623,46 +1153,39 @@ fn build_query(
                         &format!("count_{}", post_fix),
                         proc_macro2::Span::call_site(),
                     ),
+                    SelectType::Exists => Ident::new(
+                        &format!("exists_{}", post_fix),
+                        proc_macro2::Span::call_site(),
+                    ),
                 },
             };
+            let fn_name = row_filter.fn_ident(fn_name);
 
             let mut fn_args = by_fields
                 .iter()
-                .map(|field| {
-                    let arg_name = field.ident.as_ref().unwrap();
-                    let arg_type = &field.ty;
-                    if &arg_type.to_token_stream().to_string() == "String" {
-                        quote! { #arg_name: &'c str }
-                    } else {
-                        quote! { #arg_name: &'c #arg_type }
-                    }
-                })
+                .map(|field| super::get_by_condition(by_conditions, field).gen_arg(field))
                 .collect::<Vec<_>>();
 
             let mut where_condition = by_fields
                 .iter()
                 .enumerate()
                 .map(|(index, field)| {
-                    match db {
-                        Database::Postgres => format!(
-                            "{} = ${}",
-                            get_field_name_as_column(field, db),
-                            index + 1
-                        ),
-                        Database::Sqlite | Database::Mysql | Database::Any => format!(
-                            "{} = ?",
-                            get_field_name_as_column(field, db)
-                        ),
-                    }
+                    let placeholder = match db {
+                        Database::Postgres => format!("${}", index + 1),
+                        Database::Sqlite | Database::Mysql | Database::Any => "?".to_string(),
+                    };
+                    super::get_by_condition(by_conditions, field).condition(
+                        &get_field_name_as_column(field, db),
+                        &placeholder,
+                        db,
+                    )
                 })
                 .collect::<Vec<_>>();
             let mut binds = by_fields
                 .iter()
                 .map(|field| {
                     let arg_name = field.ident.as_ref().unwrap();
-                    quote! {
-                        .bind(#arg_name)
-                    }
+                    super::get_by_condition(by_conditions, field).gen_bind(arg_name, db)
                 })
                 .collect::<Vec<_>>();
             if let Some(where_stmt_str) = where_stmt_str {
@@ -789,11 +1312,12 @@ fn build_query(
                         Some(start_counter as i32),
                         db,
                     );
-                    where_condition.push(sql);
+                    where_condition.push(format!("({sql})"));
                 } else {
-                    where_condition.push(where_stmt_str);
+                    where_condition.push(format!("({where_stmt_str})"));
                 }
             }
+            where_condition.extend(row_filter.condition.clone());
             let where_condition = where_condition.join(" AND ");
 
             let count_sql = format!(
@@ -802,7 +1326,7 @@ fn build_query(
             );
             let sql = if order_fields.is_empty() {
                 format!(
-                    "SELECT {all_fields_str_join} FROM {} WHERE {}",
+                    "SELECT {select_str} FROM {} WHERE {}",
                     &table_name, where_condition
                 )
             } else {
@@ -818,11 +1342,14 @@ fn build_query(
                     .collect::<Vec<_>>()
                     .join(", ");
                 format!(
-                    "SELECT * FROM {} WHERE {} ORDER BY {}",
+                    "SELECT {} FROM {} WHERE {} ORDER BY {}",
+                    if into.is_some() { &select_str } else { "*" },
                     &table_name, where_condition, order_str
                 )
             };
-            super::check_valid_single_sql(&sql, db);
+            super::check_valid_single_sql(&sql.replace(PROJECTION_COLUMNS, &all_fields_str_join), db);
+            let locked_sql = format!("{sql}{lock_clause}");
+            let sql_expr = gen_sql_expr(&locked_sql, projection.as_ref());
             let args_signature = if fn_args.is_empty() {
                 quote! {}
             } else {
@@ -831,10 +1358,10 @@ fn build_query(
             let generated = match qtype {
                 SelectType::All => {
                     quote! {
-                        pub async fn #fn_name<'c, E: sqlx::Executor<'c, Database = #database> + 'c>(#args_signature conn: E) -> Result<Vec<#struct_name>, sqlx::Error> {
-                            let sql = #sql;
+                        pub async fn #fn_name<'c, E: sqlx::Executor<'c, Database = #database> + 'c>(#args_signature conn: E) -> Result<Vec<#row_type>, sqlx::Error> {
+                            let sql = #sql_expr;
                             #dbg_before
-                            let query_result = sqlx::query_as::<_, #struct_name>(sql)
+                            let query_result = sqlx::query_as::<_, #row_type>(sql)
                                 #(#binds)*
                                 .fetch_all(conn)
                                 .await;
@@ -845,10 +1372,10 @@ fn build_query(
                 }
                 SelectType::One => {
                     quote! {
-                        pub async fn #fn_name<'c, E: sqlx::Executor<'c, Database = #database> + 'c>(#args_signature conn: E) -> Result<Option<#struct_name>, sqlx::Error> {
-                            let sql = #sql;
+                        pub async fn #fn_name<'c, E: sqlx::Executor<'c, Database = #database> + 'c>(#args_signature conn: E) -> Result<Option<#row_type>, sqlx::Error> {
+                            let sql = #sql_expr;
                             #dbg_before
-                            let query_result = sqlx::query_as::<_, #struct_name>(sql)
+                            let query_result = sqlx::query_as::<_, #row_type>(sql)
                                 #(#binds)*
                                 .fetch_optional(conn)
                                 .await;
@@ -890,7 +1417,7 @@ fn build_query(
                         .collect::<Vec<_>>();
                     let fn_args_name_clone = fn_args_name.clone();
                     quote! {
-                        pub async fn #fn_name<'c, E: sqlx::Executor<'c, Database = #database> + Copy + 'c>(#args_signature page: impl Into<(i64, i32, bool)>, conn: E) -> Result<(Vec<#struct_name>, Option<i64>), sqlx::Error> {
+                        pub async fn #fn_name<'c, E: sqlx::Executor<'c, Database = #database> + Copy + 'c>(#args_signature page: #page_request, conn: E) -> Result<#page_response, sqlx::Error> {
                             pub async fn data_query<'c, E: sqlx::Executor<'c, Database = #database> + 'c>(#args_signature paging_offset: i64, paging_limit: i32, conn: E) -> Result<Vec<#struct_name>, sqlx::Error> {
                                 let sql = #paging_sql;
                                 #dbg_before
@@ -911,10 +1438,7 @@ fn build_query(
                                 #dbg_after
                                 Ok(query_result?)
                             }
-                            let page = page.into();
-                            let offset = page.0;
-                            let limit = page.1;
-                            let count = page.2;
+                            #page_prelude
                             let data = data_query(#(#fn_args_name),*, offset, limit, conn).await?;
                             let count = if count {
                                 if data.is_empty() && offset == 0 {
@@ -925,14 +1449,14 @@ fn build_query(
                             } else {
                                 None
                             };
-                            Ok((data, count))
+                            #page_result
                         }
                     }
                 }
                 SelectType::Stream => {
                     quote! {
                         pub fn #fn_name<'c, E: sqlx::Executor<'c, Database = #database> + 'c>(#args_signature conn: E) -> futures::stream::BoxStream<'c, Result<#struct_name, sqlx::Error>> {
-                            let sql = #sql;
+                            let sql = #locked_sql;
                             #dbg_before
                             let query_result = sqlx::query_as(sql)
                                 #(#binds)*
@@ -956,6 +1480,29 @@ fn build_query(
                         }
                     }
                 }
+                SelectType::Exists => {
+                    let (exists_sql, fetch_exists) = match db {
+                        Database::Postgres | Database::Sqlite => (
+                            format!("SELECT EXISTS(SELECT 1 FROM {} WHERE {})", &table_name, where_condition),
+                            quote! { sqlx::query_scalar::<_, bool>(sql) #(#binds)* .fetch_one(conn).await },
+                        ),
+                        // Stops at the first row, like EXISTS
+                        Database::Mysql | Database::Any => (
+                            format!("SELECT 1 FROM {} WHERE {} LIMIT 1", &table_name, where_condition),
+                            quote! { sqlx::query(sql) #(#binds)* .fetch_optional(conn).await.map(|row| row.is_some()) },
+                        ),
+                    };
+                    super::check_valid_single_sql(&exists_sql, db);
+                    quote! {
+                        pub async fn #fn_name<'c, E: sqlx::Executor<'c, Database = #database> + 'c>(#args_signature conn: E) -> Result<bool, sqlx::Error> {
+                            let sql = #exists_sql;
+                            #dbg_before
+                            let exists = #fetch_exists;
+                            #dbg_after
+                            Ok(exists?)
+                        }
+                    }
+                }
             };
             return Ok(Some(generated));
         }
@@ -963,21 +1510,856 @@ fn build_query(
     Ok(None)
 }
 
-fn check_by_fields<'a>(fields_from_attr: &Vec<&'a str>, all_fields: Vec<&'a Field>) -> Vec<Field> {
-    let by_fields = all_fields
+/// Fields a keyset pagination cursor can hold, in struct order: the `order` fields of the
+/// `tp_select_cursor` attributes and the `cursor` fields of the `tp_select_builder` attributes,
+/// and whether one of them derives serde for the cursor with `serde = true`
+fn get_cursor_fields<'a>(ast: &DeriveInput, all_fields: &[&'a Field]) -> (Vec<&'a Field>, bool) {
+    let mut names = HashSet::new();
+    let mut serde = false;
+    for attr in &ast.attrs {
+        if let Ok(Meta::List(MetaList { path, nested, .. })) = attr.parse_meta() {
+            let key = if path.is_ident("tp_select_cursor") {
+                "order"
+            } else if path.is_ident("tp_select_builder") {
+                "cursor"
+            } else {
+                continue;
+            };
+            for meta in nested {
+                if let NestedMeta::Meta(Meta::NameValue(nv)) = meta {
+                    if nv.path.is_ident("serde") {
+                        if let Lit::Bool(lit) = &nv.lit {
+                            serde |= lit.value;
+                        } else {
+                            panic!("Expected bool value serde = true|false");
+                        }
+                    } else if nv.path.is_ident(key) {
+                        if let Lit::Str(lit) = &nv.lit {
+                            for name in lit.value().split(',') {
+                                let (name, _) = extract_field_and_asc(name.trim());
+                                let field = all_fields
+                                    .iter()
+                                    .find(|f| f.ident.as_ref().is_some_and(|x| x == name))
+                                    .unwrap_or_else(|| panic!("{name} is not a field in struct"));
+                                // `(a, b) < (NULL, ?)` never holds, a NULL value would end the pagination
+                                if !std::ptr::eq(super::unwrap_option_type(&field.ty), &field.ty) {
+                                    panic!("{name} is an Option field, which can not order a keyset pagination");
+                                }
+                                names.insert(name.to_string());
+                            }
+                        } else {
+                            panic!("Expected string value {key} = \"...\"");
+                        }
+                    }
+                }
+            }
+        }
+    }
+    let fields = all_fields
         .iter()
-        .filter_map(|f| {
-            if fields_from_attr
-                .iter()
-                .any(|f_attr| f.ident.clone().is_some_and(|x| x.to_string() == **f_attr))
-            {
-                Some((*f).clone())
+        .filter(|f| f.ident.as_ref().is_some_and(|x| names.contains(&x.to_string())))
+        .copied()
+        .collect();
+    (fields, serde)
+}
+
+/// Generate the opaque keyset pagination cursor of a struct. It holds the values of the
+/// ordered fields taken from the last row of a page, and encodes into a string using their
+/// `Display` and `FromStr` implementations, ISO 8601 for `NaiveDateTime`. With `serde`, it also derives serde through the
+/// re-export of the runtime crate
+fn build_cursor_struct(
+    ident: &Ident,
+    struct_name: &proc_macro2::TokenStream,
+    cursor_fields: &[&Field],
+    serde: bool,
+) -> proc_macro2::TokenStream {
+    let cursor_name = quote::format_ident!("{}Cursor", ident);
+    let serde_derive = if serde {
+        quote! {
+            #[derive(::sqlx_template_runtime::serde::Serialize, ::sqlx_template_runtime::serde::Deserialize)]
+            #[serde(crate = "::sqlx_template_runtime::serde")]
+        }
+    } else {
+        quote! {}
+    };
+    let serde_field_attr = if serde {
+        quote! { #[serde(default, skip_serializing_if = "Option::is_none")] }
+    } else {
+        quote! {}
+    };
+    let field_names = cursor_fields.iter().map(|field| field.ident.as_ref().unwrap().to_string());
+    let struct_fields = cursor_fields.iter().map(|field| {
+        let field_name = field.ident.as_ref().unwrap();
+        let field_type = &field.ty;
+        quote! {
+            #serde_field_attr
+            #field_name: Option<#field_type>,
+        }
+    });
+    let from_row_arms = cursor_fields.iter().map(|field| {
+        let field_name = field.ident.as_ref().unwrap();
+        let field_str = field_name.to_string();
+        quote! {
+            #field_str => cursor.#field_name = Some(row.#field_name.clone()),
+        }
+    });
+    // Each value is written as `{len}:{text}`, `-` when unset
+    let encode_fields = cursor_fields.iter().map(|field| {
+        let field_name = field.ident.as_ref().unwrap();
+        let is_naive_datetime = matches!(&field.ty, Type::Path(path) if path.path.segments.last().is_some_and(|x| x.ident == "NaiveDateTime"));
+        // `NaiveDateTime` displays with a space that its `FromStr` does not read back, write it as ISO 8601
+        let to_text = if is_naive_datetime {
+            quote! { value.format("%Y-%m-%dT%H:%M:%S%.f").to_string() }
+        } else {
+            quote! { value.to_string() }
+        };
+        quote! {
+            match &self.#field_name {
+                Some(value) => {
+                    let value = #to_text;
+                    text.push_str(&format!("{}:{}", value.len(), value));
+                }
+                None => text.push('-'),
+            }
+        }
+    });
+    let decode_fields = cursor_fields.iter().map(|field| {
+        let field_name = field.ident.as_ref().unwrap();
+        quote! {
+            if let Some(tail) = rest.strip_prefix('-') {
+                rest = tail;
+            } else {
+                let (len, tail) = rest.split_once(':').ok_or_else(invalid)?;
+                let len: usize = len.parse().map_err(|_| invalid())?;
+                let value = tail.get(..len).ok_or_else(invalid)?;
+                cursor.#field_name = Some(value.parse().map_err(|_| invalid())?);
+                rest = &tail[len..];
+            }
+        }
+    });
+    let doc = format!(" Opaque keyset pagination cursor for [`{ident}`], built from the last row of a page");
+    quote! {
+        #[doc = #doc]
+        #[derive(Clone, Default)]
+        #serde_derive
+        pub struct #cursor_name {
+            #(#struct_fields)*
+        }
+
+        impl #cursor_name {
+            /// Fields the cursor can hold
+            pub const FIELDS: &'static [&'static str] = &[#(#field_names),*];
+
+            /// Build a cursor holding the values of `fields` taken from `row`
+            pub fn from_row(row: &#struct_name, fields: &[&str]) -> Self {
+                let mut cursor = Self::default();
+                for field in fields {
+                    match *field {
+                        #(#from_row_arms)*
+                        _ => {}
+                    }
+                }
+                cursor
+            }
+
+            /// Encode the cursor into an opaque string to hand to clients, read back with `decode`
+            pub fn encode(&self) -> String {
+                let mut text = String::new();
+                #(#encode_fields)*
+                text.bytes().map(|byte| format!("{:02x}", byte)).collect()
+            }
+
+            /// Decode a cursor encoded by `encode`
+            pub fn decode(encoded: &str) -> Result<Self, sqlx::Error> {
+                fn invalid() -> sqlx::Error {
+                    sqlx::Error::InvalidArgument("Invalid cursor".into())
+                }
+                let bytes = (0..encoded.len())
+                    .step_by(2)
+                    .map(|i| encoded.get(i..i + 2).and_then(|byte| u8::from_str_radix(byte, 16).ok()))
+                    .collect::<Option<Vec<u8>>>()
+                    .ok_or_else(invalid)?;
+                let text = String::from_utf8(bytes).map_err(|_| invalid())?;
+                let mut rest = text.as_str();
+                let mut cursor = Self::default();
+                #(#decode_fields)*
+                if !rest.is_empty() {
+                    return Err(invalid());
+                }
+                Ok(cursor)
+            }
+        }
+    }
+}
+
+/// Generate the seek predicate `(a, b) < (?, ?)` continuing after a cursor. Mixed order
+/// directions are expanded to `(a < ?) OR (a = ? AND b > ?)`. Returns the predicate and
+/// the fields to bind, in placeholder order
+fn build_seek_predicate(order_fields: &[(Field, bool)], start_counter: usize, db: Database) -> (String, Vec<Field>) {
+    let mut counter = start_counter;
+    let mut next_placeholder = || {
+        let placeholder = match db {
+            Database::Postgres => format!("${counter}"),
+            Database::Sqlite | Database::Mysql | Database::Any => "?".to_string(),
+        };
+        counter += 1;
+        placeholder
+    };
+    let columns = order_fields
+        .iter()
+        .map(|(field, _)| get_field_name_as_column(field, db))
+        .collect::<Vec<_>>();
+    if order_fields.iter().all(|(_, asc)| *asc == order_fields[0].1) {
+        let op = if order_fields[0].1 { ">" } else { "<" };
+        let placeholders = columns.iter().map(|_| next_placeholder()).collect::<Vec<_>>();
+        let binds = order_fields.iter().map(|(field, _)| field.clone()).collect();
+        (format!("({}) {op} ({})", columns.join(", "), placeholders.join(", ")), binds)
+    } else {
+        let mut disjuncts = Vec::new();
+        let mut binds = Vec::new();
+        for i in 0..order_fields.len() {
+            let conjuncts = (0..=i)
+                .map(|j| {
+                    let op = if j < i { "=" } else if order_fields[j].1 { ">" } else { "<" };
+                    binds.push(order_fields[j].0.clone());
+                    format!("{} {op} {}", columns[j], next_placeholder())
+                })
+                .collect::<Vec<_>>();
+            disjuncts.push(format!("({})", conjuncts.join(" AND ")));
+        }
+        (format!("({})", disjuncts.join(" OR ")), binds)
+    }
+}
+
+/// Generate a keyset pagination function from a `tp_select_cursor` attribute
+fn build_cursor_query(
+    ident: &Ident,
+    struct_name: &proc_macro2::TokenStream,
+    table_name: &str,
+    all_fields: &Vec<&Field>,
+    nested: &syn::punctuated::Punctuated<NestedMeta, Token![,]>,
+    row_filter: &RowFilter,
+    debug_slow: Option<i32>,
+    db: Database,
+) -> proc_macro2::TokenStream {
+    let mut by_fields = Vec::new();
+    let mut by_conditions = HashMap::new();
+    let mut order_fields = Vec::new();
+    let mut fn_name = None;
+    let mut debug_slow = debug_slow;
+    for meta in nested {
+        if let NestedMeta::Meta(Meta::NameValue(nv)) = meta {
+            if nv.path.is_ident("by") {
+                if let Lit::Str(lit) = &nv.lit {
+                    (by_fields, by_conditions) = super::parse_by_fields(&lit.value(), all_fields.clone());
+                    if by_conditions.values().any(|x| x.op == ByOp::In) {
+                        panic!("by = \"<field> in\" is only supported by tp_select_all");
+                    }
+                } else {
+                    panic!("Expected string value by = \"...\"");
+                }
+            } else if nv.path.is_ident("order") {
+                if let Lit::Str(lit) = &nv.lit {
+                    let lit = lit.value();
+                    let fields_str = lit.split(',').map(|x| x.trim()).collect::<Vec<_>>();
+                    let fields_and_asc = fields_str.iter().map(|x| extract_field_and_asc(x)).collect::<Vec<_>>();
+                    if has_duplicate_fields(&fields_and_asc) {
+                        panic!("Found duplicated fields: {:?}", fields_str);
+                    }
+                    // Keep the declared order, it defines the seek predicate
+                    order_fields = fields_and_asc
+                        .iter()
+                        .map(|(name, asc)| {
+                            let field = all_fields
+                                .iter()
+                                .find(|f| f.ident.as_ref().is_some_and(|x| x == name))
+                                .unwrap_or_else(|| panic!("{name} is not a field in struct"));
+                            ((*field).clone(), *asc)
+                        })
+                        .collect::<Vec<_>>();
+                } else {
+                    panic!("Expected string value order = \"...\"");
+                }
+            } else if nv.path.is_ident("fn_name") {
+                if let Lit::Str(lit) = &nv.lit {
+                    fn_name.replace(lit.value());
+                } else {
+                    panic!("Expected string value fn_name = \"...\"");
+                }
+            } else if nv.path.is_ident("debug") {
+                if let Lit::Int(lit) = &nv.lit {
+                    let slow_in_ms = lit
+                        .base10_parse()
+                        .expect("Invalid debug value. Must be integer");
+                    debug_slow.replace(slow_in_ms);
+                }
+            }
+        }
+    }
+    if order_fields.is_empty() {
+        panic!("tp_select_cursor requires a non-empty order = \"...\"");
+    }
+
+    let order_post_fix = format!(
+        "order_by_{}",
+        order_fields
+            .iter()
+            .map(|(field, asc)| format!("{}_{}", get_field_name(field), if *asc { "asc" } else { "desc" }))
+            .collect::<Vec<_>>()
+            .join("_and_")
+    );
+    let fn_name = match fn_name {
+        Some(fn_name) => fn_name,
+        None if by_fields.is_empty() => format!("find_after_{order_post_fix}"),
+        None => format!(
+            "find_after_by_{}_{order_post_fix}",
+            super::get_by_names(&by_fields, &by_conditions)
+        ),
+    };
+    let fn_name = row_filter.fn_ident(Ident::new(&fn_name, proc_macro2::Span::call_site()));
+    row_filter.scope(&mut by_fields, &mut by_conditions);
+    let cursor_name = quote::format_ident!("{}Cursor", ident);
+
+    let all_fields_str = all_fields
+        .iter()
+        .map(|x| get_field_name_as_column(x, db))
+        .collect::<Vec<String>>()
+        .join(", ");
+    let mut where_condition = build_by_conditions(&by_fields, &by_conditions, 1, db);
+    where_condition.extend(row_filter.condition.clone());
+    let order_str = order_fields
+        .iter()
+        .map(|(field, asc)| format!("{} {}", get_field_name_as_column(field, db), if *asc { "ASC" } else { "DESC" }))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let limit_placeholder = |index: usize| match db {
+        Database::Postgres => format!("${index}"),
+        Database::Sqlite | Database::Mysql | Database::Any => "?".to_string(),
+    };
+    let where_str = |conditions: &Vec<String>| {
+        if conditions.is_empty() {
+            String::new()
+        } else {
+            format!(" WHERE {}", conditions.join(" AND "))
+        }
+    };
+
+    let sql_first = format!(
+        "SELECT {all_fields_str} FROM {table_name}{} ORDER BY {order_str} LIMIT {}",
+        where_str(&where_condition),
+        limit_placeholder(by_fields.len() + 1)
+    );
+    super::check_valid_single_sql(&sql_first, db);
+    let (seek_predicate, seek_fields) = build_seek_predicate(&order_fields, by_fields.len() + 1, db);
+    where_condition.push(seek_predicate);
+    let sql_after = format!(
+        "SELECT {all_fields_str} FROM {table_name}{} ORDER BY {order_str} LIMIT {}",
+        where_str(&where_condition),
+        limit_placeholder(by_fields.len() + seek_fields.len() + 1)
+    );
+    super::check_valid_single_sql(&sql_after, db);
+
+    let fn_args = by_fields
+        .iter()
+        .map(|field| super::get_by_condition(&by_conditions, field).gen_arg(field))
+        .collect::<Vec<_>>();
+    let args_signature = if fn_args.is_empty() {
+        quote! {}
+    } else {
+        quote! {#(#fn_args),* ,}
+    };
+    let binds = by_fields
+        .iter()
+        .map(|field| {
+            let arg_name = field.ident.as_ref().unwrap();
+            super::get_by_condition(&by_conditions, field).gen_bind(arg_name, db)
+        })
+        .collect::<Vec<_>>();
+    let seek_binds = seek_fields.iter().map(|field| {
+        let field_name = field.ident.as_ref().unwrap();
+        let error = format!("Cursor has no value for {field_name}");
+        quote! {
+            .bind(cursor.#field_name.as_ref().ok_or_else(|| sqlx::Error::InvalidArgument(#error.into()))?)
+        }
+    });
+    let cursor_fields = order_fields.iter().map(|(field, _)| get_field_name(field));
+    let optional_doc = super::gen_optional_by_doc(&by_fields, &by_conditions);
+
+    let database = super::get_database_type(db);
+    let (dbg_before, dbg_after) = super::gen_debug_code(debug_slow);
+    let generated = super::gen_with_doc(quote! {
+        pub async fn #fn_name<'c, E: sqlx::Executor<'c, Database = #database> + 'c>(#args_signature cursor: Option<&#cursor_name>, limit: i32, conn: E) -> Result<(Vec<#struct_name>, Option<#cursor_name>), sqlx::Error> {
+            // One extra row tells whether there is a next page
+            let paging_limit = limit.max(0) as i64 + 1;
+            let query_result = match cursor {
+                None => {
+                    let sql = #sql_first;
+                    #dbg_before
+                    let query_result = sqlx::query_as::<_, #struct_name>(sql)
+                        #(#binds)*
+                        .bind(paging_limit)
+                        .fetch_all(conn)
+                        .await;
+                    #dbg_after
+                    query_result
+                }
+                Some(cursor) => {
+                    let sql = #sql_after;
+                    #dbg_before
+                    let query_result = sqlx::query_as::<_, #struct_name>(sql)
+                        #(#binds)*
+                        #(#seek_binds)*
+                        .bind(paging_limit)
+                        .fetch_all(conn)
+                        .await;
+                    #dbg_after
+                    query_result
+                }
+            };
+            let mut rows = query_result?;
+            let next_cursor = if rows.len() as i64 == paging_limit {
+                rows.pop();
+                rows.last().map(|last| #cursor_name::from_row(last, &[#(#cursor_fields),*]))
             } else {
                 None
+            };
+            Ok((rows, next_cursor))
+        }
+    });
+    quote! { #optional_doc #generated }
+}
+
+/// Select the rows whose `in` fields are in the given slices and whose other `by` fields are equal
+/// to the arguments. PostgreSQL binds each slice as an array with `= ANY($n)`, the other databases
+/// expand `IN (?, ?, ...)` at runtime. Empty slices return no rows without querying the database
+fn build_in_query(
+    struct_name: &proc_macro2::TokenStream,
+    table_name: &str,
+    all_fields: &Vec<&Field>,
+    by_fields: Vec<Field>,
+    by_conditions: &HashMap<String, ByCondition>,
+    order_fields: Vec<(Field, bool)>,
+    fn_name: Option<String>,
+    keep_order: bool,
+    lock_clause: &str,
+    row_filter: &RowFilter,
+    debug_slow: Option<i32>,
+    db: Database,
+) -> proc_macro2::TokenStream {
+    let is_in = |field: &Field| super::get_by_condition(by_conditions, field).op == ByOp::In;
+    let mut post_fix = format!("by_{}", super::get_by_names(&by_fields, by_conditions));
+    if !order_fields.is_empty() {
+        post_fix.push_str(&format!(
+            "_order_by_{}",
+            order_fields
+                .iter()
+                .map(|(field, asc)| format!("{}_{}", get_field_name(field), if *asc { "asc" } else { "desc" }))
+                .collect::<Vec<_>>()
+                .join("_and_")
+        ));
+    }
+    post_fix.push_str(row_filter.post_fix);
+    post_fix.push_str(&lock_post_fix(lock_clause));
+    let fn_name = row_filter.fn_ident(Ident::new(
+        &fn_name.unwrap_or_else(|| format!("find_{post_fix}")),
+        proc_macro2::Span::call_site(),
+    ));
+
+    let all_fields_str = all_fields
+        .iter()
+        .map(|x| get_field_name_as_column(x, db))
+        .collect::<Vec<String>>()
+        .join(", ");
+    // Marker of the expanded placeholders of the n-th `in` field
+    let in_marker = |index: usize| format!("$$IN{index}$$");
+    let where_condition = by_fields
+        .iter()
+        .enumerate()
+        .map(|(index, field)| {
+            let column = get_field_name_as_column(field, db);
+            match (db, is_in(field)) {
+                (Database::Postgres, false) => {
+                    super::get_by_condition(by_conditions, field).condition(&column, &format!("${}", index + 1), db)
+                }
+                (Database::Postgres, true) => format!("{column} = ANY(${})", index + 1),
+                (Database::Sqlite | Database::Mysql | Database::Any, false) => {
+                    super::get_by_condition(by_conditions, field).condition(&column, "?", db)
+                }
+                (Database::Sqlite | Database::Mysql | Database::Any, true) => {
+                    format!("{column} IN ({})", in_marker(index))
+                }
+            }
+        })
+        .chain(row_filter.condition.clone())
+        .collect::<Vec<_>>()
+        .join(" AND ");
+    let mut sql = format!("SELECT {all_fields_str} FROM {table_name} WHERE {where_condition}");
+    if !order_fields.is_empty() {
+        let order_str = order_fields
+            .iter()
+            .map(|(field, asc)| format!("{} {}", get_field_name_as_column(field, db), if *asc { "ASC" } else { "DESC" }))
+            .collect::<Vec<_>>()
+            .join(", ");
+        sql.push_str(&format!(" ORDER BY {order_str}"));
+    }
+    let mut checked_sql = sql.clone();
+    for index in 0..by_fields.len() {
+        checked_sql = checked_sql.replace(&in_marker(index), "?");
+    }
+    super::check_valid_single_sql(&checked_sql, db);
+    sql.push_str(lock_clause);
+
+    let fn_args = by_fields
+        .iter()
+        .map(|field| super::get_by_condition(by_conditions, field).gen_arg(field))
+        .collect::<Vec<_>>();
+    let in_args = by_fields
+        .iter()
+        .filter(|field| is_in(field))
+        .map(|field| field.ident.as_ref().unwrap())
+        .collect::<Vec<_>>();
+    let expand_placeholders = by_fields
+        .iter()
+        .enumerate()
+        .filter(|(_, field)| !matches!(db, Database::Postgres) && is_in(field))
+        .map(|(index, field)| {
+            let arg_name = field.ident.as_ref().unwrap();
+            let marker = in_marker(index);
+            quote! { .replace(#marker, &vec!["?"; #arg_name.len()].join(", ")) }
+        })
+        .collect::<Vec<_>>();
+    let binds = by_fields
+        .iter()
+        .map(|field| {
+            let arg_name = field.ident.as_ref().unwrap();
+            if !matches!(db, Database::Postgres) && is_in(field) {
+                quote! { let query = #arg_name.iter().fold(query, |query, value| query.bind(value)); }
+            } else {
+                let bind = super::get_by_condition(by_conditions, field).gen_bind(arg_name, db);
+                quote! { let query = query #bind; }
             }
         })
         .collect::<Vec<_>>();
+    let reorder = if keep_order {
+        let field_name = in_args.first().unwrap();
+        Some(quote! {
+            // Same order as the input values
+            rows.sort_by_key(|row| #field_name.iter().position(|value| *value == row.#field_name));
+        })
+    } else {
+        None
+    };
+
+    let database = super::get_database_type(db);
+    let (dbg_before, dbg_after) = super::gen_debug_code(debug_slow);
+    quote! {
+        pub async fn #fn_name<'c, E: sqlx::Executor<'c, Database = #database> + 'c>(#(#fn_args,)* conn: E) -> Result<Vec<#struct_name>, sqlx::Error> {
+            if #(#in_args.is_empty())||* {
+                return Ok(Vec::new());
+            }
+            let sql = #sql.to_string()#(#expand_placeholders)*;
+            #dbg_before
+            let query = sqlx::query_as::<_, #struct_name>(&sql);
+            #(#binds)*
+            let query_result = query.fetch_all(conn).await;
+            #dbg_after
+            let mut rows = query_result?;
+            #reorder
+            Ok(rows)
+        }
+    }
+}
+
+/// Count the rows matching the `by` fields for each value of the `group_by` fields, e.g.
+/// `count_by_org_group_by_status` returning `Vec<(String, i64)>` ordered by the grouped values
+fn build_count_group_query(
+    table_name: &str,
+    by_fields: Vec<Field>,
+    by_conditions: &HashMap<String, ByCondition>,
+    group_fields: Vec<Field>,
+    row_filter: &RowFilter,
+    fn_name: Option<String>,
+    debug_slow: Option<i32>,
+    db: Database,
+) -> proc_macro2::TokenStream {
+    let group_names = group_fields
+        .iter()
+        .map(|f| get_field_name(f))
+        .collect::<Vec<_>>()
+        .join("_and_");
+    let by_names = super::get_by_names(&by_fields, by_conditions);
+    let mut post_fix = if by_names.is_empty() {
+        format!("group_by_{group_names}")
+    } else {
+        format!("by_{by_names}_group_by_{group_names}")
+    };
+    post_fix.push_str(row_filter.post_fix);
+    let fn_name = row_filter.fn_ident(Ident::new(
+        &fn_name.unwrap_or_else(|| format!("count_{post_fix}")),
+        proc_macro2::Span::call_site(),
+    ));
+
+    let group_columns = group_fields
+        .iter()
+        .map(|x| get_field_name_as_column(x, db))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let mut sql = format!("SELECT {group_columns}, COUNT(1) FROM {table_name}");
+    let where_condition = build_by_conditions(&by_fields, by_conditions, 1, db)
+        .into_iter()
+        .chain(row_filter.condition.clone())
+        .collect::<Vec<_>>();
+    if !where_condition.is_empty() {
+        sql.push_str(&format!(" WHERE {}", where_condition.join(" AND ")));
+    }
+    sql.push_str(&format!(" GROUP BY {group_columns} ORDER BY {group_columns}"));
+    super::check_valid_single_sql(&sql, db);
+
+    let fn_args = by_fields
+        .iter()
+        .map(|field| super::get_by_condition(by_conditions, field).gen_arg(field))
+        .collect::<Vec<_>>();
+    let binds = by_fields.iter().map(|field| {
+        let arg_name = field.ident.as_ref().unwrap();
+        super::get_by_condition(by_conditions, field).gen_bind(arg_name, db)
+    });
+    let group_types = group_fields.iter().map(|field| &field.ty);
+
+    let database = super::get_database_type(db);
+    let (dbg_before, dbg_after) = super::gen_debug_code(debug_slow);
+    quote! {
+        pub async fn #fn_name<'c, E: sqlx::Executor<'c, Database = #database> + 'c>(#(#fn_args,)* conn: E) -> Result<Vec<(#(#group_types,)* i64)>, sqlx::Error> {
+            let sql = #sql;
+            #dbg_before
+            let query_result = sqlx::query_as(sql)
+                #(#binds)*
+                .fetch_all(conn)
+                .await;
+            #dbg_after
+            Ok(query_result?)
+        }
+    }
+}
+
+/// Aggregate `func` of a column over the rows matching the `by` fields, e.g. `sum_amount_by_org`.
+/// Returns `None` when no row matches
+fn build_agg_query(
+    table_name: &str,
+    all_fields: &Vec<&Field>,
+    nested: &syn::punctuated::Punctuated<NestedMeta, Token![,]>,
+    row_filter: &RowFilter,
+    debug_slow: Option<i32>,
+    db: Database,
+) -> proc_macro2::TokenStream {
+    let mut func = None;
+    let mut column = None;
+    let mut by_fields = Vec::new();
+    let mut by_conditions = HashMap::new();
+    let mut fn_name = None;
+    let mut debug_slow = debug_slow;
+    for meta in nested {
+        if let NestedMeta::Meta(Meta::NameValue(nv)) = meta {
+            if nv.path.is_ident("func") {
+                if let Lit::Str(lit) = &nv.lit {
+                    func.replace(lit.value().to_lowercase());
+                } else {
+                    panic!("Expected string value func = \"sum|avg|min|max\"");
+                }
+            } else if nv.path.is_ident("column") {
+                if let Lit::Str(lit) = &nv.lit {
+                    let name = lit.value();
+                    let field = all_fields
+                        .iter()
+                        .find(|f| f.ident.as_ref().is_some_and(|x| *x == name))
+                        .unwrap_or_else(|| panic!("{name} is not a field in struct"));
+                    column.replace((*field).clone());
+                } else {
+                    panic!("Expected string value column = \"...\"");
+                }
+            } else if nv.path.is_ident("by") {
+                if let Lit::Str(lit) = &nv.lit {
+                    (by_fields, by_conditions) = super::parse_by_fields(&lit.value(), all_fields.clone());
+                    if by_conditions.values().any(|x| x.op == ByOp::In) {
+                        panic!("by = \"<field> in\" is only supported by tp_select_all");
+                    }
+                } else {
+                    panic!("Expected string value by = \"...\"");
+                }
+            } else if nv.path.is_ident("fn_name") {
+                if let Lit::Str(lit) = &nv.lit {
+                    fn_name.replace(lit.value());
+                } else {
+                    panic!("Expected string value fn_name = \"...\"");
+                }
+            } else if nv.path.is_ident("debug") {
+                if let Lit::Int(lit) = &nv.lit {
+                    let slow_in_ms = lit
+                        .base10_parse()
+                        .expect("Invalid debug value. Must be integer");
+                    debug_slow.replace(slow_in_ms);
+                }
+            }
+        }
+    }
+    let func = func.expect("tp_select_agg requires func = \"sum|avg|min|max\"");
+    let column = column.expect("tp_select_agg requires column = \"...\"");
+
+    let (agg_expr, agg_type) = super::gen_aggregate(&func, &get_field_name_as_column(&column, db), &column.ty, db);
+    let mut post_fix = get_field_name(&column);
+    if !by_fields.is_empty() {
+        post_fix.push_str(&format!("_by_{}", super::get_by_names(&by_fields, &by_conditions)));
+    }
+    let fn_name = row_filter.fn_ident(Ident::new(
+        &fn_name.unwrap_or_else(|| format!("{func}_{post_fix}")),
+        proc_macro2::Span::call_site(),
+    ));
+    row_filter.scope(&mut by_fields, &mut by_conditions);
+
+    let mut sql = format!("SELECT {agg_expr} FROM {table_name}");
+    let where_condition = build_by_conditions(&by_fields, &by_conditions, 1, db)
+        .into_iter()
+        .chain(row_filter.condition.clone())
+        .collect::<Vec<_>>();
+    if !where_condition.is_empty() {
+        sql.push_str(&format!(" WHERE {}", where_condition.join(" AND ")));
+    }
+    super::check_valid_single_sql(&sql, db);
+
+    let fn_args = by_fields
+        .iter()
+        .map(|field| super::get_by_condition(&by_conditions, field).gen_arg(field))
+        .collect::<Vec<_>>();
+    let binds = by_fields.iter().map(|field| {
+        let arg_name = field.ident.as_ref().unwrap();
+        super::get_by_condition(&by_conditions, field).gen_bind(arg_name, db)
+    });
+    let optional_doc = super::gen_optional_by_doc(&by_fields, &by_conditions);
+
+    let database = super::get_database_type(db);
+    let (dbg_before, dbg_after) = super::gen_debug_code(debug_slow);
+    let generated = super::gen_with_doc(quote! {
+        pub async fn #fn_name<'c, E: sqlx::Executor<'c, Database = #database> + 'c>(#(#fn_args,)* conn: E) -> Result<Option<#agg_type>, sqlx::Error> {
+            let sql = #sql;
+            #dbg_before
+            let query_result = sqlx::query_scalar::<_, Option<#agg_type>>(sql)
+                #(#binds)*
+                .fetch_one(conn)
+                .await;
+            #dbg_after
+            Ok(query_result?)
+        }
+    });
+    quote! { #optional_doc #generated }
+}
+
+/// Rows of a select filtered by the struct-level `tp_soft_delete` and `tp_tenant`: the condition on the
+/// soft delete column, the tenant of a scoped select, and the affixes of the default name
+pub(super) struct RowFilter {
+    pub(super) condition: Option<String>,
+    pub(super) tenant: Option<super::Tenant>,
+    pub(super) prefix: &'static str,
+    post_fix: &'static str,
+}
+
+impl RowFilter {
+    /// Skips the deleted rows, unless `with_deleted = true` or `only_deleted = true`, and the rows
+    /// of other tenants, unless `unscoped = true`
+    pub(super) fn new(
+        soft_delete: Option<&super::SoftDelete>,
+        tenant: Option<&super::Tenant>,
+        with_deleted: bool,
+        only_deleted: bool,
+        unscoped: bool,
+    ) -> Self {
+        if with_deleted && only_deleted {
+            panic!("with_deleted and only_deleted can not be combined");
+        }
+        let (condition, post_fix) = match soft_delete {
+            None if with_deleted || only_deleted => {
+                panic!("with_deleted and only_deleted require #[tp_soft_delete(column = \"...\")]")
+            }
+            None => (None, ""),
+            Some(_) if with_deleted => (None, "_with_deleted"),
+            Some(soft_delete) if only_deleted => (Some(soft_delete.deleted_condition()), "_only_deleted"),
+            Some(soft_delete) => (Some(soft_delete.alive_condition()), ""),
+        };
+        let (tenant, prefix) = match unscoped {
+            true => (None, "unscoped_"),
+            false => (tenant.cloned(), ""),
+        };
+        RowFilter { condition, tenant, prefix, post_fix }
+    }
+
+    /// Adds the tenant to the `by` fields of a scoped select
+    fn scope(&self, by_fields: &mut Vec<Field>, by_conditions: &mut HashMap<String, ByCondition>) {
+        if let Some(tenant) = &self.tenant {
+            tenant.scope(by_fields, by_conditions);
+        }
+    }
+
+    /// Name of the generated function, prefixed with `unscoped_` outside of the tenant scope
+    pub(super) fn fn_ident(&self, fn_name: Ident) -> Ident {
+        quote::format_ident!("{}{}", self.prefix, fn_name)
+    }
+
+    /// ` WHERE` clause of a select without other conditions
+    fn where_clause(&self) -> String {
+        self.condition
+            .as_ref()
+            .map(|condition| format!(" WHERE {condition}"))
+            .unwrap_or_default()
+    }
+}
+
+/// Row locking clause appended to a select, e.g. ` FOR UPDATE SKIP LOCKED`. Empty without `lock`
+fn build_lock_clause(lock: Option<&str>, skip_locked: bool, nowait: bool, db: Database) -> String {
+    let Some(lock) = lock else {
+        if skip_locked || nowait {
+            panic!("skip_locked and nowait require lock = \"...\"");
+        }
+        return String::new();
+    };
+    let mut clause = match (lock, db) {
+        (_, Database::Sqlite | Database::Any) => panic!("Row locking is not supported on {db:?}"),
+        ("update", _) => " FOR UPDATE".to_string(),
+        ("share", _) => " FOR SHARE".to_string(),
+        ("no_key_update", Database::Postgres) => " FOR NO KEY UPDATE".to_string(),
+        ("no_key_update", _) => panic!("lock = \"no_key_update\" is only supported on PostgreSQL"),
+        _ => panic!("Expected lock = \"update|share|no_key_update\""),
+    };
+    match (skip_locked, nowait) {
+        (true, true) => panic!("skip_locked and nowait can not be used together"),
+        (true, false) => clause.push_str(" SKIP LOCKED"),
+        (false, true) => clause.push_str(" NOWAIT"),
+        (false, false) => {}
+    }
+    clause
+}
+
+/// Suffix of the default function names of locking selects, e.g. `_for_update_skip_locked`
+fn lock_post_fix(lock_clause: &str) -> String {
+    lock_clause.to_lowercase().replace(' ', "_")
+}
+
+/// Conditions of the `by` fields, the placeholders of PostgreSQL numbered from `start_counter`.
+/// `in` fields are not supported
+fn build_by_conditions(
+    by_fields: &[Field],
+    by_conditions: &HashMap<String, ByCondition>,
+    start_counter: usize,
+    db: Database,
+) -> Vec<String> {
     by_fields
+        .iter()
+        .enumerate()
+        .map(|(index, field)| {
+            let placeholder = match db {
+                Database::Postgres => format!("${}", index + start_counter),
+                Database::Sqlite | Database::Mysql | Database::Any => "?".to_string(),
+            };
+            super::get_by_condition(by_conditions, field).condition(&get_field_name_as_column(field, db), &placeholder, db)
+        })
+        .collect()
 }
 
 fn extract_field_and_asc(str: &str) -> (&str, bool) {
@@ -1052,3 +2434,82 @@ fn check_order_fields<'a>(
         .collect::<Vec<_>>();
     by_fields
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use syn::parse_quote;
+
+    /// Generated code of the select attributes of a PostgreSQL struct, whose SQL is a string literal in it
+    fn derive_pg(ast: DeriveInput) -> String {
+        derive_select(&ast, None, Scope::Struct, Some(Database::Postgres)).unwrap().to_string()
+    }
+
+    #[test]
+    fn test_in_list_sql() {
+        let code = derive_pg(parse_quote! {
+            #[table("tasks")]
+            #[tp_select_all(by = "id in")]
+            #[tp_select_all(by = "org, status in", order = "id desc")]
+            pub struct Task {
+                pub id: i32,
+                pub org: String,
+                pub status: String,
+            }
+        });
+        // A single array parameter, whatever the number of values
+        assert!(code.contains("\"SELECT id, org, status FROM tasks WHERE id = ANY($1)\""), "{code}");
+        assert!(code.contains("\"SELECT id, org, status FROM tasks WHERE org = $1 AND status = ANY($2) ORDER BY id DESC\""), "{code}");
+    }
+
+    #[test]
+    fn test_lock_sql() {
+        let code = derive_pg(parse_quote! {
+            #[table("jobs")]
+            #[tp_select_all(by = "status", order = "id asc", lock = "update", skip_locked = true)]
+            #[tp_select_one(by = "id", lock = "no_key_update", nowait = true)]
+            pub struct Job {
+                pub id: i32,
+                pub status: String,
+            }
+        });
+        assert!(code.contains("\"SELECT * FROM jobs WHERE status = $1 ORDER BY id FOR UPDATE SKIP LOCKED\""), "{code}");
+        assert!(code.contains("\"SELECT id, status FROM jobs WHERE id = $1 FOR NO KEY UPDATE NOWAIT\""), "{code}");
+    }
+
+    #[test]
+    fn test_ilike_sql() {
+        let code = derive_pg(parse_quote! {
+            #[table("events")]
+            #[tp_select_all(by = "title ilike")]
+            #[tp_select_count(by = "org, title ilike?")]
+            pub struct Event {
+                pub id: i32,
+                pub org: String,
+                pub title: String,
+            }
+        });
+        assert!(code.contains("\"SELECT id, org, title FROM events WHERE title ILIKE $1\""), "{code}");
+        // A `None` optional value drops the condition
+        assert!(code.contains("\"SELECT COUNT(1) FROM events WHERE org = $1 AND ($2 IS NULL OR title ILIKE $2)\""), "{code}");
+    }
+
+    #[test]
+    fn test_cursor_sql() {
+        let code = derive_pg(parse_quote! {
+            #[table("posts")]
+            #[tp_select_cursor(by = "org", order = "created_at desc, id desc")]
+            pub struct Post {
+                pub id: i32,
+                pub org: String,
+                pub created_at: i64,
+            }
+        });
+        // The first page, then the rows after the cursor with a row value comparison for a single direction
+        assert!(code.contains("\"SELECT id, org, created_at FROM posts WHERE org = $1 ORDER BY created_at DESC, id DESC LIMIT $2\""), "{code}");
+        assert!(
+            code.contains("\"SELECT id, org, created_at FROM posts WHERE org = $1 AND (created_at, id) < ($2, $3) ORDER BY created_at DESC, id DESC LIMIT $4\""),
+            "{code}"
+        );
+    }
+}
@@ -28,6 +28,8 @@ pub fn derive_update(
     let table_name = get_table_name(&ast);
     let db = db.or_else(|| Some(get_database_from_ast(&ast))).expect("Missing db config");
     let debug_slow = super::get_debug_slow_from_table_scope(&ast);
+    let soft_delete = super::get_soft_delete_from_table_scope(&ast, db);
+    let tenant = super::get_tenant_from_table_scope(&ast);
 
     let all_fields = if let syn::Data::Struct(syn::DataStruct {
         fields: syn::Fields::Named(syn::FieldsNamed { ref named, .. }),
@@ -44,15 +46,16 @@ pub fn derive_update(
         .map(|x| get_field_name_as_column(x, db))
         .collect::<Vec<_>>();
     let mut functions = Vec::new();
-    for attr in &ast.attrs {
-        if let Ok(Meta::List(MetaList {
+    for meta in super::get_metas_with_primary_key(ast, &[("tp_update", "update_by_pk")]) {
+        if let Meta::List(MetaList {
             ref path,
             ref nested,
             ..
-        })) = attr.parse_meta()
+        }) = meta
         {
             if path.is_ident("tp_update") {
                 let mut by_fields = Vec::new();
+                let mut by_conditions = HashMap::new();
                 let mut on_fields = Vec::new();
                 let mut version_fields = Vec::new();
                 let mut fn_name_attr = None;
@@ -64,19 +67,13 @@ pub fn derive_update(
                         NestedMeta::Meta(Meta::NameValue(nv)) => {
                             if nv.path.is_ident("by") {
                                 if let Lit::Str(lit) = &nv.lit {
-                                    let lit = lit.value();
-                                    let fields_str =
-                                        lit.split(',').map(|x| x.trim()).collect::<Vec<_>>();
-                                    by_fields =
-                                        super::check_fields(&fields_str, all_fields.clone());
-                                    if has_duplicates(&by_fields) {
-                                        panic!("Found duplicated fields: {:?}", fields_str);
+                                    (by_fields, by_conditions) =
+                                        super::parse_by_fields(&lit.value(), all_fields.clone());
+                                    if by_conditions.values().any(|x| x.op == super::ByOp::In) {
+                                        panic!("by = \"<field> in\" is only supported by tp_select_all");
                                     }
-                                    if by_fields.len() != fields_str.len() {
-                                        panic!(
-                                            "One of those value is duplicated or not a field in struct: {:?}",
-                                            fields_str
-                                        );
+                                    if by_conditions.values().any(|x| x.optional) {
+                                        panic!("Optional by fields are not supported by tp_update");
                                     }
                                 } else {
                                     panic!("Expected string value by = \"...\"");
@@ -194,13 +191,22 @@ pub fn derive_update(
 
                 by_fields.sort_by_key(|x| x.ident.clone());
                 on_fields.sort_by_key(|x| x.ident.clone());
+                // Checked before the tenant is added to the conditions
+                let unfiltered = by_fields.is_empty() && where_stmt_str.is_none() && version_fields.is_empty();
+                let prefix = if super::is_unscoped(nested, tenant.as_ref()) {
+                    "unscoped_"
+                } else {
+                    if let Some(tenant) = &tenant {
+                        if super::contains(&on_fields, &tenant.field) {
+                            panic!("The tenant column can only be updated by unscoped functions");
+                        }
+                        tenant.scope(&mut by_fields, &mut by_conditions);
+                    }
+                    ""
+                };
 
                 if on_fields.is_empty() {
-                    let func_name_by_field = by_fields
-                        .iter()
-                        .map(|x| get_field_name(x))
-                        .collect::<Vec<_>>()
-                        .join("_and_");
+                    let func_name_by_field = super::get_by_names(&by_fields, &by_conditions);
                     let (fn_name, fn_name_return) = if let Some(fn_name) = fn_name_attr {
                         if fn_name.len() == 0 {
                             panic!("fn_name must not be empty");
@@ -217,22 +223,14 @@ pub fn derive_update(
                             format!("update_by_{}_return", func_name_by_field),
                         )
                     };
-                    let fn_name = Ident::new(&fn_name, proc_macro2::Span::call_site());
+                    let fn_name = Ident::new(&format!("{prefix}{fn_name}"), proc_macro2::Span::call_site());
                     let fn_name_return =
-                        Ident::new(&fn_name_return, proc_macro2::Span::call_site());
+                        Ident::new(&format!("{prefix}{fn_name_return}"), proc_macro2::Span::call_site());
                     let fn_name_return_stream =
                         Ident::new(&format!("{fn_name_return}_stream"), proc_macro2::Span::call_site());
                     let mut fn_args = by_fields
                         .iter()
-                        .map(|field| {
-                            let arg_name = field.ident.as_ref().unwrap();
-                            let arg_type = &field.ty;
-                            if &arg_type.to_token_stream().to_string() == "String" {
-                                quote! { #arg_name: &'c str }
-                            } else {
-                                quote! { #arg_name: &'c #arg_type }
-                            }
-                        })
+                        .map(|field| super::get_by_condition(&by_conditions, field).gen_arg(field))
                         .collect::<Vec<_>>();
 
                     let set_fields = all_fields
@@ -240,6 +238,8 @@ pub fn derive_update(
                         .filter(|x| {
                             !super::contains(&by_fields, **x)
                                 && !super::contains(&version_fields, **x)
+                                // Only deletes and restores change the soft delete column
+                                && soft_delete.as_ref().map_or(true, |soft| x.ident != soft.field.ident)
                         })
                         .collect::<Vec<_>>();
                     if set_fields.is_empty() {
@@ -274,17 +274,15 @@ pub fn derive_update(
                         .iter()
                         .enumerate()
                         .map(|(index, field)| {
-                            match db {
-                                Database::Postgres => format!(
-                                    "{} = ${}",
-                                    get_field_name_as_column(field, db),
-                                    index + current_idx + 1
-                                ),
-                                Database::Sqlite | Database::Mysql | Database::Any => format!(
-                                    "{} = ?",
-                                    get_field_name_as_column(field, db)
-                                ),
-                            }
+                            let placeholder = match db {
+                                Database::Postgres => format!("${}", index + current_idx + 1),
+                                Database::Sqlite | Database::Mysql | Database::Any => "?".to_string(),
+                            };
+                            super::get_by_condition(&by_conditions, field).condition(
+                                &get_field_name_as_column(field, db),
+                                &placeholder,
+                                db,
+                            )
                         })
                         .collect::<Vec<_>>();
                     if version_fields.len() > 0 {
@@ -420,15 +418,17 @@ pub fn derive_update(
                                 Some(start_counter as i32),
                                 db,
                             );
-                            where_stmt.push(sql);
+                            where_stmt.push(format!("({sql})"));
                         } else {
-                            where_stmt.push(where_stmt_str);
+                            where_stmt.push(format!("({where_stmt_str})"));
                         }
                     }
 
-                    if by_fields.is_empty() && where_stmt.is_empty() {
+                    if unfiltered {
                         panic!("`by` fields or `where` attribute must not empty");
                     }
+                    // Deleted rows are not updated
+                    where_stmt.extend(soft_delete.as_ref().map(|x| x.alive_condition()));
 
                     let where_stmt = where_stmt.join(" AND ");
 
@@ -443,7 +443,7 @@ pub fn derive_update(
                     } else {
                         quote! {#(#fn_args),* ,}
                     };
-                    let generated = if return_entity.is_some() && matches!(db, Database::Postgres) {
+                    let generated = if return_entity.is_some() && matches!(db, Database::Postgres | Database::Sqlite) {
                         let binds_return = binds.clone();
                         let return_entity = return_entity.unwrap();
                         let (return_type, return_columns, query_func) = match return_entity.len() {
@@ -503,11 +503,7 @@ pub fn derive_update(
                     };
                     functions.push(super::gen_with_doc(generated));
                 } else {
-                    let func_name_by_field = by_fields
-                        .iter()
-                        .map(|x| get_field_name(x))
-                        .collect::<Vec<_>>()
-                        .join("_and_");
+                    let func_name_by_field = super::get_by_names(&by_fields, &by_conditions);
                     let func_name_on_field = on_fields
                         .iter()
                         .map(|x| get_field_name(x))
@@ -532,23 +528,14 @@ pub fn derive_update(
                             ),
                         )
                     };
-                    let fn_name = Ident::new(&fn_name, proc_macro2::Span::call_site());
+                    let fn_name = Ident::new(&format!("{prefix}{fn_name}"), proc_macro2::Span::call_site());
                     let fn_name_return =
-                        Ident::new(&fn_name_return, proc_macro2::Span::call_site());
+                        Ident::new(&format!("{prefix}{fn_name_return}"), proc_macro2::Span::call_site());
                     let fn_name_return_stream =
                         Ident::new(&format!("{fn_name_return}_stream"), proc_macro2::Span::call_site());
                     let mut fn_args = by_fields
                         .iter()
-                        .map(|field| {
-                            let arg_name = field.ident.as_ref().unwrap();
-                            let arg_type = &field.ty;
-                            if &arg_type.to_token_stream().to_string() == "String" {
-                                quote! { #arg_name: &'c str }
-                            } else {
-                                quote! { #arg_name: &'c #arg_type }
-                            }
-                            
-                        })
+                        .map(|field| super::get_by_condition(&by_conditions, field).gen_arg(field))
                         .collect::<Vec<_>>();
 
                     on_fields.iter().for_each(|field| {
@@ -597,10 +584,10 @@ pub fn derive_update(
                         .iter()
                         .enumerate()
                         .map(|(index, field)| {
-                            format!(
-                                "{} = ${}",
-                                get_field_name_as_column(field, db),
-                                index + current_idx + 1
+                            super::get_by_condition(&by_conditions, field).condition(
+                                &get_field_name_as_column(field, db),
+                                &format!("${}", index + current_idx + 1),
+                                db,
                             )
                         })
                         .collect::<Vec<_>>();
@@ -738,14 +725,16 @@ pub fn derive_update(
                                 Some(start_counter as i32),
                                 db,
                             );
-                            where_stmt.push(sql);
+                            where_stmt.push(format!("({sql})"));
                         } else {
-                            where_stmt.push(where_stmt_str);
+                            where_stmt.push(format!("({where_stmt_str})"));
                         }
                     }
-                    if by_fields.is_empty() && where_stmt.is_empty() {
+                    if unfiltered {
                         panic!("`by` fields or `where` attribute must not empty");
                     }
+                    // Deleted rows are not updated
+                    where_stmt.extend(soft_delete.as_ref().map(|x| x.alive_condition()));
                     let where_stmt = where_stmt.join(" AND ");
                     let sql = format!(
                         "UPDATE {table_name} SET {set_stmt} WHERE {where_stmt}",
@@ -759,7 +748,7 @@ pub fn derive_update(
                     } else {
                         quote! {#(#fn_args),* ,}
                     };
-                    let generated = if return_entity.is_some() && matches!(db, Database::Postgres) {
+                    let generated = if return_entity.is_some() && matches!(db, Database::Postgres | Database::Sqlite) {
 
                         let binds_return = binds.clone();
                         let return_entity = return_entity.unwrap();
@@ -22,6 +22,7 @@ pub fn derive_upsert(ast: &DeriveInput, for_path: Option<&syn::Path>, scope: sup
         panic!("`tp_upsert` is supported for Postgres, SQLite, and MySQL only")
     }
     let debug_slow = super::get_debug_slow_from_table_scope(&ast);
+    let tenant = super::get_tenant_from_table_scope(&ast);
 
     let all_fields = if let syn::Data::Struct(syn::DataStruct {
         fields: syn::Fields::Named(syn::FieldsNamed { ref named, .. }),
@@ -196,6 +197,18 @@ pub fn derive_upsert(ast: &DeriveInput, for_path: Option<&syn::Path>, scope: sup
                 }
                 by_fields.sort_by_key(|x| x.ident.clone());
                 on_fields.sort_by_key(|x| x.ident.clone());
+                // A scoped upsert inserts the row for the tenant argument, and leaves the rows of other tenants unchanged
+                let (prefix, tenant) = if super::is_unscoped(nested, tenant.as_ref()) {
+                    ("unscoped_", None)
+                } else {
+                    ("", tenant.as_ref())
+                };
+                if let Some(tenant) = tenant {
+                    if super::contains(&on_fields, &tenant.field) {
+                        panic!("The tenant column can only be updated by unscoped functions");
+                    }
+                }
+                let tenant_column = tenant.map(|x| get_field_name_as_column(&x.field, db));
 
                 let func_name_by_field = by_fields
                     .iter()
@@ -223,8 +236,8 @@ pub fn derive_upsert(ast: &DeriveInput, for_path: Option<&syn::Path>, scope: sup
                         format!("upsert_by_{}_return", func_name_by_field),
                     )
                 };
-                let fn_name = Ident::new(&fn_name, proc_macro2::Span::call_site());
-                let fn_name_return = Ident::new(&fn_name_return, proc_macro2::Span::call_site());
+                let fn_name = Ident::new(&format!("{prefix}{fn_name}"), proc_macro2::Span::call_site());
+                let fn_name_return = Ident::new(&format!("{prefix}{fn_name_return}"), proc_macro2::Span::call_site());
                 let mut fn_args = by_fields
                     .iter()
                     .map(|field| {
@@ -272,9 +285,17 @@ pub fn derive_upsert(ast: &DeriveInput, for_path: Option<&syn::Path>, scope: sup
                 let mut not_excluded_fields = by_fields
                     .iter()
                     .chain(version_fields.iter())
+                    .chain(tenant.map(|x| &x.field))
                     .map(|x| get_field_name(x))
                     .collect::<Vec<_>>()
                     ;
+                // MySQL has no WHERE on conflict, the columns of a row of another tenant keep their value
+                let mysql_set = |column: &str, value: String| match &tenant_column {
+                    Some(tenant_column) => {
+                        format!(" {column} = IF({tenant_column} = VALUES({tenant_column}), {value}, {column})")
+                    }
+                    None => format!(" {column} = {value}"),
+                };
                 
                 let do_update_stmt = match db {
                     Database::Postgres => {
@@ -382,14 +403,14 @@ pub fn derive_upsert(ast: &DeriveInput, for_path: Option<&syn::Path>, scope: sup
                                 .map(|x| {
                                     let column = check_column_name(x.to_string(), db);
                                     let values_column = check_column_name(x.to_string(), db);
-                                    format!(" {column} = VALUES({values_column})")
+                                    mysql_set(&column, format!("VALUES({values_column})"))
                                 })
                                 .collect::<Vec<_>>()
                                 ;
                             if !version_fields.is_empty() {
                                 let version_set_stmt = version_fields.iter().map(|x| {
                                     let x = get_field_name_as_column(x, db);
-                                    format!(" {x} = {x} + 1")
+                                    mysql_set(&x, format!("{x} + 1"))
                                 });
                                 set_stmt = set_stmt.into_iter().chain(version_set_stmt).collect();
                             }
@@ -406,14 +427,14 @@ pub fn derive_upsert(ast: &DeriveInput, for_path: Option<&syn::Path>, scope: sup
                                     let field_name = get_field_name(x);
                                     let column = get_field_name_as_column(x, db);
                                     let values_column = check_column_name(field_name.clone(), db);
-                                    format!(" {column} = VALUES({values_column})")
+                                    mysql_set(&column, format!("VALUES({values_column})"))
                                 })
                                 .collect::<Vec<_>>()
                                 ;
                             if !version_fields.is_empty() {
                                 let version_set_stmt = version_fields.iter().map(|x| {
                                     let x = get_field_name_as_column(x, db);
-                                    format!(" {x} = {x} + 1")
+                                    mysql_set(&x, format!("{x} + 1"))
                                 });
                                 set_stmt = set_stmt.into_iter().chain(version_set_stmt).collect();
                             }
@@ -530,6 +551,16 @@ pub fn derive_upsert(ast: &DeriveInput, for_path: Option<&syn::Path>, scope: sup
                     }
                     _ => (String::new(), String::new(), Vec::new())
                 };
+                let where_stmt = match &tenant_column {
+                    Some(tenant_column) if !do_nothing => {
+                        let tenant_condition = format!("{table_name}.{tenant_column} = EXCLUDED.{tenant_column}");
+                        match where_stmt.strip_prefix("WHERE ") {
+                            Some(where_sql) => format!("WHERE {tenant_condition} AND ({where_sql})"),
+                            None => format!("WHERE {tenant_condition}"),
+                        }
+                    }
+                    _ => where_stmt,
+                };
 
                 let sql = match db {
                     Database::Postgres | Database::Sqlite => {
@@ -545,10 +576,17 @@ pub fn derive_upsert(ast: &DeriveInput, for_path: Option<&syn::Path>, scope: sup
                     _ => panic!("Unsupported database for upsert")
                 };
                 super::check_valid_single_sql(&sql, db);
-                let insert_binds = insert_fields.iter().map(|field| {
-                    quote! {
+                let insert_binds = insert_fields.iter().map(|field| match tenant {
+                    Some(tenant) if tenant.field.ident.as_ref() == Some(*field) => quote! {
+                        .bind(#field)
+                    },
+                    _ => quote! {
                         .bind(&re.#field)
-                    }
+                    },
+                });
+                let tenant_arg = tenant.map(|x| {
+                    let arg = x.gen_arg(quote! {});
+                    quote! { #arg, }
                 });
 
                 // Combine insert binds with WHERE placeholder binds
@@ -580,9 +618,9 @@ pub fn derive_upsert(ast: &DeriveInput, for_path: Option<&syn::Path>, scope: sup
                     super::check_valid_single_sql(&sql_return, db);
                     let binds_return = binds.clone();
                     let fn_args = if where_args_vec.is_empty() {
-                        quote! { re: &#struct_name, conn: E }
+                        quote! { #tenant_arg re: &#struct_name, conn: E }
                     } else {
-                        quote! { re: &#struct_name, #(#where_args_vec,)* conn: E }
+                        quote! { #tenant_arg re: &#struct_name, #(#where_args_vec,)* conn: E }
                     };
                     quote! {
                         pub async fn #fn_name_return<'c, E: sqlx::Executor<'c, Database = #database>>(#fn_args) -> core::result::Result<#struct_name, sqlx::Error> {
@@ -598,9 +636,9 @@ pub fn derive_upsert(ast: &DeriveInput, for_path: Option<&syn::Path>, scope: sup
                     }
                 } else {
                     let fn_args = if where_args_vec.is_empty() {
-                        quote! { re: &#struct_name, conn: E }
+                        quote! { #tenant_arg re: &#struct_name, conn: E }
                     } else {
-                        quote! { re: &#struct_name, #(#where_args_vec,)* conn: E }
+                        quote! { #tenant_arg re: &#struct_name, #(#where_args_vec,)* conn: E }
                     };
                     quote! {
                         pub async fn #fn_name<'c, E: sqlx::Executor<'c, Database = #database>>(#fn_args) -> core::result::Result<u64, sqlx::Error> {
@@ -0,0 +1,34 @@
+//! Databases shared by the integration tests. Each test crate uses some of them
+#![allow(dead_code)]
+
+use sqlx::{postgres::PgPoolOptions, sqlite::SqlitePoolOptions, PgPool, SqlitePool};
+
+/// In-memory SQLite database after running the statements. The single connection keeps the database
+pub async fn sqlite(statements: &[&str]) -> Result<SqlitePool, sqlx::Error> {
+    let pool = SqlitePoolOptions::new().max_connections(1).connect(":memory:").await?;
+    for sql in statements {
+        sqlx::query(sql).execute(&pool).await?;
+    }
+    Ok(pool)
+}
+
+/// PostgreSQL database of `DATABASE_URL` after running the statements, usually creating `TEMP` tables.
+/// The single connection keeps the tables, which the other tests do not see
+pub async fn postgres(statements: &[&str]) -> Result<PgPool, sqlx::Error> {
+    connect_postgres(1, statements).await
+}
+
+/// PostgreSQL database of `DATABASE_URL` with several connections after running the statements, for the tests
+/// of concurrent transactions. Their tables are seen by the other tests, so the test drops them
+pub async fn postgres_sessions(statements: &[&str]) -> Result<PgPool, sqlx::Error> {
+    connect_postgres(4, statements).await
+}
+
+async fn connect_postgres(max_connections: u32, statements: &[&str]) -> Result<PgPool, sqlx::Error> {
+    let url = std::env::var("DATABASE_URL").expect("DATABASE_URL");
+    let pool = PgPoolOptions::new().max_connections(max_connections).connect(&url).await?;
+    for sql in statements {
+        sqlx::query(sql).execute(&pool).await?;
+    }
+    Ok(pool)
+}
@@ -0,0 +1,534 @@
+//! Builder tests, one module per feature. The PostgreSQL SQL is checked with `build_sql`, without a server
+#![allow(clippy::duplicated_attributes)]
+
+mod common;
+
+pub mod groups {
+    use sqlx_template::{PostgresTemplate, SqliteTemplate};
+    use sqlx::FromRow;
+    use super::common;
+
+    #[derive(SqliteTemplate, FromRow, Debug, Clone)]
+    #[table("users")]
+    #[tp_select_builder]
+    #[tp_update_builder]
+    #[tp_delete_builder]
+    pub struct User {
+        pub id: i32,
+        pub email: String,
+        pub name: String,
+        pub active: bool,
+    }
+
+    #[derive(PostgresTemplate, FromRow, Debug, Clone)]
+    #[table("users")]
+    #[tp_soft_delete(column = "deleted_at")]
+    #[tp_select_builder]
+    #[tp_update_builder]
+    pub struct UserPg {
+        pub id: i32,
+        pub email: String,
+        pub name: String,
+        pub active: bool,
+        pub deleted_at: Option<chrono::NaiveDateTime>,
+    }
+
+    async fn setup() -> Result<sqlx::SqlitePool, sqlx::Error> {
+        common::sqlite(&[
+            "CREATE TABLE users (id INTEGER PRIMARY KEY, email TEXT NOT NULL, name TEXT NOT NULL, active BOOLEAN NOT NULL)",
+            "INSERT INTO users VALUES (1, 'x@a.com', 'x', 1), (2, 'y@a.com', 'y', 0), (3, 'z@b.com', 'z', 1)",
+        ])
+        .await
+    }
+
+    fn ids(users: &[User]) -> Vec<i32> {
+        users.iter().map(|x| x.id).collect()
+    }
+
+    #[tokio::test]
+    async fn test_builder_groups() -> Result<(), sqlx::Error> {
+        let pool = setup().await?;
+
+        let users = User::builder_select()
+            .or(|b| b.email("y@a.com")?.name("z"))?
+            .active(&true)?
+            .order_by_id_asc()?
+            .find_all(&pool)
+            .await?;
+        assert_eq!(ids(&users), vec![3]);
+
+        let users = User::builder_select()
+            .or(|b| b.and_group(|b| b.email_like("%@a.com")?.active(&true))?.name("z"))?
+            .order_by_id_asc()?
+            .find_all(&pool)
+            .await?;
+        assert_eq!(ids(&users), vec![1, 3]);
+
+        let users = User::builder_select().not(|b| b.name("x"))?.order_by_id_asc()?.find_all(&pool).await?;
+        assert_eq!(ids(&users), vec![2, 3]);
+        assert_eq!(User::builder_select().or(Ok)?.count(&pool).await?, 3);
+
+        let updated = User::builder_update()
+            .on_active(&false)?
+            .or(|b| b.by_name("x")?.by_name("z"))?
+            .execute(&pool)
+            .await?;
+        assert_eq!(updated, 2);
+        assert_eq!(User::builder_select().active(&false)?.count(&pool).await?, 3);
+
+        let deleted = User::builder_delete().not(|b| b.email_like("%@a.com"))?.execute(&pool).await?;
+        assert_eq!(deleted, 1);
+        assert_eq!(User::builder_select().count(&pool).await?, 2);
+        Ok(())
+    }
+
+    #[test]
+    fn test_builder_groups_sql() -> Result<(), sqlx::Error> {
+        let sql = UserPg::builder_select()
+            .active(&true)?
+            .or(|b| b.email("a")?.not(|b| b.name("b")?.id(&1)))?
+            .name("c")?
+            .build_sql();
+        assert_eq!(
+            sql,
+            "SELECT id, email, name, active, deleted_at FROM users WHERE deleted_at IS NULL AND active = $1 \
+             AND (email = $2 OR NOT (name = $3 AND id = $4)) AND name = $5"
+        );
+
+        let sql = UserPg::builder_update()
+            .on_name("x")?
+            .or(|b| b.by_id(&1)?.by_id(&2))?
+            .build_sql();
+        assert_eq!(sql, "UPDATE users SET name = $1 WHERE deleted_at IS NULL AND (id = $2 OR id = $3)");
+
+        // The soft delete scope applies to the whole query
+        let sql = UserPg::builder_select().or(|b| b.name("a")?.only_deleted()?.name("b"))?.build_sql();
+        assert_eq!(
+            sql,
+            "SELECT id, email, name, active, deleted_at FROM users WHERE deleted_at IS NOT NULL AND (name = $1 OR name = $2)"
+        );
+        Ok(())
+    }
+}
+
+pub mod in_list {
+    use sqlx_template::{PostgresTemplate, SqliteTemplate};
+    use sqlx::FromRow;
+    use super::common;
+
+    #[derive(SqliteTemplate, FromRow, Debug, Clone)]
+    #[table("tasks")]
+    #[tp_select_builder]
+    #[tp_update_builder]
+    #[tp_delete_builder]
+    pub struct Task {
+        pub id: i32,
+        pub status: String,
+        pub done: bool,
+    }
+
+    #[derive(PostgresTemplate, FromRow, Debug, Clone)]
+    #[table("tasks")]
+    #[tp_select_builder]
+    #[tp_update_builder]
+    pub struct TaskPg {
+        pub id: i32,
+        pub status: String,
+    }
+
+    async fn setup() -> Result<sqlx::SqlitePool, sqlx::Error> {
+        common::sqlite(&[
+            "CREATE TABLE tasks (id INTEGER PRIMARY KEY, status TEXT NOT NULL, done BOOLEAN NOT NULL)",
+            "INSERT INTO tasks VALUES (1, 'open', 0), (2, 'late', 0), (3, 'closed', 1), (4, 'open', 0)",
+        ])
+        .await
+    }
+
+    fn ids(tasks: &[Task]) -> Vec<i32> {
+        tasks.iter().map(|x| x.id).collect()
+    }
+
+    #[tokio::test]
+    async fn test_builder_in() -> Result<(), sqlx::Error> {
+        let pool = setup().await?;
+        let statuses = vec!["open".to_string(), "late".to_string()];
+
+        let tasks = Task::builder_select().status_in(&statuses)?.id_not_in(&[4])?.order_by_id_asc()?.find_all(&pool).await?;
+        assert_eq!(ids(&tasks), vec![1, 2]);
+        assert_eq!(Task::builder_select().status_not_in(&statuses)?.count(&pool).await?, 1);
+
+        // An empty slice matches no rows with `_in` and all rows with `_not_in`
+        assert_eq!(Task::builder_select().id_in(&[])?.count(&pool).await?, 0);
+        assert_eq!(Task::builder_select().id_not_in(&[])?.count(&pool).await?, 4);
+        assert_eq!(Task::builder_select().or(|b| b.id_in(&[])?.done(&true))?.count(&pool).await?, 1);
+
+        let updated = Task::builder_update().on_done(&true)?.by_id_in(&[1, 2])?.execute(&pool).await?;
+        assert_eq!(updated, 2);
+        assert_eq!(Task::builder_update().on_done(&true)?.by_id_in(&[])?.execute(&pool).await?, 0);
+        assert_eq!(Task::builder_select().done_in(&[true])?.count(&pool).await?, 3);
+
+        assert_eq!(Task::builder_delete().status_not_in(&statuses)?.execute(&pool).await?, 1);
+        assert_eq!(Task::builder_delete().id_in(&[1, 9])?.execute(&pool).await?, 1);
+        assert_eq!(Task::builder_select().count(&pool).await?, 2);
+        Ok(())
+    }
+
+    #[test]
+    fn test_builder_in_sql() -> Result<(), sqlx::Error> {
+        let statuses = vec!["open".to_string(), "late".to_string()];
+        let sql = TaskPg::builder_select().status_in(&statuses)?.id_not_in(&[1])?.id(&2)?.build_sql();
+        assert_eq!(sql, "SELECT id, status FROM tasks WHERE status IN ($1, $2) AND id NOT IN ($3) AND id = $4");
+
+        let sql = TaskPg::builder_update().on_status("x")?.by_id_in(&[1, 2])?.build_sql();
+        assert_eq!(sql, "UPDATE tasks SET status = $1 WHERE id IN ($2, $3)");
+        Ok(())
+    }
+
+}
+
+pub mod null {
+    use sqlx_template::{PostgresTemplate, SqliteTemplate};
+    use sqlx::FromRow;
+    use super::common;
+
+    #[derive(SqliteTemplate, FromRow, Debug, Clone)]
+    #[table("users")]
+    #[tp_select_builder]
+    #[tp_update_builder]
+    #[tp_delete_builder]
+    pub struct User {
+        pub id: i32,
+        pub org: Option<i32>,
+        pub nickname: Option<String>,
+    }
+
+    #[derive(PostgresTemplate, FromRow, Debug, Clone)]
+    #[table("users")]
+    #[tp_select_builder]
+    #[tp_update_builder]
+    pub struct UserPg {
+        pub id: i32,
+        pub org: Option<i32>,
+    }
+
+    async fn setup() -> Result<sqlx::SqlitePool, sqlx::Error> {
+        common::sqlite(&[
+            "CREATE TABLE users (id INTEGER PRIMARY KEY, org INTEGER, nickname TEXT)",
+            "INSERT INTO users VALUES (1, 1, 'a'), (2, NULL, NULL), (3, 2, NULL), (4, 1, NULL)",
+        ])
+        .await
+    }
+
+    fn ids(users: &[User]) -> Vec<i32> {
+        users.iter().map(|x| x.id).collect()
+    }
+
+    #[tokio::test]
+    async fn test_builder_null() -> Result<(), sqlx::Error> {
+        let pool = setup().await?;
+
+        let users = User::builder_select().org(&1)?.order_by_id_asc()?.find_all(&pool).await?;
+        assert_eq!(ids(&users), vec![1, 4]);
+        let users = User::builder_select().org_is_null()?.find_all(&pool).await?;
+        assert_eq!(ids(&users), vec![2]);
+        assert_eq!(User::builder_select().org_is_not_null()?.org_gt(&1)?.count(&pool).await?, 1);
+        assert_eq!(User::builder_select().nickname("a")?.count(&pool).await?, 1);
+        assert_eq!(User::builder_select().nickname_is_null()?.org_in(&[1, 2])?.count(&pool).await?, 2);
+        assert_eq!(User::builder_select().or(|b| b.org_is_null()?.org(&2))?.count(&pool).await?, 2);
+
+        let updated = User::builder_update().on_org(&Some(3))?.by_org_is_null()?.execute(&pool).await?;
+        assert_eq!(updated, 1);
+        let updated = User::builder_update().on_nickname("b")?.by_org(&1)?.by_nickname_is_null()?.execute(&pool).await?;
+        assert_eq!(updated, 1);
+        assert_eq!(User::builder_select().nickname_is_not_null()?.count(&pool).await?, 2);
+
+        assert_eq!(User::builder_delete().nickname_is_null()?.execute(&pool).await?, 2);
+        assert_eq!(User::builder_select().count(&pool).await?, 2);
+        Ok(())
+    }
+
+    #[test]
+    fn test_builder_null_sql() -> Result<(), sqlx::Error> {
+        let sql = UserPg::builder_select().org_is_null()?.id(&1)?.org_is_not_null()?.org(&2)?.build_sql();
+        assert_eq!(sql, "SELECT id, org FROM users WHERE org IS NULL AND id = $1 AND org IS NOT NULL AND org = $2");
+
+        let sql = UserPg::builder_update().on_org(&None)?.by_org_is_null()?.by_id(&1)?.build_sql();
+        assert_eq!(sql, "UPDATE users SET org = $1 WHERE org IS NULL AND id = $2");
+        Ok(())
+    }
+
+}
+
+pub mod search {
+    use sqlx_template::{PostgresTemplate, SqliteTemplate};
+    use sqlx::FromRow;
+    use super::common;
+
+    #[derive(SqliteTemplate, FromRow, Debug, Clone)]
+    #[table("products")]
+    #[tp_select_builder]
+    #[tp_update_builder]
+    #[tp_delete_builder]
+    pub struct Product {
+        pub id: i32,
+        pub name: String,
+        pub price: i32,
+    }
+
+    #[derive(PostgresTemplate, FromRow, Debug, Clone)]
+    #[table("products")]
+    #[tp_select_builder]
+    #[tp_update_builder]
+    pub struct ProductPg {
+        pub id: i32,
+        pub name: String,
+        pub price: i32,
+    }
+
+    async fn setup() -> Result<sqlx::SqlitePool, sqlx::Error> {
+        common::sqlite(&[
+            "CREATE TABLE products (id INTEGER PRIMARY KEY, name TEXT NOT NULL, price INTEGER NOT NULL)",
+            "INSERT INTO products VALUES (1, 'Red Apple', 10), (2, '100% juice', 20), (3, 'green_tea', 30), \
+             (4, 'greenXtea', 40), (5, 'Big! deal', 50)",
+        ])
+        .await
+    }
+
+    fn ids(products: &[Product]) -> Vec<i32> {
+        products.iter().map(|x| x.id).collect()
+    }
+
+    #[tokio::test]
+    async fn test_builder_search() -> Result<(), sqlx::Error> {
+        let pool = setup().await?;
+
+        let products = Product::builder_select().price_between(&20, &40)?.order_by_id_asc()?.find_all(&pool).await?;
+        assert_eq!(ids(&products), vec![2, 3, 4]);
+        assert_eq!(Product::builder_select().price_between(&40, &20)?.count(&pool).await?, 0);
+
+        let products = Product::builder_select().name_ilike("%APPLE")?.find_all(&pool).await?;
+        assert_eq!(ids(&products), vec![1]);
+        assert_eq!(ids(&Product::builder_select().name_ilike("GREEN!_%")?.find_all(&pool).await?), vec![3]);
+
+        // `%`, `_` and the escape character are matched literally
+        assert_eq!(ids(&Product::builder_select().name_contains("0%")?.find_all(&pool).await?), vec![2]);
+        assert_eq!(ids(&Product::builder_select().name_contains("n_t")?.find_all(&pool).await?), vec![3]);
+        assert_eq!(ids(&Product::builder_select().name_contains("! ")?.find_all(&pool).await?), vec![5]);
+        assert_eq!(ids(&Product::builder_select().name_start_with("green_")?.find_all(&pool).await?), vec![3]);
+        assert_eq!(ids(&Product::builder_select().name_end_with("_tea")?.find_all(&pool).await?), vec![3]);
+        assert_eq!(Product::builder_select().name_like("green_tea")?.count(&pool).await?, 2);
+
+        let updated = Product::builder_update().on_price(&0)?.by_price_between(&10, &20)?.by_name_contains("e")?.execute(&pool).await?;
+        assert_eq!(updated, 2);
+        assert_eq!(Product::builder_update().on_price(&1)?.by_name_ilike("GREEN%")?.execute(&pool).await?, 2);
+
+        assert_eq!(Product::builder_delete().price_between(&0, &1)?.name_contains("_")?.execute(&pool).await?, 1);
+        assert_eq!(Product::builder_select().count(&pool).await?, 4);
+        Ok(())
+    }
+
+    #[test]
+    fn test_builder_search_sql() -> Result<(), sqlx::Error> {
+        let sql = ProductPg::builder_select()
+            .price_between(&1, &2)?
+            .name_ilike("a%")?
+            .name_contains("b")?
+            .id(&3)?
+            .build_sql();
+        assert_eq!(
+            sql,
+            "SELECT id, name, price FROM products WHERE price BETWEEN $1 AND $2 AND name ILIKE $3 ESCAPE '!' \
+             AND name LIKE $4 ESCAPE '!' AND id = $5"
+        );
+
+        let sql = ProductPg::builder_update().on_price(&0)?.by_price_between(&1, &2)?.build_sql();
+        assert_eq!(sql, "UPDATE products SET price = $1 WHERE price BETWEEN $2 AND $3");
+        Ok(())
+    }
+
+}
+
+pub mod limit {
+    use futures::StreamExt;
+    use sqlx_template::{PostgresTemplate, SqliteTemplate};
+    use sqlx::FromRow;
+    use super::common;
+
+    #[derive(SqliteTemplate, FromRow, Debug, Clone)]
+    #[table("events")]
+    #[tp_select_builder(cursor = "id")]
+    pub struct Event {
+        pub id: i32,
+        pub kind: String,
+    }
+
+    #[derive(PostgresTemplate, FromRow, Debug, Clone)]
+    #[table("events")]
+    #[tp_select_builder]
+    pub struct EventPg {
+        pub id: i32,
+        pub kind: String,
+    }
+
+    async fn setup() -> Result<sqlx::SqlitePool, sqlx::Error> {
+        common::sqlite(&[
+            "CREATE TABLE events (id INTEGER PRIMARY KEY, kind TEXT NOT NULL)",
+            "INSERT INTO events VALUES (1, 'a'), (2, 'b'), (3, 'a'), (4, 'a'), (5, 'b')",
+        ])
+        .await
+    }
+
+    fn ids(events: &[Event]) -> Vec<i32> {
+        events.iter().map(|x| x.id).collect()
+    }
+
+    #[tokio::test]
+    async fn test_builder_limit() -> Result<(), sqlx::Error> {
+        let pool = setup().await?;
+
+        let events = Event::builder_select().order_by_id_asc()?.limit(2)?.find_all(&pool).await?;
+        assert_eq!(ids(&events), vec![1, 2]);
+        let events = Event::builder_select().limit(2)?.offset(1)?.kind("a")?.order_by_id_asc()?.find_all(&pool).await?;
+        assert_eq!(ids(&events), vec![3, 4]);
+        let events = Event::builder_select().order_by_id_desc()?.offset(3)?.find_all(&pool).await?;
+        assert_eq!(ids(&events), vec![2, 1]);
+        let events = Event::builder_select().offset(3)?.order_by_id_asc()?.first_n(1)?.find_all(&pool).await?;
+        assert_eq!(ids(&events), vec![1]);
+
+        // Counts and aggregates reject the limit and offset
+        assert!(matches!(
+            Event::builder_select().kind("a")?.limit(1)?.count(&pool).await,
+            Err(sqlx::Error::InvalidArgument(_))
+        ));
+        assert!(Event::builder_select().offset(1)?.exists(&pool).await.is_err());
+        assert!(Event::builder_select().limit(1)?.max_id(&pool).await.is_err());
+        assert!(Event::builder_select().limit(1)?.count_group_by_kind(&pool).await.is_err());
+
+        let event = Event::builder_select().order_by_id_desc()?.offset(1)?.find_one(&pool).await?;
+        assert_eq!(event.map(|x| x.id), Some(4));
+
+        let mut builder = Event::builder_select().kind("b")?.order_by_id_asc()?.limit(1)?;
+        let events = builder.stream(&pool).await.collect::<Vec<_>>().await;
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].as_ref().unwrap().id, 2);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_builder_limit_page() -> Result<(), sqlx::Error> {
+        let pool = setup().await?;
+
+        // The page replaces the limit and offset of the builder
+        let builder = Event::builder_select().kind("a")?.order_by_id_asc()?.limit(1)?;
+        let (events, total) = builder.find_page((1, 5, true), &pool).await?;
+        assert_eq!(ids(&events), vec![3, 4]);
+        assert_eq!(total, Some(3));
+
+        // So does the cursor
+        let builder = Event::builder_select().order_by_id_asc()?.offset(2)?;
+        let (events, next) = builder.find_after(None, 2, &pool).await?;
+        assert_eq!(ids(&events), vec![1, 2]);
+        assert!(next.is_some());
+        Ok(())
+    }
+
+    #[test]
+    fn test_builder_limit_sql() -> Result<(), sqlx::Error> {
+        let builder = EventPg::builder_select().limit(10)?.offset(20)?.kind("a")?.order_by_id_asc()?;
+        assert_eq!(
+            builder.build_sql(),
+            "SELECT id, kind FROM events WHERE kind = $1 ORDER BY id ASC LIMIT $2 OFFSET $3"
+        );
+        assert_eq!(builder.build_sql_with_params().1, 3);
+
+        let sql = EventPg::builder_select().offset(5)?.for_update()?.build_sql();
+        assert_eq!(sql, "SELECT id, kind FROM events LIMIT $1 OFFSET $2 FOR UPDATE");
+        Ok(())
+    }
+
+}
+
+pub mod project {
+    use sqlx_template::{PostgresTemplate, SqliteTemplate};
+    use sqlx::FromRow;
+    use super::common;
+
+    #[derive(SqliteTemplate, FromRow, Debug, Clone)]
+    #[table("accounts")]
+    #[tp_select_builder]
+    pub struct Account {
+        pub id: i32,
+        pub email: String,
+        pub nickname: Option<String>,
+    }
+
+    #[derive(SqliteTemplate, FromRow, Debug, Clone)]
+    #[table("accounts")]
+    #[tp_select_builder]
+    pub struct AccountByEmail {
+        #[primary_key]
+        pub email: String,
+        pub id: i32,
+    }
+
+    #[derive(PostgresTemplate, FromRow, Debug, Clone)]
+    #[table("accounts")]
+    #[tp_select_builder]
+    pub struct AccountPg {
+        pub id: i32,
+        pub email: String,
+    }
+
+    async fn setup() -> Result<sqlx::SqlitePool, sqlx::Error> {
+        common::sqlite(&[
+            "CREATE TABLE accounts (id INTEGER PRIMARY KEY, email TEXT NOT NULL, nickname TEXT)",
+            "INSERT INTO accounts VALUES (1, 'a@x.com', 'a'), (2, 'b@y.com', NULL), (3, 'c@x.com', NULL)",
+        ])
+        .await
+    }
+
+    #[tokio::test]
+    async fn test_builder_select_column() -> Result<(), sqlx::Error> {
+        let pool = setup().await?;
+
+        let ids = Account::builder_select().email_end_with("@x.com")?.order_by_id_desc()?.pluck_id(&pool).await?;
+        assert_eq!(ids, vec![3, 1]);
+        let emails = Account::builder_select().id_gt(&1)?.order_by_id_asc()?.limit(1)?.select_email(&pool).await?;
+        assert_eq!(emails, vec!["b@y.com".to_string()]);
+        let nicknames = Account::builder_select().order_by_id_asc()?.select_nickname(&pool).await?;
+        assert_eq!(nicknames, vec![Some("a".to_string()), None, None]);
+
+        // `pluck_id` selects the primary key
+        let emails = AccountByEmail::builder_select().id_lte(&2)?.order_by_id_asc()?.pluck_id(&pool).await?;
+        assert_eq!(emails, vec!["a@x.com".to_string(), "b@y.com".to_string()]);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_builder_project() -> Result<(), sqlx::Error> {
+        let pool = setup().await?;
+
+        let rows = Account::builder_select()
+            .nickname_is_null()?
+            .order_by_id_asc()?
+            .project::<(i32, String)>(&[AccountColumn::Id, AccountColumn::Email], &pool)
+            .await?;
+        assert_eq!(rows, vec![(2, "b@y.com".to_string()), (3, "c@x.com".to_string())]);
+
+        let rows = Account::builder_select().id(&1)?.project::<(Option<String>,)>(&[AccountColumn::Nickname], &pool).await?;
+        assert_eq!(rows, vec![(Some("a".to_string()),)]);
+        assert_eq!(AccountColumn::Nickname.column(), "nickname");
+
+        let result = Account::builder_select().project::<(i32,)>(&[], &pool).await;
+        assert!(matches!(result, Err(sqlx::Error::InvalidArgument(_))));
+        Ok(())
+    }
+
+    #[test]
+    fn test_builder_project_sql() -> Result<(), sqlx::Error> {
+        let sql = AccountPg::builder_select().email_end_with("@x.com")?.order_by_id_desc()?.limit(2)?.build_sql();
+        assert_eq!(sql, "SELECT id, email FROM accounts WHERE email LIKE $1 ESCAPE '!' ORDER BY id DESC LIMIT $2");
+        assert_eq!(AccountPgColumn::Email.column(), "email");
+        Ok(())
+    }
+}
@@ -0,0 +1,344 @@
+//! Insert tests, one module per feature. The ignored tests need a PostgreSQL server, e.g.
+//! `DATABASE_URL=postgres://postgres@localhost/postgres cargo test -- --include-ignored`
+#![allow(clippy::duplicated_attributes)]
+
+mod common;
+
+pub mod insert_many {
+    use sqlx_template::{PostgresTemplate, SqliteTemplate};
+    use sqlx::FromRow;
+    use super::common;
+
+    #[derive(SqliteTemplate, FromRow, Debug, Clone)]
+    #[table("events")]
+    pub struct Event {
+        #[auto]
+        pub id: i32,
+        pub name: String,
+        pub score: i32,
+        pub active: bool,
+    }
+
+    #[derive(PostgresTemplate, FromRow, Debug, Clone)]
+    #[table("events")]
+    pub struct EventPg {
+        #[auto]
+        pub id: i32,
+        pub name: String,
+        pub score: i32,
+    }
+
+    #[tokio::test]
+    async fn test_insert_many_chunks() -> Result<(), sqlx::Error> {
+        let pool = common::sqlite(&[r#"
+            CREATE TABLE events (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                name TEXT NOT NULL,
+                score INTEGER NOT NULL,
+                active BOOLEAN NOT NULL
+            )
+            "#])
+        .await?;
+
+        assert_eq!(Event::insert_many(&[], &pool).await?, 0);
+
+        // 3 binds per row: more than one chunk is needed with the SQLite limit of 32766
+        let events = (0..12000)
+            .map(|i| Event {
+                id: 0,
+                name: format!("event-{i}"),
+                score: i,
+                active: i % 2 == 0,
+            })
+            .collect::<Vec<_>>();
+        let rows_affected = Event::insert_many(&events, &pool).await?;
+        assert_eq!(rows_affected, 12000);
+        assert_eq!(Event::count_all(&pool).await?, 12000);
+
+        let last = sqlx::query_as::<_, Event>("SELECT * FROM events WHERE score = 11999")
+            .fetch_one(&pool)
+            .await?;
+        assert_eq!(last.name, "event-11999");
+        assert!(!last.active);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_insert_many_atomic() -> Result<(), sqlx::Error> {
+        let pool = common::sqlite(&[
+            "CREATE TABLE events (id INTEGER PRIMARY KEY AUTOINCREMENT, name TEXT NOT NULL, score INTEGER NOT NULL UNIQUE, active BOOLEAN NOT NULL)",
+        ])
+        .await?;
+
+        // The duplicated score fails the second chunk, which rolls back the first one
+        let events = (0..12000)
+            .map(|i| Event {
+                id: 0,
+                name: format!("event-{i}"),
+                score: if i == 11000 { 0 } else { i },
+                active: true,
+            })
+            .collect::<Vec<_>>();
+        assert!(Event::insert_many(&events, &pool).await.is_err());
+        assert_eq!(Event::count_all(&pool).await?, 0);
+
+        // Inside a transaction, the rows are only visible once it is committed
+        let mut tx = pool.begin().await?;
+        assert_eq!(Event::insert_many(&events[..10], &mut *tx).await?, 10);
+        assert_eq!(Event::insert_many_return(&events[10..20], &mut tx).await?.len(), 10);
+        tx.rollback().await?;
+        assert_eq!(Event::count_all(&pool).await?, 0);
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[ignore]
+    async fn test_insert_many_chunks_pg() -> Result<(), sqlx::Error> {
+        let pool = common::postgres(&["CREATE TEMP TABLE events (id SERIAL PRIMARY KEY, name TEXT NOT NULL, score INT NOT NULL)"]).await?;
+
+        // 2 binds per row: more than one chunk is needed with the PostgreSQL limit of 65535
+        let events = (0..40000)
+            .map(|i| EventPg {
+                id: 0,
+                name: format!("event-{i}"),
+                score: i,
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(EventPg::insert_many(&events, &pool).await?, 40000);
+        assert_eq!(EventPg::count_all(&pool).await?, 40000);
+        Ok(())
+    }
+}
+
+pub mod sqlite_returning {
+    use sqlx_template::SqliteTemplate;
+    use sqlx::FromRow;
+    use super::common;
+
+    #[derive(SqliteTemplate, FromRow, Debug, Clone, PartialEq)]
+    #[table("users")]
+    #[tp_update(by = "id", returning = true)]
+    #[tp_update(by = "id", on = "score", returning = "score")]
+    #[tp_delete(by = "id", returning = true)]
+    #[tp_delete(by = "email", returning = "id, score")]
+    #[tp_upsert(by = "email", returning = true)]
+    pub struct User {
+        #[auto]
+        pub id: i32,
+        pub email: String,
+        pub score: i32,
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_returning() -> Result<(), sqlx::Error> {
+        let pool = common::sqlite(&[r#"
+            CREATE TABLE users (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                email TEXT UNIQUE NOT NULL,
+                score INTEGER NOT NULL
+            )
+            "#])
+        .await?;
+
+        let user = User { id: 0, email: "alice@example.com".into(), score: 10 };
+        let alice = User::insert_return(&user, &pool).await?;
+        assert_eq!(alice.id, 1);
+        assert_eq!(alice.score, 10);
+
+        let many = User::insert_many_return(
+            &[
+                User { id: 0, email: "bob@example.com".into(), score: 20 },
+                User { id: 0, email: "carol@example.com".into(), score: 30 },
+            ],
+            &pool,
+        )
+        .await?;
+        assert_eq!(many.iter().map(|x| x.id).collect::<Vec<_>>(), vec![2, 3]);
+
+        let updated = User::update_by_id_return(&alice.id, &User { score: 11, ..alice.clone() }, &pool).await?;
+        assert_eq!(updated.len(), 1);
+        assert_eq!(updated[0].score, 11);
+
+        let scores = User::update_by_id_on_score_return(&alice.id, &15, &pool).await?;
+        assert_eq!(scores, vec![15]);
+
+        let upserted = User::upsert_by_email_return(&User { id: 0, email: "bob@example.com".into(), score: 25 }, &pool).await?;
+        assert_eq!(upserted.id, 2);
+        assert_eq!(upserted.score, 25);
+
+        let deleted = User::delete_by_email_return("carol@example.com", &pool).await?;
+        assert_eq!(deleted, vec![(3, 30)]);
+
+        let deleted = User::delete_by_id_return(&alice.id, &pool).await?;
+        assert_eq!(deleted.len(), 1);
+        assert_eq!(deleted[0].email, "alice@example.com");
+        Ok(())
+    }
+}
+
+pub mod tp_insert {
+    use sqlx_template::SqliteTemplate;
+    use sqlx::FromRow;
+    use super::common;
+
+    #[derive(SqliteTemplate, FromRow, Debug, Clone)]
+    #[table("accounts")]
+    #[tp_insert(returning = "id")]
+    #[tp_insert(returning = "id, score")]
+    #[tp_insert(on_conflict = "ignore")]
+    #[tp_insert(on_conflict = "ignore", returning = true)]
+    #[tp_insert(fn_name = "create", returning = true)]
+    pub struct Account {
+        #[auto]
+        pub id: i32,
+        pub email: String,
+        pub score: i32,
+    }
+
+    #[tokio::test]
+    async fn test_tp_insert() -> Result<(), sqlx::Error> {
+        let pool = common::sqlite(&[r#"
+            CREATE TABLE accounts (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                email TEXT UNIQUE NOT NULL,
+                score INTEGER NOT NULL
+            )
+            "#])
+        .await?;
+
+        let account = |email: &str, score| Account { id: 0, email: email.into(), score };
+
+        let id = Account::insert_return_id(&account("a@example.com", 1), &pool).await?;
+        assert_eq!(id, 1);
+
+        let (id, score) = Account::insert_return_id_and_score(&account("b@example.com", 2), &pool).await?;
+        assert_eq!((id, score), (2, 2));
+
+        assert_eq!(Account::insert_or_ignore(&account("a@example.com", 3), &pool).await?, 0);
+        assert_eq!(Account::insert_or_ignore(&account("c@example.com", 3), &pool).await?, 1);
+
+        assert!(Account::insert_or_ignore_return(&account("a@example.com", 4), &pool).await?.is_none());
+        let inserted = Account::insert_or_ignore_return(&account("d@example.com", 4), &pool).await?;
+        assert_eq!(inserted.map(|x| x.email), Some("d@example.com".to_string()));
+
+        let created = Account::create(&account("e@example.com", 5), &pool).await?;
+        assert_eq!(created.email, "e@example.com");
+        assert_eq!(created.score, 5);
+
+        assert!(Account::insert(&account("a@example.com", 6), &pool).await.is_err());
+        Ok(())
+    }
+
+}
+
+pub mod copy {
+    use sqlx_template::PostgresTemplate;
+    use sqlx::FromRow;
+    use futures::TryStreamExt;
+    use super::common;
+
+    #[derive(PostgresTemplate, FromRow, Debug, Clone, PartialEq)]
+    #[table("measurements")]
+    #[tp_copy_out]
+    pub struct Measurement {
+        #[auto]
+        pub id: i64,
+        pub sensor: String,
+        pub value: f64,
+        pub note: Option<String>,
+        pub taken_at: chrono::NaiveDateTime,
+    }
+
+    #[tokio::test]
+    #[ignore]
+    async fn test_copy_roundtrip() -> Result<(), sqlx::Error> {
+        let pool = common::postgres(&["CREATE TEMP TABLE measurements (id BIGSERIAL PRIMARY KEY, sensor TEXT NOT NULL, value FLOAT8 NOT NULL, note TEXT, taken_at TIMESTAMP NOT NULL)"]).await?;
+        let mut conn = pool.acquire().await?;
+
+        let taken_at = chrono::NaiveDate::from_ymd_opt(2024, 2, 29).unwrap().and_hms_micro_opt(12, 30, 1, 250).unwrap();
+        let rows = (0..5000)
+            .map(|i| Measurement {
+                id: 0,
+                sensor: format!("s{i}"),
+                value: i as f64 / 2.0,
+                note: (i % 2 == 0).then(|| "even".to_string()),
+                taken_at,
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(Measurement::copy_in(&rows, &mut conn).await?, 5000);
+
+        let mut copied = Measurement::copy_out(&mut conn).await?.try_collect::<Vec<_>>().await?;
+        copied.sort_by_key(|x| x.id);
+        assert_eq!(copied.len(), 5000);
+        for (i, (copied, row)) in copied.iter().zip(&rows).enumerate() {
+            assert_eq!(copied.id, i as i64 + 1);
+            assert_eq!(Measurement { id: 0, ..copied.clone() }, *row);
+        }
+        Ok(())
+    }
+}
+
+pub mod insert_builder {
+    use sqlx_template::SqliteTemplate;
+    use sqlx::FromRow;
+    use super::common;
+
+    #[derive(SqliteTemplate, FromRow, Debug, Clone)]
+    #[table("tasks")]
+    #[tp_insert_builder]
+    pub struct Task {
+        #[auto]
+        pub id: i32,
+        pub title: String,
+        pub status: String,
+        pub priority: i32,
+    }
+
+    #[tokio::test]
+    async fn test_insert_builder() -> Result<(), sqlx::Error> {
+        let pool = common::sqlite(&[r#"
+            CREATE TABLE tasks (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                title TEXT NOT NULL DEFAULT 'untitled',
+                status TEXT NOT NULL DEFAULT 'todo',
+                priority INTEGER NOT NULL DEFAULT 3
+            )
+            "#])
+        .await?;
+
+        let builder = Task::builder_insert().on_title("write docs")?;
+        assert_eq!(builder.build_sql(), "INSERT INTO tasks(title) VALUES (?)");
+        assert_eq!(builder.execute(&pool).await?, 1);
+
+        let task = Task::builder_insert()
+            .on_title("review")?
+            .on_priority(&1)?
+            .execute_returning(&pool)
+            .await?;
+        assert_eq!(task.title, "review");
+        assert_eq!(task.status, "todo");
+        assert_eq!(task.priority, 1);
+
+        let task = Task::builder_insert().execute_returning(&pool).await?;
+        assert_eq!(task.id, 3);
+        assert_eq!(task.title, "untitled");
+        assert_eq!(task.priority, 3);
+
+        let first = sqlx::query_as::<_, Task>("SELECT * FROM tasks WHERE id = 1")
+            .fetch_one(&pool)
+            .await?;
+        assert_eq!(first.status, "todo");
+        assert_eq!(first.priority, 3);
+
+        // Setting a field again replaces its value instead of repeating the column
+        let builder = Task::builder_insert()
+            .on_priority(&5)?
+            .on_title("draft")?
+            .on_title("final")?;
+        assert_eq!(builder.build_sql(), "INSERT INTO tasks(title, priority) VALUES (?, ?)");
+        let task = builder.execute_returning(&pool).await?;
+        assert_eq!(task.title, "final");
+        assert_eq!(task.priority, 5);
+        Ok(())
+    }
+}
@@ -0,0 +1,1405 @@
+//! Select tests, one module per feature. The ignored tests need a PostgreSQL server, e.g.
+//! `DATABASE_URL=postgres://postgres@localhost/postgres cargo test -- --include-ignored`
+#![allow(clippy::duplicated_attributes)]
+
+mod common;
+
+pub mod page_counts {
+    use sqlx_template::SqliteTemplate;
+    use sqlx::FromRow;
+    use super::common;
+
+    #[derive(SqliteTemplate, FromRow, Debug, Clone)]
+    #[table("notes")]
+    #[tp_select_page(order = "id desc")]
+    pub struct Note {
+        #[auto]
+        pub id: i32,
+        pub body: String,
+    }
+
+    #[tokio::test]
+    async fn test_page_counts() -> Result<(), sqlx::Error> {
+        let pool = common::sqlite(&[
+            "CREATE TABLE notes (id INTEGER PRIMARY KEY AUTOINCREMENT, body TEXT NOT NULL)",
+            "INSERT INTO notes (body) VALUES ('a'), ('b'), ('c'), ('d'), ('e')",
+        ])
+        .await?;
+
+        // The total is counted with its own query, not the paged one
+        let (notes, total) = Note::find_page_all((2, 2, true), &pool).await?;
+        assert_eq!(notes.iter().map(|x| x.body.as_str()).collect::<Vec<_>>(), vec!["c", "d"]);
+        assert_eq!(total, Some(5));
+
+        let (notes, total) = Note::find_page_order_by_id_desc((0, 2, true), &pool).await?;
+        assert_eq!(notes.iter().map(|x| x.id).collect::<Vec<_>>(), vec![5, 4]);
+        assert_eq!(total, Some(5));
+        Ok(())
+    }
+}
+
+pub mod cursor_pagination {
+    use chrono::{NaiveDate, NaiveDateTime};
+    use sqlx_template::SqliteTemplate;
+    use sqlx::FromRow;
+    use super::common;
+
+    #[derive(SqliteTemplate, FromRow, Debug, Clone)]
+    #[table("posts")]
+    #[tp_select_cursor(by = "org", order = "created_at desc, id desc", serde = true)]
+    #[tp_select_cursor(order = "score asc, id desc", fn_name = "find_after_by_score")]
+    #[tp_select_builder(cursor = "score, id")]
+    pub struct Post {
+        #[auto]
+        pub id: i32,
+        pub org: String,
+        pub created_at: NaiveDateTime,
+        pub score: i32,
+    }
+
+    async fn setup() -> Result<sqlx::SqlitePool, sqlx::Error> {
+        let pool = common::sqlite(&[r#"
+            CREATE TABLE posts (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                org TEXT NOT NULL,
+                created_at TEXT NOT NULL,
+                score INTEGER NOT NULL
+            )
+            "#])
+        .await?;
+        // Several posts share the same created_at and score, the id breaks the ties
+        let start = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap().and_hms_opt(0, 0, 0).unwrap();
+        for i in 0..25i32 {
+            let org = if i % 5 == 0 { "other" } else { "acme" };
+            let created_at = start + chrono::Duration::milliseconds(i64::from(i / 3) * 250);
+            Post::insert(&Post { id: 0, org: org.into(), created_at, score: i % 4 }, &pool).await?;
+        }
+        Ok(pool)
+    }
+
+    fn ids(posts: &[Post]) -> Vec<i32> {
+        posts.iter().map(|x| x.id).collect()
+    }
+
+    #[tokio::test]
+    async fn test_cursor_attribute() -> Result<(), sqlx::Error> {
+        let pool = setup().await?;
+
+        let mut cursor = None;
+        let mut all = vec![];
+        loop {
+            let (rows, next) = Post::find_after_by_org_order_by_created_at_desc_and_id_desc("acme", cursor.as_ref(), 6, &pool).await?;
+            assert!(rows.len() <= 6);
+            all.extend(ids(&rows));
+            match next {
+                Some(next) => cursor = Some(next),
+                None => break,
+            }
+        }
+        let expected = sqlx::query_scalar::<_, i32>("SELECT id FROM posts WHERE org = 'acme' ORDER BY created_at DESC, id DESC")
+            .fetch_all(&pool)
+            .await?;
+        assert_eq!(all.len(), 20);
+        assert_eq!(all, expected);
+
+        // Mixed directions
+        let mut cursor = None;
+        let mut all = vec![];
+        loop {
+            let (rows, next) = Post::find_after_by_score(cursor.as_ref(), 4, &pool).await?;
+            all.extend(ids(&rows));
+            match next {
+                Some(next) => cursor = Some(next),
+                None => break,
+            }
+        }
+        let expected = sqlx::query_scalar::<_, i32>("SELECT id FROM posts ORDER BY score ASC, id DESC")
+            .fetch_all(&pool)
+            .await?;
+        assert_eq!(all, expected);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_cursor_encoding() -> Result<(), sqlx::Error> {
+        let pool = setup().await?;
+
+        // The cursor holds a `NaiveDateTime` with fractional seconds
+        let (rows, next) = Post::find_after_by_org_order_by_created_at_desc_and_id_desc("acme", None, 4, &pool).await?;
+        let next = next.unwrap();
+        let encoded = next.encode();
+        assert!(encoded.chars().all(|c| c.is_ascii_hexdigit()));
+        let decoded = PostCursor::decode(&encoded)?;
+        assert_eq!(decoded.encode(), encoded);
+        assert_eq!(rows.last().unwrap().created_at.to_string(), "2024-01-01 00:00:01.750");
+
+        let (rows, _) = Post::find_after_by_org_order_by_created_at_desc_and_id_desc("acme", Some(&decoded), 4, &pool).await?;
+        let (expected, _) = Post::find_after_by_org_order_by_created_at_desc_and_id_desc("acme", Some(&next), 4, &pool).await?;
+        assert_eq!(ids(&rows), ids(&expected));
+
+        let json = serde_json::to_string(&next).unwrap();
+        let cursor: PostCursor = serde_json::from_str(&json).unwrap();
+        assert_eq!(cursor.encode(), encoded);
+
+        assert!(matches!(PostCursor::decode("zz"), Err(sqlx::Error::InvalidArgument(_))));
+        assert!(matches!(PostCursor::decode(&format!("{encoded}00")), Err(sqlx::Error::InvalidArgument(_))));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_cursor_builder() -> Result<(), sqlx::Error> {
+        let pool = setup().await?;
+
+        let mut cursor: Option<PostCursor> = None;
+        let mut all = vec![];
+        loop {
+            let (rows, next) = Post::builder_select()
+                .org("acme")?
+                .order_by_score_asc()?
+                .order_by_id_desc()?
+                .find_after(cursor.as_ref(), 7, &pool)
+                .await?;
+            all.extend(ids(&rows));
+            match next {
+                Some(next) => cursor = Some(next),
+                None => break,
+            }
+        }
+        let expected = sqlx::query_scalar::<_, i32>("SELECT id FROM posts WHERE org = 'acme' ORDER BY score ASC, id DESC")
+            .fetch_all(&pool)
+            .await?;
+        assert_eq!(all, expected);
+
+        let (rows, next) = Post::builder_select()
+            .order_by_score_desc()?
+            .order_by_id_desc()?
+            .find_after(None, 100, &pool)
+            .await?;
+        assert_eq!(rows.len(), 25);
+        assert!(next.is_none());
+
+        // A cursor built for other fields can not be used
+        let cursor = PostCursor::from_row(&rows[0], &["id"]);
+        let res = Post::builder_select()
+            .order_by_score_asc()?
+            .find_after(Some(&cursor), 10, &pool)
+            .await;
+        assert!(matches!(res, Err(sqlx::Error::InvalidArgument(_))));
+        assert!(Post::builder_select().find_after(None, 10, &pool).await.is_err());
+        // Only the cursor fields can order a page
+        let res = Post::builder_select().order_by_created_at_desc()?.find_after(None, 10, &pool).await;
+        assert!(matches!(res, Err(sqlx::Error::InvalidArgument(_))));
+        Ok(())
+    }
+}
+
+pub mod page_request {
+    use sqlx_template::{sqlite_select, SqliteTemplate};
+    use sqlx::FromRow;
+    use sqlx_template_runtime::PageRequest;
+    use super::common;
+
+    #[derive(SqliteTemplate, FromRow, Debug, Clone)]
+    #[table("items")]
+    #[max_page_size = 5]
+    #[tp_select_page(by = "org")]
+    #[tp_select_page(order = "id asc", max_page_size = 2)]
+    #[tp_select_builder]
+    pub struct Item {
+        #[auto]
+        pub id: i32,
+        pub org: String,
+    }
+
+    #[derive(SqliteTemplate, FromRow, Debug, Clone)]
+    #[table("items")]
+    #[max_page_size = 5]
+    #[tp_select_page(by = "org", page_type = true)]
+    #[tp_select_page(order = "id asc", max_page_size = 2, page_type = true)]
+    #[tp_select_builder(page_type = true)]
+    pub struct PagedItem {
+        #[auto]
+        pub id: i32,
+        pub org: String,
+    }
+
+    #[sqlite_select("SELECT * FROM items WHERE org = :org ORDER BY id")]
+    pub async fn find_item_page(org: &str) -> Page<Item> {}
+
+    #[sqlite_select("SELECT * FROM items WHERE org = :org ORDER BY id")]
+    pub async fn find_item_page_request(org: &str) -> sqlx_template_runtime::Page<Item> {}
+
+    async fn setup() -> Result<sqlx::SqlitePool, sqlx::Error> {
+        let pool = common::sqlite(&["CREATE TABLE items (id INTEGER PRIMARY KEY AUTOINCREMENT, org TEXT NOT NULL)"]).await?;
+        for i in 0..12 {
+            let org = if i < 8 { "a" } else { "b" };
+            sqlx::query("INSERT INTO items (org) VALUES (?)")
+                .bind(org)
+                .execute(&pool)
+                .await?;
+        }
+        Ok(pool)
+    }
+
+    #[tokio::test]
+    async fn test_page_tuple() -> Result<(), sqlx::Error> {
+        let pool = setup().await?;
+
+        let (items, total) = Item::find_page_by_org("a", (0, 3, true), &pool).await?;
+        assert_eq!(items.len(), 3);
+        assert_eq!(total, Some(8));
+
+        // Clamped by the struct level max_page_size
+        let (items, total) = Item::find_page_all((0, 100, true), &pool).await?;
+        assert_eq!(items.len(), 5);
+        assert_eq!(total, Some(12));
+
+        // Clamped by the attribute max_page_size
+        let (items, total) = Item::find_page_order_by_id_asc((4, 100, false), &pool).await?;
+        assert_eq!(items.iter().map(|x| x.id).collect::<Vec<_>>(), vec![5, 6]);
+        assert_eq!(total, None);
+
+        let (items, _) = Item::builder_select().find_page((0, 100, false), &pool).await?;
+        assert_eq!(items.len(), 5);
+
+        let (items, total) = find_item_page("b", (1, 3, true), &pool).await?;
+        assert_eq!(items.iter().map(|x| x.id).collect::<Vec<_>>(), vec![10, 11, 12]);
+        assert_eq!(total, Some(4));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_page_request_functions() -> Result<(), sqlx::Error> {
+        let pool = setup().await?;
+
+        let page = PagedItem::find_page_by_org("a", PageRequest::page(2, 3), &pool).await?;
+        assert_eq!(page.items.iter().map(|x| x.id).collect::<Vec<_>>(), vec![7, 8]);
+        assert_eq!(page.total, Some(8));
+        assert_eq!(page.page, 2);
+        assert!(!page.has_next);
+
+        let page = PagedItem::find_page_by_org("a", PageRequest::page(1, 3), &pool).await?;
+        assert_eq!(page.items.len(), 3);
+        assert!(page.has_next);
+
+        // The tuple is still accepted, clamped by the struct level max_page_size
+        let page = PagedItem::find_page_by_org("a", (0, 100, true), &pool).await?;
+        assert_eq!(page.items.len(), 5);
+        assert_eq!(page.size, 5);
+        assert_eq!(page.total, Some(8));
+        assert!(page.has_next);
+
+        let page = PagedItem::find_page_order_by_id_asc(PageRequest::offset(10, 100).with_total(false), &pool).await?;
+        assert_eq!(page.items.iter().map(|x| x.id).collect::<Vec<_>>(), vec![11, 12]);
+        assert_eq!(page.total, None);
+        assert!(!page.has_next);
+
+        let page = PagedItem::builder_select()
+            .org("b")?
+            .find_page(PageRequest::page(0, 3), &pool)
+            .await?;
+        assert_eq!(page.items.len(), 3);
+        assert_eq!(page.total, Some(4));
+        assert!(page.has_next);
+
+        let page = find_item_page_request("b", PageRequest::page(1, 3), &pool).await?;
+        assert_eq!(page.items.iter().map(|x| x.id).collect::<Vec<_>>(), vec![12]);
+        assert_eq!(page.total, Some(4));
+        assert_eq!(page.page, 1);
+        assert!(!page.has_next);
+        Ok(())
+    }
+}
+
+pub mod projection {
+    use sqlx_template::{Columns, SqliteTemplate};
+    use sqlx::FromRow;
+    use super::common;
+
+    #[derive(SqliteTemplate, FromRow, Debug, Clone)]
+    #[table("users")]
+    #[tp_select_all(by = "org", into = "UserSummary")]
+    #[tp_select_all(order = "id desc", into = "UserSummary")]
+    #[tp_select_all(into = "UserSummary")]
+    #[tp_select_one(by = "id", into = "UserSummary", fn_name = "find_summary")]
+    #[tp_select_builder(find_all_as = true)]
+    pub struct User {
+        #[auto]
+        pub id: i32,
+        pub org: String,
+        pub email: String,
+        pub bio: String,
+        pub score: i32,
+    }
+
+    #[derive(Columns, FromRow, Debug, Clone, PartialEq)]
+    #[columns_trait]
+    pub struct UserSummary {
+        pub id: i32,
+        pub email: String,
+    }
+
+    async fn setup() -> Result<sqlx::SqlitePool, sqlx::Error> {
+        let pool = common::sqlite(&[r#"
+            CREATE TABLE users (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                org TEXT NOT NULL,
+                email TEXT NOT NULL,
+                bio TEXT NOT NULL,
+                score INTEGER NOT NULL
+            )
+            "#])
+        .await?;
+        for (org, email) in [("a", "x@a.com"), ("a", "y@a.com"), ("b", "z@b.com")] {
+            sqlx::query("INSERT INTO users (org, email, bio, score) VALUES (?, ?, 'bio', 1)")
+                .bind(org)
+                .bind(email)
+                .execute(&pool)
+                .await?;
+        }
+        Ok(pool)
+    }
+
+    fn summary(id: i32, email: &str) -> UserSummary {
+        UserSummary {
+            id,
+            email: email.to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_projection() -> Result<(), sqlx::Error> {
+        let pool = setup().await?;
+
+        assert_eq!(<UserSummary as sqlx_template_runtime::Columns>::COLUMNS, &["id", "email"]);
+
+        let users = User::find_by_org_as_user_summary("a", &pool).await?;
+        assert_eq!(users, vec![summary(1, "x@a.com"), summary(2, "y@a.com")]);
+
+        let users = User::find_order_by_id_desc_as_user_summary(&pool).await?;
+        assert_eq!(users.first(), Some(&summary(3, "z@b.com")));
+
+        let users = User::find_as_user_summary(&pool).await?;
+        assert_eq!(users.len(), 3);
+
+        let user = User::find_summary(&2, &pool).await?;
+        assert_eq!(user, Some(summary(2, "y@a.com")));
+        assert_eq!(User::find_summary(&9, &pool).await?, None);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_builder_projection() -> Result<(), sqlx::Error> {
+        let pool = setup().await?;
+
+        let users = User::builder_select()
+            .org("a")?
+            .order_by_id_desc()?
+            .find_all_as::<UserSummary>(&pool)
+            .await?;
+        assert_eq!(users, vec![summary(2, "y@a.com"), summary(1, "x@a.com")]);
+        Ok(())
+    }
+
+}
+
+pub mod in_list {
+    use sqlx_template::{PostgresTemplate, SqliteTemplate};
+    use sqlx::FromRow;
+    use super::common;
+
+    #[derive(SqliteTemplate, FromRow, Debug, Clone)]
+    #[table("tasks")]
+    #[tp_select_all(by = "id in")]
+    #[tp_select_all(by = "id in", keep_order = true, fn_name = "load_by_ids")]
+    #[tp_select_all(by = "org, status in", order = "id desc")]
+    #[tp_select_all(by = "org in, status in")]
+    pub struct Task {
+        #[auto]
+        pub id: i32,
+        pub org: String,
+        pub status: String,
+    }
+
+    #[derive(PostgresTemplate, FromRow, Debug, Clone)]
+    #[table("tasks")]
+    #[tp_select_all(by = "id in", keep_order = true)]
+    #[tp_select_all(by = "org, status in", order = "id desc")]
+    pub struct TaskPg {
+        pub id: i32,
+        pub org: String,
+        pub status: String,
+    }
+
+    async fn setup() -> Result<sqlx::SqlitePool, sqlx::Error> {
+        let pool = common::sqlite(&["CREATE TABLE tasks (id INTEGER PRIMARY KEY AUTOINCREMENT, org TEXT NOT NULL, status TEXT NOT NULL)"])
+            .await?;
+        for (org, status) in [("a", "open"), ("a", "done"), ("a", "open"), ("b", "open"), ("b", "late")] {
+            sqlx::query("INSERT INTO tasks (org, status) VALUES (?, ?)")
+                .bind(org)
+                .bind(status)
+                .execute(&pool)
+                .await?;
+        }
+        Ok(pool)
+    }
+
+    fn ids(tasks: &[Task]) -> Vec<i32> {
+        tasks.iter().map(|x| x.id).collect()
+    }
+
+    #[tokio::test]
+    async fn test_in_list() -> Result<(), sqlx::Error> {
+        let pool = setup().await?;
+
+        assert!(Task::find_by_id_in(&[], &pool).await?.is_empty());
+
+        let mut tasks = Task::find_by_id_in(&[4, 1, 9, 2], &pool).await?;
+        tasks.sort_by_key(|x| x.id);
+        assert_eq!(ids(&tasks), vec![1, 2, 4]);
+
+        let tasks = Task::load_by_ids(&[4, 1, 9, 2], &pool).await?;
+        assert_eq!(ids(&tasks), vec![4, 1, 2]);
+
+        let statuses = vec!["open".to_string(), "late".to_string()];
+        let tasks = Task::find_by_org_and_status_in_order_by_id_desc("a", &statuses, &pool).await?;
+        assert_eq!(ids(&tasks), vec![3, 1]);
+        assert!(Task::find_by_org_and_status_in_order_by_id_desc("a", &[], &pool).await?.is_empty());
+
+        let orgs = vec!["b".to_string()];
+        let mut tasks = Task::find_by_org_in_and_status_in(&orgs, &statuses, &pool).await?;
+        tasks.sort_by_key(|x| x.id);
+        assert_eq!(ids(&tasks), vec![4, 5]);
+        assert!(Task::find_by_org_in_and_status_in(&[], &statuses, &pool).await?.is_empty());
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[ignore]
+    async fn test_in_list_pg() -> Result<(), sqlx::Error> {
+        let pool = common::postgres(&[
+            "CREATE TEMP TABLE tasks (id INT PRIMARY KEY, org TEXT NOT NULL, status TEXT NOT NULL)",
+            "INSERT INTO tasks VALUES (1, 'a', 'open'), (2, 'a', 'done'), (3, 'a', 'open'), (4, 'b', 'open'), (5, 'b', 'late')",
+        ])
+        .await?;
+
+        let tasks = TaskPg::find_by_id_in(&[4, 1, 9, 2], &pool).await?;
+        assert_eq!(tasks.iter().map(|x| x.id).collect::<Vec<_>>(), vec![4, 1, 2]);
+        assert!(TaskPg::find_by_id_in(&[], &pool).await?.is_empty());
+
+        let statuses = vec!["open".to_string(), "late".to_string()];
+        let tasks = TaskPg::find_by_org_and_status_in_order_by_id_desc("a", &statuses, &pool).await?;
+        assert_eq!(tasks.iter().map(|x| x.id).collect::<Vec<_>>(), vec![3, 1]);
+        Ok(())
+    }
+}
+
+pub mod row_lock {
+    use sqlx_template::PostgresTemplate;
+    use sqlx::{FromRow, PgPool};
+    use super::common;
+
+    // SQLite does not support row locking
+    #[derive(PostgresTemplate, FromRow, Debug, Clone)]
+    #[table("jobs")]
+    #[tp_select_all(by = "status", order = "id asc", lock = "update", skip_locked = true)]
+    #[tp_select_one(by = "id", lock = "no_key_update", nowait = true)]
+    #[tp_select_stream(by = "status", lock = "update")]
+    #[tp_select_all(by = "id in", lock = "update")]
+    #[tp_select_all(lock = "update", skip_locked = true)]
+    #[tp_select_builder]
+    pub struct Job {
+        pub id: i32,
+        pub status: String,
+    }
+
+    #[test]
+    fn test_builder_lock_sql() -> Result<(), sqlx::Error> {
+        let sql = Job::builder_select()
+            .status("queued")?
+            .order_by_id_asc()?
+            .for_update()?
+            .skip_locked()?
+            .build_sql();
+        assert_eq!(
+            sql,
+            "SELECT id, status FROM jobs WHERE status = $1 ORDER BY id ASC FOR UPDATE SKIP LOCKED"
+        );
+
+        let sql = Job::builder_select().for_no_key_update()?.nowait()?.build_sql();
+        assert_eq!(sql, "SELECT id, status FROM jobs FOR NO KEY UPDATE NOWAIT");
+
+        assert!(Job::builder_select().skip_locked().is_err());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_builder_page_rejects_lock() -> Result<(), sqlx::Error> {
+        // Rejected before connecting
+        let pool = PgPool::connect_lazy("postgres://localhost/unused")?;
+        let res = Job::builder_select().for_update()?.find_page((0, 10, true), &pool).await;
+        assert!(matches!(res, Err(sqlx::Error::InvalidArgument(_))));
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[ignore]
+    async fn test_skip_locked() -> Result<(), sqlx::Error> {
+        let pool = common::postgres_sessions(&[
+            "DROP TABLE IF EXISTS jobs",
+            "CREATE TABLE jobs (id INT PRIMARY KEY, status TEXT NOT NULL)",
+            "INSERT INTO jobs VALUES (1, 'queued'), (2, 'queued'), (3, 'done')",
+        ])
+        .await?;
+
+        // The first worker locks the first queued job in its transaction
+        let mut tx = pool.begin().await?;
+        let locked = Job::builder_select()
+            .status("queued")?
+            .order_by_id_asc()?
+            .limit(1)?
+            .for_update()?
+            .skip_locked()?
+            .find_all(&mut *tx)
+            .await?;
+        assert_eq!(locked.iter().map(|x| x.id).collect::<Vec<_>>(), vec![1]);
+
+        // Another worker skips it while the transaction holds the lock
+        let mut other = pool.begin().await?;
+        let jobs = Job::find_by_status_order_by_id_asc_for_update_skip_locked("queued", &mut *other).await?;
+        assert_eq!(jobs.iter().map(|x| x.id).collect::<Vec<_>>(), vec![2]);
+        other.rollback().await?;
+
+        let res = Job::find_one_by_id_for_no_key_update_nowait(&1, &pool).await;
+        assert!(res.is_err());
+
+        tx.rollback().await?;
+        let jobs = Job::find_by_status_order_by_id_asc_for_update_skip_locked("queued", &pool).await?;
+        assert_eq!(jobs.len(), 2);
+        sqlx::query("DROP TABLE jobs").execute(&pool).await?;
+        Ok(())
+    }
+}
+
+pub mod select_exists {
+    use sqlx_template::SqliteTemplate;
+    use sqlx::FromRow;
+    use super::common;
+
+    #[derive(SqliteTemplate, FromRow, Debug, Clone)]
+    #[table("accounts")]
+    #[tp_select_exists(by = "email")]
+    #[tp_select_exists(by = "org, active", fn_name = "has_active_account")]
+    #[tp_select_exists]
+    #[tp_select_builder]
+    pub struct Account {
+        #[auto]
+        pub id: i32,
+        pub org: String,
+        pub email: String,
+        pub active: bool,
+    }
+
+    #[tokio::test]
+    async fn test_select_exists() -> Result<(), sqlx::Error> {
+        let pool = common::sqlite(&[
+            "CREATE TABLE accounts (id INTEGER PRIMARY KEY AUTOINCREMENT, org TEXT NOT NULL, email TEXT NOT NULL, active BOOLEAN NOT NULL)",
+        ])
+        .await?;
+        assert!(!Account::exists_all(&pool).await?);
+        sqlx::query("INSERT INTO accounts (org, email, active) VALUES ('a', 'x@a.com', 1), ('b', 'y@b.com', 0)")
+            .execute(&pool)
+            .await?;
+
+        assert!(Account::exists_all(&pool).await?);
+        assert!(Account::exists_by_email("x@a.com", &pool).await?);
+        assert!(!Account::exists_by_email("z@a.com", &pool).await?);
+        assert!(Account::has_active_account(&true, "a", &pool).await?);
+        assert!(!Account::has_active_account(&true, "b", &pool).await?);
+
+        assert!(Account::builder_select().org("b")?.exists(&pool).await?);
+        assert!(!Account::builder_select().org("b")?.active(&true)?.exists(&pool).await?);
+        assert!(Account::builder_select().exists(&pool).await?);
+        Ok(())
+    }
+
+}
+
+pub mod select_agg {
+    use sqlx_template::{PostgresTemplate, SqliteTemplate};
+    use sqlx::FromRow;
+    use super::common;
+
+    #[derive(SqliteTemplate, FromRow, Debug, Clone)]
+    #[table("orders")]
+    #[tp_select_agg(func = "sum", column = "amount", by = "org")]
+    #[tp_select_agg(func = "avg", column = "amount")]
+    #[tp_select_agg(func = "max", column = "created_at", by = "org")]
+    #[tp_select_agg(func = "min", column = "note", fn_name = "first_note")]
+    #[tp_select_builder]
+    pub struct Order {
+        #[auto]
+        pub id: i32,
+        pub org: String,
+        pub amount: i32,
+        pub price: f64,
+        pub note: Option<String>,
+        pub created_at: i64,
+    }
+
+    #[derive(PostgresTemplate, FromRow, Debug, Clone)]
+    #[table("orders")]
+    #[tp_select_agg(func = "sum", column = "amount", by = "org")]
+    #[tp_select_agg(func = "avg", column = "price")]
+    #[tp_select_builder]
+    pub struct OrderPg {
+        pub id: i32,
+        pub org: String,
+        pub amount: i64,
+        pub price: f32,
+    }
+
+    #[tokio::test]
+    async fn test_select_agg() -> Result<(), sqlx::Error> {
+        let pool = common::sqlite(&[
+            r#"
+            CREATE TABLE orders (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                org TEXT NOT NULL,
+                amount INTEGER NOT NULL,
+                price REAL NOT NULL,
+                note TEXT,
+                created_at INTEGER NOT NULL
+            )
+            "#,
+            "INSERT INTO orders (org, amount, price, note, created_at) VALUES ('a', 10, 1.5, 'b', 100), ('a', 20, 2.5, NULL, 300), ('b', 3, 4.0, 'a', 200)",
+        ])
+        .await?;
+
+        let sum: Option<i64> = Order::sum_amount_by_org("a", &pool).await?;
+        assert_eq!(sum, Some(30));
+        assert_eq!(Order::sum_amount_by_org("c", &pool).await?, None);
+        assert_eq!(Order::avg_amount(&pool).await?, Some(11.0));
+        assert_eq!(Order::max_created_at_by_org("a", &pool).await?, Some(300));
+        assert_eq!(Order::first_note(&pool).await?, Some("a".to_string()));
+
+        assert_eq!(Order::builder_select().org("a")?.sum_amount(&pool).await?, Some(30));
+        assert_eq!(Order::builder_select().org("a")?.avg_price(&pool).await?, Some(2.0));
+        assert_eq!(Order::builder_select().max_created_at(&pool).await?, Some(300));
+        assert_eq!(Order::builder_select().org("b")?.min_id(&pool).await?, Some(3));
+        assert_eq!(Order::builder_select().org("c")?.sum_price(&pool).await?, None);
+        Ok(())
+    }
+
+    // PostgreSQL sums integers into NUMERIC and averages into DOUBLE PRECISION, which are cast back to the field types
+    #[tokio::test]
+    #[ignore]
+    async fn test_select_agg_pg() -> Result<(), sqlx::Error> {
+        let pool = common::postgres(&[
+            "CREATE TEMP TABLE orders (id INT PRIMARY KEY, org TEXT NOT NULL, amount BIGINT NOT NULL, price REAL NOT NULL)",
+            "INSERT INTO orders VALUES (1, 'a', 10, 1.5), (2, 'a', 20, 2.5), (3, 'b', 3, 4.0)",
+        ])
+        .await?;
+        assert_eq!(OrderPg::sum_amount_by_org("a", &pool).await?, Some(30));
+        assert_eq!(OrderPg::sum_amount_by_org("c", &pool).await?, None);
+        assert_eq!(OrderPg::avg_price(&pool).await?, Some(8.0 / 3.0));
+        assert_eq!(OrderPg::builder_select().org("a")?.sum_amount(&pool).await?, Some(30));
+        assert_eq!(OrderPg::builder_select().org("a")?.avg_price(&pool).await?, Some(2.0));
+        assert_eq!(OrderPg::builder_select().max_price(&pool).await?, Some(4.0));
+        Ok(())
+    }
+}
+
+pub mod count_group_by {
+    use sqlx_template::SqliteTemplate;
+    use sqlx::FromRow;
+    use super::common;
+
+    #[derive(SqliteTemplate, FromRow, Debug, Clone)]
+    #[table("tickets")]
+    #[tp_select_count(by = "org", group_by = "status")]
+    #[tp_select_count(group_by = "org, status", fn_name = "count_per_org_status")]
+    #[tp_select_builder]
+    pub struct Ticket {
+        #[auto]
+        pub id: i32,
+        pub org: String,
+        pub status: String,
+        pub assignee: Option<String>,
+    }
+
+    #[tokio::test]
+    async fn test_count_group_by() -> Result<(), sqlx::Error> {
+        let pool = common::sqlite(&[
+            "CREATE TABLE tickets (id INTEGER PRIMARY KEY AUTOINCREMENT, org TEXT NOT NULL, status TEXT NOT NULL, assignee TEXT)",
+            "INSERT INTO tickets (org, status, assignee) VALUES ('a', 'open', 'x'), ('a', 'done', NULL), ('a', 'open', NULL), ('b', 'open', 'y')",
+        ])
+        .await?;
+
+        let counts: Vec<(String, i64)> = Ticket::count_by_org_group_by_status("a", &pool).await?;
+        assert_eq!(counts, vec![("done".to_string(), 1), ("open".to_string(), 2)]);
+        assert!(Ticket::count_by_org_group_by_status("c", &pool).await?.is_empty());
+
+        let counts = Ticket::count_per_org_status(&pool).await?;
+        assert_eq!(counts.len(), 3);
+        assert_eq!(counts[2], ("b".to_string(), "open".to_string(), 1));
+
+        let counts = Ticket::builder_select().status("open")?.count_group_by_org(&pool).await?;
+        assert_eq!(counts, vec![("a".to_string(), 2), ("b".to_string(), 1)]);
+        let counts = Ticket::builder_select().org("a")?.count_group_by_assignee(&pool).await?;
+        assert_eq!(counts, vec![(None, 2), (Some("x".to_string()), 1)]);
+        Ok(())
+    }
+
+}
+
+pub mod by_operators {
+    use sqlx_template::SqliteTemplate;
+    use sqlx::FromRow;
+    use super::common;
+
+    #[derive(SqliteTemplate, FromRow, Debug, Clone)]
+    #[table("events")]
+    #[tp_select_all(by = "org, created_at >=, score <", order = "id")]
+    #[tp_select_all(by = "title like")]
+    #[tp_select_all(by = "title ilike", fn_name = "search_title")]
+    #[tp_select_count(by = "org !=")]
+    #[tp_select_one(by = "score>=, org", order = "score desc")]
+    #[tp_update(by = "created_at <", on = "org")]
+    #[tp_delete(by = "org, score <=")]
+    #[tp_select_agg(func = "max", column = "score", by = "org, created_at >=")]
+    #[tp_select_cursor(by = "title like", order = "id")]
+    pub struct Event {
+        #[auto]
+        pub id: i32,
+        pub org: String,
+        pub title: String,
+        pub score: Option<i32>,
+        pub created_at: i64,
+    }
+
+    fn ids(events: &[Event]) -> Vec<i32> {
+        events.iter().map(|x| x.id).collect()
+    }
+
+    #[tokio::test]
+    async fn test_by_operators() -> Result<(), sqlx::Error> {
+        let pool = common::sqlite(&[
+            "CREATE TABLE events (id INTEGER PRIMARY KEY AUTOINCREMENT, org TEXT NOT NULL, title TEXT NOT NULL, score INTEGER, created_at INTEGER NOT NULL)",
+            "INSERT INTO events (org, title, score, created_at) VALUES ('a', 'Deploy', 1, 100), ('a', 'deploy again', 5, 200), ('a', 'Review', 2, 300), ('b', 'Release', NULL, 400)",
+        ])
+        .await?;
+
+        let events = Event::find_by_created_at_gte_and_org_and_score_lt_order_by_id_asc(&200, "a", &3, &pool).await?;
+        assert_eq!(ids(&events), vec![3]);
+
+        assert_eq!(ids(&Event::find_by_title_like("%eploy%", &pool).await?), vec![1, 2]);
+        assert_eq!(ids(&Event::search_title("DEPLOY%", &pool).await?), vec![1, 2]);
+        assert_eq!(Event::count_by_org_ne("a", &pool).await?, 1);
+        assert_eq!(Event::max_score_by_created_at_gte_and_org(&200, "a", &pool).await?, Some(5));
+
+        let (events, cursor) = Event::find_after_by_title_like_order_by_id_asc("%e%", None, 2, &pool).await?;
+        assert_eq!(ids(&events), vec![1, 2]);
+        let (events, _) = Event::find_after_by_title_like_order_by_id_asc("%e%", cursor.as_ref(), 2, &pool).await?;
+        assert_eq!(ids(&events), vec![3, 4]);
+
+        let event = Event::find_one_by_org_and_score_gte_order_by_score_desc("a", &2, &pool).await?;
+        assert_eq!(event.map(|x| x.id), Some(2));
+
+        assert_eq!(Event::update_by_created_at_lt_on_org(&250, "c", &pool).await?, 2);
+        assert_eq!(Event::count_by_org_ne("c", &pool).await?, 2);
+
+        assert_eq!(Event::delete_by_org_and_score_lte("c", &1, &pool).await?, 1);
+        assert_eq!(Event::count_by_org_ne("x", &pool).await?, 3);
+        Ok(())
+    }
+
+}
+
+pub mod optional_by {
+    use sqlx_template::SqliteTemplate;
+    use sqlx::FromRow;
+    use super::common;
+
+    #[derive(SqliteTemplate, FromRow, Debug, Clone)]
+    #[table("products")]
+    #[tp_select_all(by = "org, status?, price >=?", order = "id", fn_name = "search")]
+    #[tp_select_all(by = "id in, status?")]
+    #[tp_select_count(by = "name like?")]
+    #[tp_select_one(by = "org, status?", order = "id desc")]
+    #[tp_select_agg(func = "sum", column = "price", by = "org, status?")]
+    #[tp_select_cursor(by = "status?", order = "id")]
+    pub struct Product {
+        #[auto]
+        pub id: i32,
+        pub org: String,
+        pub name: String,
+        pub status: Option<String>,
+        pub price: i32,
+    }
+
+    fn ids(products: &[Product]) -> Vec<i32> {
+        products.iter().map(|x| x.id).collect()
+    }
+
+    #[tokio::test]
+    async fn test_optional_by() -> Result<(), sqlx::Error> {
+        let pool = common::sqlite(&[
+            "CREATE TABLE products (id INTEGER PRIMARY KEY AUTOINCREMENT, org TEXT NOT NULL, name TEXT NOT NULL, status TEXT, price INTEGER NOT NULL)",
+            "INSERT INTO products (org, name, status, price) VALUES ('a', 'pen', 'active', 2), ('a', 'book', 'archived', 10), ('a', 'bag', NULL, 30), ('b', 'cup', 'active', 5)",
+        ])
+        .await?;
+
+        assert_eq!(ids(&Product::search("a", None, None, &pool).await?), vec![1, 2, 3]);
+        assert_eq!(ids(&Product::search("a", None, Some("active"), &pool).await?), vec![1]);
+        assert_eq!(ids(&Product::search("a", Some(&5), None, &pool).await?), vec![2, 3]);
+        assert_eq!(ids(&Product::search("a", Some(&5), Some("archived"), &pool).await?), vec![2]);
+
+        let mut products = Product::find_by_id_in_and_status(&[1, 2, 4], Some("active"), &pool).await?;
+        products.sort_by_key(|x| x.id);
+        assert_eq!(ids(&products), vec![1, 4]);
+        assert_eq!(Product::find_by_id_in_and_status(&[1, 2, 4], None, &pool).await?.len(), 3);
+
+        assert_eq!(Product::count_by_name_like(None, &pool).await?, 4);
+        assert_eq!(Product::count_by_name_like(Some("b%"), &pool).await?, 2);
+
+        let product = Product::find_one_by_org_and_status_order_by_id_desc("a", None, &pool).await?;
+        assert_eq!(product.map(|x| x.id), Some(3));
+        let product = Product::find_one_by_org_and_status_order_by_id_desc("a", Some("active"), &pool).await?;
+        assert_eq!(product.map(|x| x.id), Some(1));
+
+        assert_eq!(Product::sum_price_by_org_and_status("a", None, &pool).await?, Some(42));
+        assert_eq!(Product::sum_price_by_org_and_status("a", Some("active"), &pool).await?, Some(2));
+
+        let (products, cursor) = Product::find_after_by_status_order_by_id_asc(None, None, 3, &pool).await?;
+        assert_eq!(ids(&products), vec![1, 2, 3]);
+        let (products, _) = Product::find_after_by_status_order_by_id_asc(None, cursor.as_ref(), 3, &pool).await?;
+        assert_eq!(ids(&products), vec![4]);
+        let (products, _) = Product::find_after_by_status_order_by_id_asc(Some("active"), None, 3, &pool).await?;
+        assert_eq!(ids(&products), vec![1, 4]);
+        Ok(())
+    }
+
+}
+
+pub mod soft_delete {
+    use sqlx_template::{PostgresTemplate, SqliteTemplate};
+    use sqlx::FromRow;
+    use super::common;
+
+    #[derive(SqliteTemplate, FromRow, Debug, Clone)]
+    #[table("notes")]
+    #[tp_soft_delete(column = "deleted_at")]
+    #[tp_select_all(by = "org", order = "id")]
+    #[tp_select_all(by = "org", order = "id", with_deleted = true)]
+    #[tp_select_all(by = "org", order = "id", only_deleted = true)]
+    #[tp_select_one(by = "id")]
+    #[tp_select_count(by = "org")]
+    #[tp_update(by = "id")]
+    #[tp_delete(by = "id")]
+    #[tp_select_builder]
+    #[tp_update_builder]
+    #[tp_delete_builder]
+    pub struct Note {
+        #[auto]
+        pub id: i32,
+        pub org: String,
+        pub body: String,
+        pub deleted_at: Option<String>,
+    }
+
+    #[derive(PostgresTemplate, FromRow, Debug, Clone)]
+    #[table("notes")]
+    #[tp_soft_delete(column = "deleted_at")]
+    #[tp_select_builder(not_deleted = "deleted_at IS NULL")]
+    #[tp_delete_builder]
+    pub struct NotePg {
+        pub id: i32,
+        pub org: String,
+        pub deleted_at: Option<chrono::NaiveDateTime>,
+    }
+
+    fn ids(notes: &[Note]) -> Vec<i32> {
+        notes.iter().map(|x| x.id).collect()
+    }
+
+    async fn setup() -> Result<sqlx::SqlitePool, sqlx::Error> {
+        common::sqlite(&[
+            "CREATE TABLE notes (id INTEGER PRIMARY KEY AUTOINCREMENT, org TEXT NOT NULL, body TEXT NOT NULL, deleted_at TEXT)",
+            "INSERT INTO notes (org, body, deleted_at) VALUES ('a', 'one', NULL), ('a', 'two', '2024-01-01 00:00:00'), ('a', 'three', NULL), ('b', 'four', NULL)",
+        ])
+        .await
+    }
+
+    #[tokio::test]
+    async fn test_soft_delete() -> Result<(), sqlx::Error> {
+        let pool = setup().await?;
+
+        assert_eq!(ids(&Note::find_by_org_order_by_id_asc("a", &pool).await?), vec![1, 3]);
+        assert_eq!(ids(&Note::find_by_org_order_by_id_asc_with_deleted("a", &pool).await?), vec![1, 2, 3]);
+        assert_eq!(ids(&Note::find_by_org_order_by_id_asc_only_deleted("a", &pool).await?), vec![2]);
+        assert!(Note::find_one_by_id(&2, &pool).await?.is_none());
+        assert_eq!(Note::count_by_org("a", &pool).await?, 2);
+        assert_eq!(Note::find_all(&pool).await?.len(), 3);
+        assert_eq!(Note::count_all(&pool).await?, 3);
+
+        // Deleted rows are not updated
+        let mut note = Note::find_one_by_id(&1, &pool).await?.unwrap();
+        note.body = "edited".to_string();
+        assert_eq!(Note::update_by_id(&1, &note, &pool).await?, 1);
+        assert_eq!(Note::update_by_id(&2, &note, &pool).await?, 0);
+
+        assert_eq!(Note::delete_by_id(&1, &pool).await?, 1);
+        assert_eq!(Note::delete_by_id(&1, &pool).await?, 0);
+        assert_eq!(ids(&Note::find_by_org_order_by_id_asc("a", &pool).await?), vec![3]);
+        assert_eq!(Note::restore_by_id(&1, &pool).await?, 1);
+        assert_eq!(ids(&Note::find_by_org_order_by_id_asc("a", &pool).await?), vec![1, 3]);
+        assert_eq!(Note::hard_delete_by_id(&2, &pool).await?, 1);
+        assert_eq!(ids(&Note::find_by_org_order_by_id_asc_with_deleted("a", &pool).await?), vec![1, 3]);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_soft_delete_builders() -> Result<(), sqlx::Error> {
+        let pool = setup().await?;
+
+        assert_eq!(Note::builder_select().org("a")?.count(&pool).await?, 2);
+        assert_eq!(Note::builder_select().org("a")?.with_deleted()?.count(&pool).await?, 3);
+        let notes = Note::builder_select().only_deleted()?.find_all(&pool).await?;
+        assert_eq!(ids(&notes), vec![2]);
+
+        assert_eq!(Note::builder_update().on_body("x")?.by_org("a")?.execute(&pool).await?, 2);
+        assert_eq!(Note::builder_update().on_body("x")?.by_org("a")?.with_deleted()?.execute(&pool).await?, 3);
+
+        assert_eq!(Note::builder_delete().org("a")?.execute(&pool).await?, 2);
+        assert_eq!(Note::builder_select().org("a")?.count(&pool).await?, 0);
+        assert_eq!(Note::builder_select().org("a")?.only_deleted()?.count(&pool).await?, 3);
+        assert_eq!(Note::builder_delete().org("a")?.hard_delete()?.execute(&pool).await?, 3);
+        assert_eq!(Note::builder_select().with_deleted()?.count(&pool).await?, 1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_soft_delete_builder_sql() -> Result<(), sqlx::Error> {
+        let sql = NotePg::builder_delete().org("a")?.build_sql();
+        assert_eq!(sql, "UPDATE notes SET deleted_at = CURRENT_TIMESTAMP WHERE deleted_at IS NULL AND org = $1");
+
+        let sql = NotePg::builder_delete().org("a")?.hard_delete()?.build_sql();
+        assert_eq!(sql, "DELETE FROM notes WHERE org = $1");
+
+        let sql = NotePg::builder_select().only_deleted()?.build_sql();
+        assert_eq!(sql, "SELECT id, org, deleted_at FROM notes WHERE deleted_at IS NOT NULL");
+
+        // The scope is kept apart from the conditions of the calls, even the same condition
+        let sql = NotePg::builder_select().not_deleted()?.with_deleted()?.build_sql();
+        assert_eq!(sql, "SELECT id, org, deleted_at FROM notes WHERE deleted_at IS NULL");
+        Ok(())
+    }
+
+}
+
+pub mod tenant {
+    use sqlx_template::{PostgresTemplate, SqliteTemplate};
+    use sqlx::FromRow;
+    use super::common;
+
+    #[derive(sqlx::Type, Debug, Clone, Copy, PartialEq)]
+    #[sqlx(transparent)]
+    pub struct TenantId(i64);
+
+    #[derive(SqliteTemplate, FromRow, Debug, Clone)]
+    #[table("docs")]
+    #[tp_tenant(column = "tenant_id")]
+    #[tp_select_all(by = "org", order = "id")]
+    #[tp_select_all(by = "org", unscoped = true)]
+    #[tp_select_all(unscoped = true)]
+    #[tp_select_one(by = "id")]
+    #[tp_select_count(by = "org")]
+    #[tp_select_count(unscoped = true)]
+    #[tp_update(by = "id")]
+    #[tp_update(by = "id", on = "title")]
+    #[tp_delete(by = "id")]
+    #[tp_upsert(by = "id")]
+    #[tp_select_builder]
+    #[tp_insert_builder]
+    #[tp_update_builder]
+    #[tp_delete_builder]
+    pub struct Doc {
+        pub id: i32,
+        pub tenant_id: TenantId,
+        pub org: String,
+        pub title: String,
+    }
+
+    #[derive(PostgresTemplate, FromRow, Debug, Clone)]
+    #[table("docs")]
+    #[tp_tenant(column = "tenant_id")]
+    #[tp_select_builder]
+    #[tp_insert_builder]
+    #[tp_update_builder]
+    pub struct DocPg {
+        pub id: i32,
+        pub tenant_id: String,
+        pub org: String,
+        pub title: String,
+    }
+
+    const T1: TenantId = TenantId(1);
+    const T2: TenantId = TenantId(2);
+
+    fn ids(docs: &[Doc]) -> Vec<i32> {
+        docs.iter().map(|x| x.id).collect()
+    }
+
+    async fn setup() -> Result<sqlx::SqlitePool, sqlx::Error> {
+        common::sqlite(&[
+            "CREATE TABLE docs (id INTEGER PRIMARY KEY, tenant_id INTEGER NOT NULL, org TEXT NOT NULL, title TEXT NOT NULL)",
+            "INSERT INTO docs VALUES (1, 1, 'a', 'one'), (2, 1, 'a', 'two'), (3, 2, 'a', 'three'), (4, 2, 'b', 'four')",
+        ])
+        .await
+    }
+
+    #[tokio::test]
+    async fn test_tenant() -> Result<(), sqlx::Error> {
+        let pool = setup().await?;
+
+        assert_eq!(ids(&Doc::find_by_org_order_by_id_asc(&T1, "a", &pool).await?), vec![1, 2]);
+        assert_eq!(ids(&Doc::find_by_org_order_by_id_asc(&T2, "a", &pool).await?), vec![3]);
+        assert_eq!(Doc::unscoped_find_by_org("a", &pool).await?.len(), 3);
+        assert!(Doc::find_one_by_id(&T2, &1, &pool).await?.is_none());
+        assert_eq!(Doc::count_by_org(&T2, "b", &pool).await?, 1);
+        assert_eq!(Doc::find_all(&T2, &pool).await?.len(), 2);
+        assert_eq!(Doc::count_all(&T1, &pool).await?, 2);
+        assert_eq!(Doc::unscoped_find_all(&pool).await?.len(), 4);
+        assert_eq!(Doc::unscoped_count_all(&pool).await?, 4);
+
+        // Rows of another tenant are neither updated nor deleted
+        let mut doc = Doc::find_one_by_id(&T1, &1, &pool).await?.unwrap();
+        doc.title = "edited".to_string();
+        doc.tenant_id = T2;
+        assert_eq!(Doc::update_by_id(&T2, &1, &doc, &pool).await?, 0);
+        assert_eq!(Doc::update_by_id(&T1, &1, &doc, &pool).await?, 1);
+        assert_eq!(Doc::update_by_id_on_title(&T2, &2, "x", &pool).await?, 0);
+        assert_eq!(Doc::delete_by_id(&T2, &1, &pool).await?, 0);
+        let doc = Doc::find_one_by_id(&T1, &1, &pool).await?.unwrap();
+        assert_eq!((doc.tenant_id, doc.title.as_str()), (T1, "edited"));
+
+        let mut doc = doc;
+        doc.title = "stolen".to_string();
+        assert_eq!(Doc::upsert_by_id(&T2, &doc, &pool).await?, 0);
+        doc.title = "upserted".to_string();
+        assert_eq!(Doc::upsert_by_id(&T1, &doc, &pool).await?, 1);
+        doc.id = 5;
+        assert_eq!(Doc::upsert_by_id(&T2, &doc, &pool).await?, 1);
+        assert_eq!(Doc::find_one_by_id(&T1, &1, &pool).await?.unwrap().title, "upserted");
+        assert_eq!(Doc::find_one_by_id(&T2, &5, &pool).await?.unwrap().tenant_id, T2);
+
+        assert_eq!(Doc::delete_by_id(&T1, &1, &pool).await?, 1);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_tenant_builders() -> Result<(), sqlx::Error> {
+        let pool = setup().await?;
+
+        assert_eq!(Doc::builder_select(&T1)?.org("a")?.count(&pool).await?, 2);
+        assert_eq!(Doc::unscoped_builder_select().org("a")?.count(&pool).await?, 3);
+        assert_eq!(Doc::builder_update(&T2)?.on_title("x")?.by_id(&1)?.execute(&pool).await?, 0);
+        assert_eq!(Doc::builder_update(&T2)?.on_title("x")?.by_org("a")?.execute(&pool).await?, 1);
+        assert_eq!(Doc::builder_delete(&T1)?.id(&3)?.execute(&pool).await?, 0);
+        assert_eq!(Doc::builder_delete(&T1)?.execute(&pool).await?, 2);
+        assert_eq!(Doc::unscoped_builder_select().count(&pool).await?, 2);
+
+        // The inserted row belongs to the tenant of the builder, which has no on_tenant_id
+        assert_eq!(Doc::builder_insert(&T2)?.on_id(&9)?.on_org("c")?.on_title("new")?.execute(&pool).await?, 1);
+        assert_eq!(Doc::find_one_by_id(&T2, &9, &pool).await?.unwrap().tenant_id, T2);
+        assert!(Doc::unscoped_builder_insert().on_id(&10)?.on_org("c")?.on_title("new")?.execute(&pool).await.is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_tenant_builder_sql() -> Result<(), sqlx::Error> {
+        // The tenant is bound after the conditions of the calls, except in the select builder
+        let sql = DocPg::builder_select("t1")?.org("a")?.build_sql();
+        assert_eq!(sql, "SELECT id, tenant_id, org, title FROM docs WHERE tenant_id = $1 AND org = $2");
+        let sql = DocPg::builder_update("t1")?.on_title("x")?.by_org("a")?.build_sql();
+        assert_eq!(sql, "UPDATE docs SET title = $1 WHERE org = $2 AND tenant_id = $3");
+        let sql = DocPg::builder_insert("t1")?.on_id(&1)?.on_title("x")?.build_sql();
+        assert_eq!(sql, "INSERT INTO docs(id, tenant_id, title) VALUES ($1, $2, $3)");
+        Ok(())
+    }
+
+}
+
+pub mod primary_key {
+    use sqlx_template::SqliteTemplate;
+    use sqlx::FromRow;
+    use super::common;
+
+    #[derive(SqliteTemplate, FromRow, Debug, Clone, PartialEq)]
+    #[table("users")]
+    pub struct User {
+        #[primary_key]
+        pub id: i32,
+        pub email: String,
+        pub name: String,
+    }
+
+    #[derive(SqliteTemplate, FromRow, Debug, Clone, PartialEq)]
+    #[table("members")]
+    pub struct Member {
+        #[primary_key]
+        pub user_id: i32,
+        #[primary_key]
+        pub org: String,
+        pub role: String,
+    }
+
+    async fn setup() -> Result<sqlx::SqlitePool, sqlx::Error> {
+        common::sqlite(&[
+            "CREATE TABLE users (id INTEGER PRIMARY KEY, email TEXT NOT NULL, name TEXT NOT NULL)",
+            "CREATE TABLE members (user_id INTEGER NOT NULL, org TEXT NOT NULL, role TEXT NOT NULL, PRIMARY KEY (user_id, org))",
+            "INSERT INTO users VALUES (1, 'x@a.com', 'x'), (2, 'y@a.com', 'y')",
+            "INSERT INTO members VALUES (1, 'a', 'admin'), (1, 'b', 'viewer'), (2, 'a', 'viewer')",
+        ])
+        .await
+    }
+
+    #[tokio::test]
+    async fn test_primary_key() -> Result<(), sqlx::Error> {
+        let pool = setup().await?;
+
+        assert_eq!(User::find_by_pk(&1, &pool).await?.map(|x| x.name), Some("x".to_string()));
+        assert_eq!(User::find_by_pk(&9, &pool).await?, None);
+        assert_eq!(User::get_by_pk(&2, &pool).await?.email, "y@a.com");
+        assert!(matches!(User::get_by_pk(&9, &pool).await, Err(sqlx::Error::RowNotFound)));
+        assert!(User::exists_by_pk(&1, &pool).await?);
+        assert!(!User::exists_by_pk(&9, &pool).await?);
+
+        let mut user = User::get_by_pk(&1, &pool).await?;
+        let mut edited = user.clone();
+        edited.name = "edited".to_string();
+        assert_eq!(User::update_by_pk(&1, &edited, &pool).await?, 1);
+        user.reload(&pool).await?;
+        assert_eq!(user, edited);
+
+        assert_eq!(User::delete_by_pk(&1, &pool).await?, 1);
+        assert!(matches!(user.reload(&pool).await, Err(sqlx::Error::RowNotFound)));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_composite_primary_key() -> Result<(), sqlx::Error> {
+        let pool = setup().await?;
+
+        // The key fields are sorted by name like `by` fields
+        let mut member = Member::get_by_pk("b", &1, &pool).await?;
+        assert_eq!(member.role, "viewer");
+        assert!(Member::find_by_pk("b", &2, &pool).await?.is_none());
+        assert!(Member::exists_by_pk("a", &2, &pool).await?);
+
+        let mut edited = member.clone();
+        edited.role = "admin".to_string();
+        assert_eq!(Member::update_by_pk("b", &1, &edited, &pool).await?, 1);
+        member.reload(&pool).await?;
+        assert_eq!(member.role, "admin");
+        assert_eq!(Member::get_by_pk("a", &2, &pool).await?.role, "viewer");
+
+        assert_eq!(Member::delete_by_pk("a", &1, &pool).await?, 1);
+        assert!(!Member::exists_by_pk("a", &1, &pool).await?);
+        assert!(Member::exists_by_pk("b", &1, &pool).await?);
+        Ok(())
+    }
+
+}
+
+pub mod relation {
+    use sqlx_template::SqliteTemplate;
+    use sqlx::FromRow;
+    use super::common;
+
+    #[derive(SqliteTemplate, FromRow, Debug, Clone, PartialEq)]
+    #[table("organizations")]
+    #[tp_has_many(User, fk = "org")]
+    pub struct Organization {
+        #[primary_key]
+        pub id: i32,
+        pub name: String,
+    }
+
+    #[derive(SqliteTemplate, FromRow, Debug, Clone, PartialEq)]
+    #[table("users")]
+    #[tp_belongs_to(Organization, fk = "org")]
+    #[tp_belongs_to(User, fk = "manager_id")]
+    #[tp_has_many(Order, fk = "user_id")]
+    pub struct User {
+        pub id: i32,
+        pub org: i32,
+        pub manager_id: Option<i32>,
+    }
+
+    #[derive(SqliteTemplate, FromRow, Debug, Clone, PartialEq)]
+    #[table("orders")]
+    pub struct Order {
+        pub id: i32,
+        pub user_id: i32,
+    }
+
+    #[derive(SqliteTemplate, FromRow, Debug, Clone, PartialEq)]
+    #[table("projects")]
+    #[tp_tenant(column = "tenant_id")]
+    #[tp_has_many(Task, fk = "project_id")]
+    #[tp_has_many(Task, fk = "project_id", unscoped = true)]
+    pub struct Project {
+        pub id: i32,
+        pub tenant_id: i32,
+    }
+
+    #[derive(SqliteTemplate, FromRow, Debug, Clone, PartialEq)]
+    #[table("tasks")]
+    #[tp_tenant(column = "tenant_id")]
+    #[tp_soft_delete(column = "deleted_at")]
+    #[tp_belongs_to(Project, fk = "project_id")]
+    pub struct Task {
+        pub id: i32,
+        pub tenant_id: i32,
+        pub project_id: i32,
+        pub deleted_at: Option<String>,
+    }
+
+    // Related by other columns than the primary key
+    #[derive(SqliteTemplate, FromRow, Debug, Clone, PartialEq)]
+    #[table("teams")]
+    pub struct Team {
+        pub code: String,
+    }
+
+    #[derive(SqliteTemplate, FromRow, Debug, Clone, PartialEq)]
+    #[table("players")]
+    #[tp_belongs_to(Team, fk = "team_code", references = "code", name = "team")]
+    #[tp_has_many(Player, fk = "team_code", key = "team_code", name = "teammates")]
+    pub struct Player {
+        pub id: i32,
+        pub team_code: String,
+    }
+
+    async fn setup() -> Result<sqlx::SqlitePool, sqlx::Error> {
+        common::sqlite(&[
+            "CREATE TABLE organizations (id INTEGER PRIMARY KEY, name TEXT NOT NULL)",
+            "CREATE TABLE users (id INTEGER PRIMARY KEY, org INTEGER NOT NULL, manager_id INTEGER)",
+            "CREATE TABLE orders (id INTEGER PRIMARY KEY, user_id INTEGER NOT NULL)",
+            "CREATE TABLE projects (id INTEGER PRIMARY KEY, tenant_id INTEGER NOT NULL)",
+            "CREATE TABLE tasks (id INTEGER PRIMARY KEY, tenant_id INTEGER NOT NULL, project_id INTEGER NOT NULL, deleted_at TEXT)",
+            "CREATE TABLE teams (code TEXT PRIMARY KEY)",
+            "CREATE TABLE players (id INTEGER PRIMARY KEY, team_code TEXT NOT NULL)",
+            "INSERT INTO organizations VALUES (1, 'a'), (2, 'b'), (3, 'c')",
+            "INSERT INTO users VALUES (1, 1, NULL), (2, 1, 1), (3, 2, 1)",
+            "INSERT INTO orders VALUES (1, 1), (2, 1), (3, 3)",
+            "INSERT INTO projects VALUES (1, 1), (2, 2)",
+            "INSERT INTO tasks VALUES (1, 1, 1, NULL), (2, 1, 1, '2024-01-01'), (3, 2, 1, NULL), (4, 2, 2, NULL)",
+            "INSERT INTO teams VALUES ('a'), ('b')",
+            "INSERT INTO players VALUES (1, 'a'), (2, 'a'), (3, 'c')",
+        ])
+        .await
+    }
+
+    #[tokio::test]
+    async fn test_belongs_to() -> Result<(), sqlx::Error> {
+        let pool = setup().await?;
+        let users = User::find_all(&pool).await?;
+
+        assert_eq!(users[2].load_org(&pool).await?.map(|x| x.name), Some("b".to_string()));
+        assert_eq!(users[0].load_manager(&pool).await?, None);
+        assert_eq!(users[1].load_manager(&pool).await?, Some(users[0].clone()));
+
+        let orgs = User::load_orgs_for(&users, &pool).await?;
+        assert_eq!(orgs.len(), 2);
+        assert_eq!(orgs[&1].name, "a");
+        assert_eq!(orgs[&2].name, "b");
+
+        let managers = User::load_managers_for(&users, &pool).await?;
+        assert_eq!(managers.keys().collect::<Vec<_>>(), vec![&1]);
+        assert!(User::load_orgs_for(&[], &pool).await?.is_empty());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_has_many() -> Result<(), sqlx::Error> {
+        let pool = setup().await?;
+        let users = User::find_all(&pool).await?;
+
+        assert_eq!(users[0].load_orders(&pool).await?.len(), 2);
+        assert!(users[1].load_orders(&pool).await?.is_empty());
+
+        let orders = User::load_orders_for(&users, &pool).await?;
+        assert_eq!(orders[&1].iter().map(|x| x.id).collect::<Vec<_>>(), vec![1, 2]);
+        assert_eq!(orders[&3], vec![Order { id: 3, user_id: 3 }]);
+        assert!(!orders.contains_key(&2));
+
+        // The key defaults to the primary key
+        let orgs = Organization::find_all(&pool).await?;
+        let users = Organization::load_users_for(&orgs, &pool).await?;
+        assert_eq!(users[&1].len(), 2);
+        assert!(!users.contains_key(&3));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_relation_chunks() -> Result<(), sqlx::Error> {
+        let pool = setup().await?;
+        // More keys than the bind parameter limit of SQLite, loaded in several queries
+        let users = (1..=40000).map(|id| User { id, org: id, manager_id: None }).collect::<Vec<_>>();
+        let mut conn = pool.acquire().await?;
+
+        let orgs = User::load_orgs_for(&users, &mut *conn).await?;
+        assert_eq!(orgs.len(), 3);
+        let orders = User::load_orders_for(&users, &mut *conn).await?;
+        assert_eq!((orders[&1].len(), orders[&3].len()), (2, 1));
+
+        let projects = (1..=40000).map(|id| Project { id, tenant_id: 1 }).collect::<Vec<_>>();
+        let tasks = Project::load_tasks_for(&projects, &2, &mut *conn).await?;
+        assert_eq!((tasks[&1].len(), tasks[&2].len()), (1, 1));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_scoped_relations() -> Result<(), sqlx::Error> {
+        let pool = setup().await?;
+        let projects = vec![Project { id: 1, tenant_id: 1 }, Project { id: 2, tenant_id: 2 }];
+
+        // The deleted task 2 and the task 3 of another tenant are skipped
+        let tasks = projects[0].load_tasks(&1, &pool).await?;
+        assert_eq!(tasks.iter().map(|x| x.id).collect::<Vec<_>>(), vec![1]);
+        let tasks = Project::load_tasks_for(&projects, &1, &pool).await?;
+        assert_eq!(tasks[&1].iter().map(|x| x.id).collect::<Vec<_>>(), vec![1]);
+        assert!(!tasks.contains_key(&2));
+
+        // Out of the tenant scope only the deleted task is skipped
+        let tasks = projects[0].unscoped_load_tasks(&pool).await?;
+        assert_eq!(tasks.iter().map(|x| x.id).collect::<Vec<_>>(), vec![1, 3]);
+        let tasks = Project::unscoped_load_tasks_for(&projects, &pool).await?;
+        assert_eq!(tasks[&2].iter().map(|x| x.id).collect::<Vec<_>>(), vec![4]);
+
+        let tasks = Task::find_all(&2, &pool).await?;
+        assert_eq!(tasks[0].load_project(&2, &pool).await?, None);
+        assert_eq!(tasks[1].load_project(&2, &pool).await?, Some(projects[1].clone()));
+        assert_eq!(Task::load_projects_for(&tasks, &2, &pool).await?.len(), 1);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_relation_keys() -> Result<(), sqlx::Error> {
+        let pool = setup().await?;
+        let players = Player::find_all(&pool).await?;
+
+        assert_eq!(players[0].load_team(&pool).await?, Some(Team { code: "a".to_string() }));
+        assert!(players[2].load_team(&pool).await?.is_none());
+        assert_eq!(Player::load_teams_for(&players, &pool).await?.len(), 1);
+
+        let teammates = players[1].load_teammates(&pool).await?;
+        assert_eq!(teammates.iter().map(|x| x.id).collect::<Vec<_>>(), vec![1, 2]);
+        let teammates = Player::load_teammates_for(&players, &pool).await?;
+        assert_eq!((teammates["a"].len(), teammates["c"].len()), (2, 1));
+        Ok(())
+    }
+}